@@ -0,0 +1,66 @@
+//! Importing a human's in-progress local branch as a `Ralph` loop, so
+//! loopr can take over finishing or fixing work someone already started
+//! by hand instead of only running loops it spawned itself.
+
+use crate::domain::{LoopRecord, LoopType};
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// Adopts `branch`, an existing local branch/worktree carrying
+/// in-progress human work, as a `Ralph` loop with `goal` as its
+/// description and `validation_command` as the gate its iterations must
+/// pass. Unlike [`crate::manual::create_manual_loop`], an adopted loop
+/// needs no `Phase` parent: the work it's taking over didn't come from
+/// loopr's own hierarchy, though `parent_id` may still link it under one
+/// if the human branch was already tracked against a plan.
+pub fn adopt_branch(
+    storage: &dyn Storage,
+    branch: impl Into<String>,
+    goal: impl Into<String>,
+    validation_command: impl Into<String>,
+    parent_id: Option<Uuid>,
+) -> anyhow::Result<LoopRecord> {
+    let branch = branch.into();
+    if branch.trim().is_empty() {
+        anyhow::bail!("a branch name is required to adopt it as a loop");
+    }
+    let record = LoopRecord::new(LoopType::Ralph, parent_id, goal).with_adopted_branch(branch).with_validation_command(validation_command);
+    storage.save_loop(record.clone())?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn adopts_a_branch_with_no_parent() {
+        let storage = InMemoryStorage::new();
+        let record = adopt_branch(&storage, "fix/rate-limit-bug", "finish the rate limit fix", "cargo test", None).unwrap();
+        assert_eq!(record.loop_type, LoopType::Ralph);
+        assert_eq!(record.parent_id, None);
+        assert_eq!(record.description, "finish the rate limit fix");
+        assert_eq!(record.adopted_branch, Some("fix/rate-limit-bug".to_string()));
+        assert_eq!(record.validation_command, Some("cargo test".to_string()));
+    }
+
+    #[test]
+    fn adopts_a_branch_under_an_existing_plan() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship feature x");
+        let plan_id = plan.id;
+        storage.save_loop(plan).unwrap();
+
+        let record = adopt_branch(&storage, "feature-x-wip", "finish feature x", "cargo test", Some(plan_id)).unwrap();
+        assert_eq!(record.parent_id, Some(plan_id));
+        assert_eq!(storage.get_loop(record.id).unwrap().unwrap().adopted_branch, Some("feature-x-wip".to_string()));
+    }
+
+    #[test]
+    fn an_empty_branch_name_is_refused() {
+        let storage = InMemoryStorage::new();
+        let err = adopt_branch(&storage, "  ", "finish it", "cargo test", None).unwrap_err();
+        assert!(err.to_string().contains("branch name is required"));
+    }
+}
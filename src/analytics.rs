@@ -0,0 +1,128 @@
+//! Aggregate reporting over completed loops: average iterations by type,
+//! the most common failure categories, and cost per merged change.
+//! Backs the TUI dashboard panel; the CLI has no `loopr stats` command
+//! that prints these reports.
+
+use crate::domain::{FailureCategory, LoopStatus, LoopType};
+use crate::storage::Storage;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub avg_iterations_by_type: HashMap<LoopType, f64>,
+    pub failure_category_counts: HashMap<FailureCategory, u32>,
+    pub total_cost_usd: f64,
+    pub cost_per_merged_change: f64,
+    /// Total cost of iterations served by each model, including
+    /// [`crate::llm::FallbackLlmClient`] fallbacks; iterations that
+    /// predate per-iteration model tracking are excluded.
+    pub cost_by_model: HashMap<String, f64>,
+    /// How many times each named validation gate failed in a loop's last
+    /// recorded run, keyed by [`crate::validation::GateSummary::name`];
+    /// surfaces which gate most often blocks a loop from completing.
+    pub gate_failure_counts: HashMap<String, u32>,
+}
+
+/// Builds a [`Report`] from every loop in storage: per-type iteration
+/// averages, failure category counts across all iterations, and the
+/// amortized cost of each `Completed` loop.
+pub fn build_report(storage: &dyn Storage) -> anyhow::Result<Report> {
+    let loops = storage.list_loops()?;
+
+    let mut iterations_by_type: HashMap<LoopType, Vec<usize>> = HashMap::new();
+    let mut failure_category_counts: HashMap<FailureCategory, u32> = HashMap::new();
+    let mut total_cost_usd = 0.0;
+    let mut merged_count = 0u32;
+    let mut cost_by_model: HashMap<String, f64> = HashMap::new();
+    let mut gate_failure_counts: HashMap<String, u32> = HashMap::new();
+
+    for record in &loops {
+        iterations_by_type.entry(record.loop_type.clone()).or_default().push(record.iterations.len());
+        if record.status == LoopStatus::Completed {
+            merged_count += 1;
+        }
+        for iteration in &record.iterations {
+            total_cost_usd += iteration.cost_usd;
+            if let Some(category) = iteration.failure_category {
+                *failure_category_counts.entry(category).or_insert(0) += 1;
+            }
+            if let Some(model) = &iteration.model {
+                *cost_by_model.entry(model.clone()).or_insert(0.0) += iteration.cost_usd;
+            }
+        }
+        for gate in &record.last_gate_results {
+            if !gate.passed {
+                *gate_failure_counts.entry(gate.name.clone()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let avg_iterations_by_type = iterations_by_type
+        .into_iter()
+        .map(|(loop_type, counts)| {
+            let avg = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+            (loop_type, avg)
+        })
+        .collect();
+
+    let cost_per_merged_change = if merged_count == 0 { 0.0 } else { total_cost_usd / merged_count as f64 };
+
+    Ok(Report {
+        avg_iterations_by_type,
+        failure_category_counts,
+        total_cost_usd,
+        cost_per_merged_change,
+        cost_by_model,
+        gate_failure_counts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord};
+    use crate::storage::InMemoryStorage;
+    use crate::validation::GateSummary;
+
+    #[test]
+    fn aggregates_iterations_and_cost() {
+        let storage = InMemoryStorage::new();
+
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.status = LoopStatus::Completed;
+        let mut a = Iteration::new(0);
+        a.cost_usd = 1.0;
+        a.failure_category = Some(FailureCategory::CompileError);
+        a.model = Some("claude-sonnet".to_string());
+        let b = Iteration::new(1);
+        record.iterations = vec![a, b];
+        storage.save_loop(record).unwrap();
+
+        let report = build_report(&storage).unwrap();
+        assert_eq!(report.avg_iterations_by_type[&LoopType::Ralph], 2.0);
+        assert_eq!(report.failure_category_counts[&FailureCategory::CompileError], 1);
+        assert_eq!(report.total_cost_usd, 1.0);
+        assert_eq!(report.cost_per_merged_change, 1.0);
+        assert_eq!(report.cost_by_model["claude-sonnet"], 1.0);
+    }
+
+    #[test]
+    fn tallies_gate_failures_from_each_loop_s_last_run() {
+        let storage = InMemoryStorage::new();
+
+        let mut a = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        a.last_gate_results = vec![
+            GateSummary { name: "build".to_string(), passed: true, duration_ms: 100, first_failure_line: None },
+            GateSummary { name: "test".to_string(), passed: false, duration_ms: 200, first_failure_line: None },
+        ];
+        storage.save_loop(a).unwrap();
+
+        let mut b = LoopRecord::new(LoopType::Ralph, None, "fix it too");
+        b.last_gate_results = vec![GateSummary { name: "test".to_string(), passed: false, duration_ms: 150, first_failure_line: None }];
+        storage.save_loop(b).unwrap();
+
+        let report = build_report(&storage).unwrap();
+        assert_eq!(report.gate_failure_counts["test"], 2);
+        assert_eq!(report.gate_failure_counts.get("build"), None);
+    }
+}
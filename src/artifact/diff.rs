@@ -0,0 +1,64 @@
+/// A minimal line-oriented diff between two artifact versions, good enough
+/// for a reviewer glancing at what an "iterate with feedback" pass changed.
+/// Not a general-purpose diff algorithm (no move/rename detection) — just
+/// longest-common-subsequence-free line classification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+pub fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+
+    let mut result = Vec::new();
+    let mut b = 0;
+    let mut a = 0;
+    while b < before_lines.len() && a < after_lines.len() {
+        if before_lines[b] == after_lines[a] {
+            result.push(DiffLine::Unchanged(before_lines[b].to_string()));
+            b += 1;
+            a += 1;
+        } else if !after_lines[a..].contains(&before_lines[b]) {
+            result.push(DiffLine::Removed(before_lines[b].to_string()));
+            b += 1;
+        } else {
+            result.push(DiffLine::Added(after_lines[a].to_string()));
+            a += 1;
+        }
+    }
+    result.extend(before_lines[b..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    result.extend(after_lines[a..].iter().map(|l| DiffLine::Added(l.to_string())));
+    result
+}
+
+/// Renders a [`diff_lines`] result as a unified-diff-style string with
+/// `+`/`-`/` ` prefixes.
+pub fn render_diff(before: &str, after: &str) -> String {
+    diff_lines(before, after)
+        .into_iter()
+        .map(|line| match line {
+            DiffLine::Unchanged(text) => format!("  {text}"),
+            DiffLine::Added(text) => format!("+ {text}"),
+            DiffLine::Removed(text) => format!("- {text}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_added_and_removed_lines() {
+        let before = "one\ntwo\nthree";
+        let after = "one\ntwo changed\nthree";
+        let rendered = render_diff(before, after);
+        assert!(rendered.contains("- two"));
+        assert!(rendered.contains("+ two changed"));
+        assert!(rendered.contains("  one"));
+    }
+}
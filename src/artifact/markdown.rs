@@ -0,0 +1,90 @@
+use super::{Phase, Plan, Spec};
+
+/// Scans a markdown plan document heading-by-heading: `#` is the plan
+/// title, `##` starts a spec, `###` starts a phase within the current
+/// spec. Body text under a heading becomes that node's description.
+pub fn parse(content: &str) -> anyhow::Result<Plan> {
+    let mut plan = Plan::default();
+    let mut current_spec: Option<Spec> = None;
+    let mut current_phase: Option<Phase> = None;
+    let mut buffer = String::new();
+
+    for line in content.lines() {
+        if let Some(title) = line.strip_prefix("# ") {
+            plan.title = title.trim().to_string();
+        } else if let Some(name) = line.strip_prefix("## ") {
+            flush_phase(&mut current_spec, &mut current_phase, &mut buffer);
+            flush_spec(&mut plan, &mut current_spec);
+            current_spec = Some(Spec {
+                name: name.trim().to_string(),
+                description: String::new(),
+                phases: Vec::new(),
+            });
+        } else if let Some(name) = line.strip_prefix("### ") {
+            flush_phase(&mut current_spec, &mut current_phase, &mut buffer);
+            current_phase = Some(Phase {
+                name: name.trim().to_string(),
+                description: String::new(),
+            });
+        } else {
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+    }
+    flush_phase(&mut current_spec, &mut current_phase, &mut buffer);
+    flush_spec(&mut plan, &mut current_spec);
+
+    Ok(plan)
+}
+
+fn flush_phase(spec: &mut Option<Spec>, phase: &mut Option<Phase>, buffer: &mut String) {
+    if let Some(mut phase) = phase.take() {
+        phase.description = buffer.trim().to_string();
+        if let Some(spec) = spec.as_mut() {
+            spec.phases.push(phase);
+        }
+    } else if let Some(spec) = spec.as_mut() {
+        if spec.description.is_empty() {
+            spec.description = buffer.trim().to_string();
+        }
+    }
+    buffer.clear();
+}
+
+fn flush_spec(plan: &mut Plan, spec: &mut Option<Spec>) {
+    if let Some(spec) = spec.take() {
+        plan.specs.push(spec);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_specs_and_phases() {
+        let content = "\
+# Add login flow
+
+## Spec: auth
+
+Handle the backend auth work.
+
+### Phase: session tokens
+
+Issue and verify JWTs.
+
+### Phase: rate limiting
+
+Throttle repeated attempts.
+";
+        let plan = parse(content).unwrap();
+        assert_eq!(plan.title, "Add login flow");
+        assert_eq!(plan.specs.len(), 1);
+        let spec = &plan.specs[0];
+        assert_eq!(spec.name, "Spec: auth");
+        assert_eq!(spec.description, "Handle the backend auth work.");
+        assert_eq!(spec.phases.len(), 2);
+        assert_eq!(spec.phases[0].name, "Phase: session tokens");
+    }
+}
@@ -0,0 +1,137 @@
+//! Parsing of the artifacts (plan/spec/phase documents) an LLM produces,
+//! into typed data that the scheduler spawns children from.
+
+mod diff;
+mod markdown;
+mod structured;
+
+pub use diff::{diff_lines, render_diff, DiffLine};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A single unit of work within a `Spec`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Phase {
+    pub name: String,
+    pub description: String,
+}
+
+/// A group of related phases within a `Plan`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spec {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub phases: Vec<Phase>,
+}
+
+/// The typed result of parsing a plan artifact, regardless of the source
+/// format it was written in.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Plan {
+    pub title: String,
+    #[serde(default)]
+    pub specs: Vec<Spec>,
+}
+
+/// The artifact formats loopr knows how to parse into a [`Plan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactFormat {
+    Markdown,
+    Yaml,
+    Json,
+}
+
+impl ArtifactFormat {
+    /// Resolves a format from a file extension (without the leading dot).
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Parses artifact `content` written in `format` into a typed [`Plan`].
+/// Structured formats (YAML/JSON) deserialize directly against the
+/// [`Plan`] schema; markdown is scanned heading-by-heading.
+pub fn parse_artifact(format: ArtifactFormat, content: &str) -> anyhow::Result<Plan> {
+    match format {
+        ArtifactFormat::Markdown => markdown::parse(content),
+        ArtifactFormat::Yaml => structured::parse_yaml(content),
+        ArtifactFormat::Json => structured::parse_json(content),
+    }
+}
+
+/// The JSON schema a [`Plan`] conforms to, for requesting structured
+/// output from an LLM via `CompletionRequest::with_response_schema`
+/// instead of free-form markdown.
+pub fn plan_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "required": ["title", "specs"],
+        "properties": {
+            "title": { "type": "string" },
+            "specs": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "description"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "description": { "type": "string" },
+                        "phases": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["name", "description"],
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "description": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Parses an LLM's JSON response into a [`Plan`], first checking it
+/// against `schema` so a malformed structured-output response fails with
+/// a clear error rather than an opaque deserialize error.
+pub fn parse_structured_plan(schema: &serde_json::Value, content: &str) -> anyhow::Result<Plan> {
+    crate::llm::schema::validate(schema, content)?;
+    parse_artifact(ArtifactFormat::Json, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_format_from_extension() {
+        assert_eq!(ArtifactFormat::from_extension("md"), Some(ArtifactFormat::Markdown));
+        assert_eq!(ArtifactFormat::from_extension("yml"), Some(ArtifactFormat::Yaml));
+        assert_eq!(ArtifactFormat::from_extension("json"), Some(ArtifactFormat::Json));
+        assert_eq!(ArtifactFormat::from_extension("txt"), None);
+    }
+
+    #[test]
+    fn parses_a_structured_plan_that_satisfies_the_schema() {
+        let content = r#"{"title": "add login", "specs": [{"name": "backend", "description": "add the endpoint"}]}"#;
+        let plan = parse_structured_plan(&plan_schema(), content).unwrap();
+        assert_eq!(plan.title, "add login");
+        assert_eq!(plan.specs[0].name, "backend");
+    }
+
+    #[test]
+    fn rejects_a_structured_plan_missing_a_required_field() {
+        let content = r#"{"specs": []}"#;
+        assert!(parse_structured_plan(&plan_schema(), content).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use super::Plan;
+
+/// Parses a plan written directly as YAML against the [`Plan`] schema,
+/// used when a project generates structured plans instead of prose.
+pub fn parse_yaml(content: &str) -> anyhow::Result<Plan> {
+    Ok(serde_yaml::from_str(content)?)
+}
+
+/// Parses a plan written directly as JSON against the [`Plan`] schema.
+pub fn parse_json(content: &str) -> anyhow::Result<Plan> {
+    Ok(serde_json::from_str(content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_plan() {
+        let yaml = "\
+title: Add login flow
+specs:
+  - name: auth
+    description: Handle the backend auth work.
+    phases:
+      - name: session tokens
+        description: Issue and verify JWTs.
+";
+        let plan = parse_yaml(yaml).unwrap();
+        assert_eq!(plan.title, "Add login flow");
+        assert_eq!(plan.specs[0].phases[0].name, "session tokens");
+    }
+
+    #[test]
+    fn parses_json_plan() {
+        let json = r#"{"title": "Add login flow", "specs": []}"#;
+        let plan = parse_json(json).unwrap();
+        assert_eq!(plan.title, "Add login flow");
+    }
+}
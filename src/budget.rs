@@ -0,0 +1,148 @@
+//! Per-project spending caps: once the cost projection crosses the
+//! configured daily or per-plan limit, the scheduler throttles down to
+//! loops labeled "critical" and stays throttled until an operator
+//! explicitly overrides it with `loopr budget override`.
+
+use crate::domain::LoopRecord;
+use serde::{Deserialize, Serialize};
+
+/// Spending limits configured for a project.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BudgetConfig {
+    pub max_usd_per_day: Option<f64>,
+    pub max_usd_per_plan: Option<f64>,
+}
+
+/// Which cap a projected spend crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BudgetScope {
+    Day,
+    Plan,
+}
+
+/// A cap crossing, worded for the budget event pushed to clients.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BudgetViolation {
+    pub scope: BudgetScope,
+    pub projected_usd: f64,
+    pub limit_usd: f64,
+}
+
+/// Checks today's and this plan's projected spend against `config`,
+/// returning every cap it crosses. Checks both rather than stopping at
+/// the first, so an operator sees the full picture in one event.
+pub fn check(config: &BudgetConfig, projected_usd_per_day: f64, projected_usd_per_plan: f64) -> Vec<BudgetViolation> {
+    let mut violations = Vec::new();
+    if let Some(limit_usd) = config.max_usd_per_day {
+        if projected_usd_per_day > limit_usd {
+            violations.push(BudgetViolation { scope: BudgetScope::Day, projected_usd: projected_usd_per_day, limit_usd });
+        }
+    }
+    if let Some(limit_usd) = config.max_usd_per_plan {
+        if projected_usd_per_plan > limit_usd {
+            violations.push(BudgetViolation { scope: BudgetScope::Plan, projected_usd: projected_usd_per_plan, limit_usd });
+        }
+    }
+    violations
+}
+
+/// Loops carrying this label keep running once the budget trips, for
+/// work an operator has judged too important to wait on an override.
+pub const CRITICAL_LABEL: &str = "critical";
+
+/// Whether the scheduler is letting new iterations start, or holding
+/// everything but critical loops until an operator overrides it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleState {
+    Normal,
+    Throttled,
+}
+
+/// Tracks whether a spending cap has tripped for the project.
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetThrottle {
+    pub state: ThrottleState,
+}
+
+impl Default for BudgetThrottle {
+    fn default() -> Self {
+        Self { state: ThrottleState::Normal }
+    }
+}
+
+impl BudgetThrottle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trips the throttle once a cap has been crossed; stays tripped
+    /// until [`Self::override_throttle`] is called, even if spend later
+    /// dips back under the cap.
+    pub fn trip(&mut self) {
+        self.state = ThrottleState::Throttled;
+    }
+
+    /// The operator override that lets new iterations resume despite the
+    /// cap still being crossed.
+    pub fn override_throttle(&mut self) {
+        self.state = ThrottleState::Normal;
+    }
+
+    /// Whether `record` may start a new iteration given the current
+    /// throttle state.
+    pub fn may_start(&self, record: &LoopRecord) -> bool {
+        self.state == ThrottleState::Normal || record.has_label(CRITICAL_LABEL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+
+    #[test]
+    fn no_violation_under_both_caps() {
+        let config = BudgetConfig { max_usd_per_day: Some(100.0), max_usd_per_plan: Some(50.0) };
+        assert!(check(&config, 90.0, 40.0).is_empty());
+    }
+
+    #[test]
+    fn flags_a_daily_cap_crossed() {
+        let config = BudgetConfig { max_usd_per_day: Some(100.0), max_usd_per_plan: None };
+        let violations = check(&config, 150.0, 0.0);
+        assert_eq!(violations, vec![BudgetViolation { scope: BudgetScope::Day, projected_usd: 150.0, limit_usd: 100.0 }]);
+    }
+
+    #[test]
+    fn flags_both_caps_when_both_are_crossed() {
+        let config = BudgetConfig { max_usd_per_day: Some(100.0), max_usd_per_plan: Some(50.0) };
+        let violations = check(&config, 150.0, 75.0);
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn throttle_blocks_ordinary_loops_once_tripped() {
+        let mut throttle = BudgetThrottle::new();
+        let record = LoopRecord::new(LoopType::Ralph, None, "ordinary work");
+        assert!(throttle.may_start(&record));
+        throttle.trip();
+        assert!(!throttle.may_start(&record));
+    }
+
+    #[test]
+    fn throttle_still_lets_critical_labeled_loops_start() {
+        let mut throttle = BudgetThrottle::new();
+        throttle.trip();
+        let record = LoopRecord::new(LoopType::Ralph, None, "incident response").with_labels(vec![CRITICAL_LABEL.to_string()]);
+        assert!(throttle.may_start(&record));
+    }
+
+    #[test]
+    fn override_clears_the_throttle() {
+        let mut throttle = BudgetThrottle::new();
+        throttle.trip();
+        throttle.override_throttle();
+        let record = LoopRecord::new(LoopType::Ralph, None, "ordinary work");
+        assert!(throttle.may_start(&record));
+    }
+}
@@ -0,0 +1,101 @@
+//! Operating on many loops in one call. An operator canceling a dozen
+//! stale Ralph loops shouldn't have to confirm each one individually;
+//! `loop.bulk_action` (see [`crate::ipc::Methods::LOOP_BULK_ACTION`])
+//! takes an explicit id list or a filter-derived one and applies a single
+//! action to all of them, continuing past ids that no longer resolve.
+
+use crate::domain::{LoopRecord, LoopStatus};
+use crate::storage::Storage;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+/// An action [`apply_bulk_action`] can apply to a batch of loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BulkAction {
+    Cancel,
+    Delete,
+}
+
+/// The outcome of a bulk action: which ids were found and acted on, and
+/// which no longer resolved to a loop.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkActionResult {
+    pub applied: Vec<Uuid>,
+    pub missing: Vec<Uuid>,
+}
+
+/// Applies `action` to every id in `ids`, skipping ids that no longer
+/// resolve to a loop instead of aborting the whole batch.
+pub fn apply_bulk_action(storage: &dyn Storage, ids: &[Uuid], action: BulkAction) -> anyhow::Result<BulkActionResult> {
+    let mut result = BulkActionResult::default();
+    for &id in ids {
+        let found = match action {
+            BulkAction::Delete => storage.delete_loop(id)?,
+            BulkAction::Cancel => match storage.get_loop(id)? {
+                Some(mut record) => {
+                    record.status = LoopStatus::Cancelled;
+                    storage.save_loop(record)?;
+                    true
+                }
+                None => false,
+            },
+        };
+        if found {
+            result.applied.push(id);
+        } else {
+            result.missing.push(id);
+        }
+    }
+    Ok(result)
+}
+
+/// Builds a bulk action's id list from a filter expression instead of an
+/// explicit selection, e.g. "every `Ralph` loop that's been `Failed`".
+pub fn select_matching(records: &[LoopRecord], filter: impl Fn(&LoopRecord) -> bool) -> Vec<Uuid> {
+    records.iter().filter(|record| filter(record)).map(|record| record.id).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn cancels_every_applied_id() {
+        let storage = InMemoryStorage::new();
+        let a = LoopRecord::new(LoopType::Ralph, None, "stale a");
+        let b = LoopRecord::new(LoopType::Ralph, None, "stale b");
+        let (a_id, b_id) = (a.id, b.id);
+        storage.save_loop(a).unwrap();
+        storage.save_loop(b).unwrap();
+
+        let result = apply_bulk_action(&storage, &[a_id, b_id], BulkAction::Cancel).unwrap();
+        assert_eq!(result.applied, vec![a_id, b_id]);
+        assert!(result.missing.is_empty());
+        assert_eq!(storage.get_loop(a_id).unwrap().unwrap().status, LoopStatus::Cancelled);
+        assert_eq!(storage.get_loop(b_id).unwrap().unwrap().status, LoopStatus::Cancelled);
+    }
+
+    #[test]
+    fn a_missing_id_is_reported_without_aborting_the_batch() {
+        let storage = InMemoryStorage::new();
+        let a = LoopRecord::new(LoopType::Ralph, None, "stale a");
+        let a_id = a.id;
+        storage.save_loop(a).unwrap();
+        let missing_id = Uuid::new_v4();
+
+        let result = apply_bulk_action(&storage, &[a_id, missing_id], BulkAction::Delete).unwrap();
+        assert_eq!(result.applied, vec![a_id]);
+        assert_eq!(result.missing, vec![missing_id]);
+    }
+
+    #[test]
+    fn select_matching_filters_by_predicate() {
+        let mut failed = LoopRecord::new(LoopType::Ralph, None, "flaky ralph");
+        failed.status = LoopStatus::Failed;
+        let pending = LoopRecord::new(LoopType::Ralph, None, "fresh ralph");
+        let ids = select_matching(&[failed.clone(), pending], |record| record.status == LoopStatus::Failed);
+        assert_eq!(ids, vec![failed.id]);
+    }
+}
@@ -0,0 +1,150 @@
+//! Changelog/completion report generation for a plan tree that completed
+//! and merged: features delivered, files changed, tests added, and total
+//! cost/iterations, assembled into a markdown artifact stored alongside
+//! the plan (via [`crate::storage::Storage::save_artifact_version`]) and
+//! exposed via [`crate::ipc::Methods::LOOP_CHANGELOG`] and the CLI.
+
+use crate::delete::descendants_of;
+use crate::domain::{LoopRecord, LoopStatus};
+use crate::storage::Storage;
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+/// Features delivered, files/tests touched, and total cost/iterations for
+/// a completed plan, suitable for a changelog entry or PR body.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Changelog {
+    pub plan_id: Uuid,
+    pub title: String,
+    pub delivered: Vec<String>,
+    pub files_changed: usize,
+    pub tests_added: usize,
+    pub total_cost_usd: f64,
+    pub total_iterations: usize,
+}
+
+/// Assembles a [`Changelog`] from a completed plan and its descendants:
+/// what was delivered (every descendant's description), and files/tests/
+/// cost/iteration totals aggregated from their iteration history's
+/// [`crate::diff_summary::DiffSummary`]s.
+pub fn build(plan: &LoopRecord, descendants: &[LoopRecord]) -> Changelog {
+    let delivered = descendants.iter().map(|record| record.description.clone()).collect();
+    let iterations: Vec<_> = descendants.iter().flat_map(|record| record.iterations.iter()).collect();
+    let mut files_changed: Vec<&String> = iterations.iter().filter_map(|iteration| iteration.diff_summary.as_ref()).flat_map(|summary| summary.files_changed.iter()).collect();
+    files_changed.sort();
+    files_changed.dedup();
+    let tests_added = iterations.iter().filter_map(|iteration| iteration.diff_summary.as_ref()).map(|summary| summary.tests_added).sum();
+    let total_cost_usd = iterations.iter().map(|iteration| iteration.cost_usd).sum();
+    Changelog { plan_id: plan.id, title: plan.description.clone(), delivered, files_changed: files_changed.len(), tests_added, total_cost_usd, total_iterations: iterations.len() }
+}
+
+/// Renders a [`Changelog`] as markdown, suitable for a changelog entry or
+/// PR body.
+pub fn render(changelog: &Changelog) -> String {
+    let mut rendered = format!("# {}\n\n## Delivered\n\n", changelog.title);
+    for item in &changelog.delivered {
+        rendered.push_str(&format!("- {item}\n"));
+    }
+    rendered.push_str(&format!(
+        "\n## Stats\n\n- {} file(s) changed\n- {} test(s) added\n- {} iteration(s)\n- ${:.2} spent\n",
+        changelog.files_changed, changelog.tests_added, changelog.total_iterations, changelog.total_cost_usd
+    ));
+    rendered
+}
+
+/// Generates a changelog for `plan_id` and persists its rendered markdown
+/// as a new artifact version, refusing if the plan hasn't actually
+/// completed.
+pub fn generate(storage: &dyn Storage, plan_id: Uuid) -> anyhow::Result<Changelog> {
+    let plan = storage.get_loop(plan_id)?.ok_or_else(|| anyhow::anyhow!("no loop with id {plan_id}"))?;
+    if plan.status != LoopStatus::Completed {
+        anyhow::bail!("loop {plan_id} has not completed; refusing to generate a changelog for it");
+    }
+    let descendant_ids = descendants_of(storage, plan_id)?;
+    let descendants: Vec<LoopRecord> = descendant_ids
+        .into_iter()
+        .filter(|id| *id != plan_id)
+        .filter_map(|id| storage.get_loop(id).transpose())
+        .collect::<anyhow::Result<_>>()?;
+
+    let changelog = build(&plan, &descendants);
+    storage.save_artifact_version(plan_id, plan.iterations.len() as u32, render(&changelog))?;
+    Ok(changelog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_summary::DiffSummary;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use crate::storage::InMemoryStorage;
+
+    fn iteration_with_diff(index: u32, files_changed: &[&str], tests_added: usize, cost_usd: f64) -> Iteration {
+        let mut iteration = Iteration::new(index);
+        iteration.diff_summary = Some(DiffSummary { files_changed: files_changed.iter().map(|path| path.to_string()).collect(), tests_added, ..Default::default() });
+        iteration.cost_usd = cost_usd;
+        iteration
+    }
+
+    #[test]
+    fn build_aggregates_delivered_items_and_stats_across_descendants() {
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let mut ralph = LoopRecord::new(LoopType::Ralph, Some(plan.id), "implement session tokens");
+        ralph.iterations = vec![iteration_with_diff(0, &["src/a.rs", "src/b.rs", "src/c.rs"], 2, 0.5), iteration_with_diff(1, &["src/a.rs"], 0, 0.25)];
+        let changelog = build(&plan, &[ralph]);
+        assert_eq!(changelog.delivered, vec!["implement session tokens".to_string()]);
+        assert_eq!(changelog.files_changed, 3);
+        assert_eq!(changelog.tests_added, 2);
+        assert_eq!(changelog.total_iterations, 2);
+        assert_eq!(changelog.total_cost_usd, 0.75);
+    }
+
+    #[test]
+    fn render_includes_every_section() {
+        let changelog = Changelog {
+            plan_id: Uuid::new_v4(),
+            title: "ship login flow".to_string(),
+            delivered: vec!["implement session tokens".to_string()],
+            files_changed: 4,
+            tests_added: 2,
+            total_cost_usd: 0.75,
+            total_iterations: 2,
+        };
+        let rendered = render(&changelog);
+        assert!(rendered.contains("# ship login flow"));
+        assert!(rendered.contains("implement session tokens"));
+        assert!(rendered.contains("4 file(s) changed"));
+        assert!(rendered.contains("2 test(s) added"));
+        assert!(rendered.contains("$0.75"));
+    }
+
+    #[test]
+    fn generate_persists_an_artifact_version_for_a_completed_plan() {
+        let storage = InMemoryStorage::new();
+        let mut plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        plan.status = LoopStatus::Completed;
+        let plan_id = plan.id;
+        let mut spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "Spec: auth");
+        spec.iterations = vec![iteration_with_diff(0, &["src/a.rs", "src/b.rs"], 1, 0.4)];
+        storage.save_loop(plan).unwrap();
+        storage.save_loop(spec).unwrap();
+
+        let changelog = generate(&storage, plan_id).unwrap();
+        assert_eq!(changelog.files_changed, 2);
+
+        let history = storage.artifact_history(plan_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].content.contains("Spec: auth"));
+    }
+
+    #[test]
+    fn generate_refuses_a_plan_that_has_not_completed() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let plan_id = plan.id;
+        storage.save_loop(plan).unwrap();
+
+        let err = generate(&storage, plan_id).unwrap_err();
+        assert!(err.to_string().contains("has not completed"));
+    }
+}
@@ -0,0 +1,306 @@
+//! Fault injection for resilience testing. When enabled (via config or
+//! env, see [`ChaosConfig::from_env`]), randomly injects LLM errors, tool
+//! timeouts, storage errors, and daemon restarts during loop execution,
+//! so recovery and checkpointing paths are exercised continuously rather
+//! than only when a real incident happens to hit them.
+//!
+//! Randomness is a seeded counter rather than a real RNG, so a chaos run
+//! is reproducible from its seed — useful when a fault-induced failure
+//! needs to be reproduced outside of CI.
+
+use crate::domain::LoopRecord;
+use crate::llm::{CompletionRequest, CompletionResponse, LlmClient};
+use crate::storage::{ArtifactVersion, AuditEntry, ChatSessionRecord, Storage, ToolJobRecord, TranscriptEntry};
+use async_trait::async_trait;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Injection rates, one per fault kind. All zero and `enabled: false` by
+/// default, so chaos mode is strictly opt-in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    pub llm_error_rate: f64,
+    pub tool_timeout_rate: f64,
+    pub storage_error_rate: f64,
+    pub daemon_restart_rate: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            llm_error_rate: 0.0,
+            tool_timeout_rate: 0.0,
+            storage_error_rate: 0.0,
+            daemon_restart_rate: 0.0,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Reads `LOOPR_CHAOS_ENABLED` and the four `LOOPR_CHAOS_*_RATE`
+    /// variables, falling back to [`ChaosConfig::default`] for anything
+    /// unset or unparseable.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            enabled: std::env::var("LOOPR_CHAOS_ENABLED").map(|v| v == "1" || v == "true").unwrap_or(default.enabled),
+            llm_error_rate: read_rate("LOOPR_CHAOS_LLM_ERROR_RATE", default.llm_error_rate),
+            tool_timeout_rate: read_rate("LOOPR_CHAOS_TOOL_TIMEOUT_RATE", default.tool_timeout_rate),
+            storage_error_rate: read_rate("LOOPR_CHAOS_STORAGE_ERROR_RATE", default.storage_error_rate),
+            daemon_restart_rate: read_rate("LOOPR_CHAOS_DAEMON_RESTART_RATE", default.daemon_restart_rate),
+        }
+    }
+}
+
+fn read_rate(key: &str, default: f64) -> f64 {
+    std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// What kind of fault was injected, used to tag the error surfaced to
+/// whichever recovery path is supposed to catch it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    LlmError,
+    ToolTimeout,
+    StorageError,
+    DaemonRestart,
+}
+
+impl FaultKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FaultKind::LlmError => "llm_error",
+            FaultKind::ToolTimeout => "tool_timeout",
+            FaultKind::StorageError => "storage_error",
+            FaultKind::DaemonRestart => "daemon_restart",
+        }
+    }
+}
+
+/// Rolls injection decisions against a [`ChaosConfig`] using a seeded
+/// splitmix64 counter, so the same seed always injects the same faults
+/// in the same order.
+pub struct ChaosInjector {
+    config: ChaosConfig,
+    state: u64,
+}
+
+impl ChaosInjector {
+    pub fn new(config: ChaosConfig, seed: u64) -> Self {
+        Self { config, state: seed }
+    }
+
+    fn next_unit(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn roll(&mut self, rate: f64, kind: FaultKind) -> Option<FaultKind> {
+        if self.config.enabled && rate > 0.0 && self.next_unit() < rate {
+            Some(kind)
+        } else {
+            None
+        }
+    }
+
+    pub fn maybe_llm_error(&mut self) -> Option<FaultKind> {
+        self.roll(self.config.llm_error_rate, FaultKind::LlmError)
+    }
+
+    pub fn maybe_tool_timeout(&mut self) -> Option<FaultKind> {
+        self.roll(self.config.tool_timeout_rate, FaultKind::ToolTimeout)
+    }
+
+    pub fn maybe_storage_error(&mut self) -> Option<FaultKind> {
+        self.roll(self.config.storage_error_rate, FaultKind::StorageError)
+    }
+
+    pub fn maybe_daemon_restart(&mut self) -> Option<FaultKind> {
+        self.roll(self.config.daemon_restart_rate, FaultKind::DaemonRestart)
+    }
+}
+
+/// Wraps an [`LlmClient`], injecting an error before delegating to `inner`
+/// whenever the chaos roll fires.
+pub struct ChaosLlmClient<C: LlmClient> {
+    inner: C,
+    injector: Mutex<ChaosInjector>,
+}
+
+impl<C: LlmClient> ChaosLlmClient<C> {
+    pub fn new(inner: C, config: ChaosConfig, seed: u64) -> Self {
+        Self { inner, injector: Mutex::new(ChaosInjector::new(config, seed)) }
+    }
+}
+
+#[async_trait]
+impl<C: LlmClient> LlmClient for ChaosLlmClient<C> {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+        let fault = self.injector.lock().unwrap().maybe_llm_error();
+        if let Some(fault) = fault {
+            anyhow::bail!("injected fault: {}", fault.as_str());
+        }
+        self.inner.complete(request).await
+    }
+}
+
+/// Wraps a [`Storage`], injecting a storage error before delegating to
+/// `inner` whenever the chaos roll fires, the same way [`ChaosLlmClient`]
+/// wraps an [`LlmClient`].
+pub struct ChaosStorage<S: Storage> {
+    inner: S,
+    injector: Mutex<ChaosInjector>,
+}
+
+impl<S: Storage> ChaosStorage<S> {
+    pub fn new(inner: S, config: ChaosConfig, seed: u64) -> Self {
+        Self { inner, injector: Mutex::new(ChaosInjector::new(config, seed)) }
+    }
+
+    fn maybe_fail(&self) -> anyhow::Result<()> {
+        if let Some(fault) = self.injector.lock().unwrap().maybe_storage_error() {
+            anyhow::bail!("injected fault: {}", fault.as_str());
+        }
+        Ok(())
+    }
+}
+
+impl<S: Storage> Storage for ChaosStorage<S> {
+    fn save_loop(&self, record: LoopRecord) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_loop(record)
+    }
+
+    fn get_loop(&self, id: Uuid) -> anyhow::Result<Option<LoopRecord>> {
+        self.maybe_fail()?;
+        self.inner.get_loop(id)
+    }
+
+    fn list_loops(&self) -> anyhow::Result<Vec<LoopRecord>> {
+        self.maybe_fail()?;
+        self.inner.list_loops()
+    }
+
+    fn delete_loop(&self, id: Uuid) -> anyhow::Result<bool> {
+        self.maybe_fail()?;
+        self.inner.delete_loop(id)
+    }
+
+    fn save_artifact_version(&self, loop_id: Uuid, iteration: u32, content: String) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_artifact_version(loop_id, iteration, content)
+    }
+
+    fn artifact_history(&self, loop_id: Uuid) -> anyhow::Result<Vec<ArtifactVersion>> {
+        self.maybe_fail()?;
+        self.inner.artifact_history(loop_id)
+    }
+
+    fn save_tool_job(&self, job: ToolJobRecord) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_tool_job(job)
+    }
+
+    fn tool_jobs(&self, loop_id: Uuid) -> anyhow::Result<Vec<ToolJobRecord>> {
+        self.maybe_fail()?;
+        self.inner.tool_jobs(loop_id)
+    }
+
+    fn save_transcript_entry(&self, entry: TranscriptEntry) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_transcript_entry(entry)
+    }
+
+    fn transcript(&self, loop_id: Uuid) -> anyhow::Result<Vec<TranscriptEntry>> {
+        self.maybe_fail()?;
+        self.inner.transcript(loop_id)
+    }
+
+    fn save_chat_session(&self, session: ChatSessionRecord) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_chat_session(session)
+    }
+
+    fn list_chat_sessions(&self) -> anyhow::Result<Vec<ChatSessionRecord>> {
+        self.maybe_fail()?;
+        self.inner.list_chat_sessions()
+    }
+
+    fn delete_chat_session(&self, id: Uuid) -> anyhow::Result<bool> {
+        self.maybe_fail()?;
+        self.inner.delete_chat_session(id)
+    }
+
+    fn save_audit_entry(&self, entry: AuditEntry) -> anyhow::Result<()> {
+        self.maybe_fail()?;
+        self.inner.save_audit_entry(entry)
+    }
+
+    fn audit_log(&self, loop_id: Option<Uuid>) -> anyhow::Result<Vec<AuditEntry>> {
+        self.maybe_fail()?;
+        self.inner.audit_log(loop_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, MockLlmClient, Role};
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn disabled_by_default_never_injects() {
+        let mut injector = ChaosInjector::new(ChaosConfig::default(), 42);
+        for _ in 0..100 {
+            assert!(injector.maybe_llm_error().is_none());
+        }
+    }
+
+    #[test]
+    fn full_rate_always_injects_when_enabled() {
+        let config = ChaosConfig { enabled: true, llm_error_rate: 1.0, ..ChaosConfig::default() };
+        let mut injector = ChaosInjector::new(config, 7);
+        assert_eq!(injector.maybe_llm_error(), Some(FaultKind::LlmError));
+        assert!(injector.maybe_tool_timeout().is_none());
+    }
+
+    #[test]
+    fn same_seed_produces_the_same_sequence_of_rolls() {
+        let config = ChaosConfig { enabled: true, llm_error_rate: 0.5, ..ChaosConfig::default() };
+        let rolls = |seed: u64| {
+            let mut injector = ChaosInjector::new(config, seed);
+            (0..20).map(|_| injector.maybe_llm_error().is_some()).collect::<Vec<_>>()
+        };
+        assert_eq!(rolls(99), rolls(99));
+    }
+
+    #[tokio::test]
+    async fn chaos_llm_client_surfaces_an_injected_error_instead_of_delegating() {
+        let inner = MockLlmClient::new(vec!["should not be seen".to_string()]);
+        let config = ChaosConfig { enabled: true, llm_error_rate: 1.0, ..ChaosConfig::default() };
+        let client = ChaosLlmClient::new(inner, config, 1);
+        let request = CompletionRequest::new("mock", vec![Message::text(Role::User, "hi")]);
+        let result = client.complete(request).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn chaos_storage_surfaces_an_injected_error_instead_of_delegating() {
+        let config = ChaosConfig { enabled: true, storage_error_rate: 1.0, ..ChaosConfig::default() };
+        let storage = ChaosStorage::new(InMemoryStorage::new(), config, 1);
+        let err = storage.list_loops().unwrap_err();
+        assert!(err.to_string().contains("injected fault: storage_error"));
+    }
+
+    #[test]
+    fn chaos_storage_delegates_when_no_fault_fires() {
+        let storage = ChaosStorage::new(InMemoryStorage::new(), ChaosConfig::default(), 1);
+        assert!(storage.list_loops().unwrap().is_empty());
+    }
+}
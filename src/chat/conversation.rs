@@ -0,0 +1,212 @@
+use crate::llm::{Message, MessageContent, Role};
+use crate::prompts::estimate_tokens;
+use uuid::Uuid;
+
+/// The default model and temperature loops use unless a conversation has
+/// overridden them, so exploratory chat can run a cheaper model.
+pub const DEFAULT_MODEL: &str = "claude-sonnet";
+pub const DEFAULT_TEMPERATURE: f32 = 0.0;
+
+/// Above this many estimated tokens of conversation history, `compact`
+/// replaces everything but the most recent turns with a single summary
+/// message, so a long-running daemon chat doesn't grow its context
+/// indefinitely.
+pub const COMPACTION_THRESHOLD_TOKENS: usize = 6000;
+
+/// How many of the most recent messages `compact` always keeps verbatim.
+const COMPACTION_KEEP_RECENT: usize = 4;
+
+/// A chat conversation's persisted state: its history plus any per-
+/// conversation model/temperature override set via `/model` or `/temp`.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    pub id: Uuid,
+    pub messages: Vec<Message>,
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+}
+
+impl Conversation {
+    pub fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            messages: Vec::new(),
+            model: None,
+            temperature: None,
+        }
+    }
+
+    pub fn model(&self) -> &str {
+        self.model.as_deref().unwrap_or(DEFAULT_MODEL)
+    }
+
+    pub fn temperature(&self) -> f32 {
+        self.temperature.unwrap_or(DEFAULT_TEMPERATURE)
+    }
+
+    pub fn set_model(&mut self, model: impl Into<String>) {
+        self.model = Some(model.into());
+    }
+
+    /// Parses and applies a `/temp` argument, rejecting values outside the
+    /// valid `0.0..=1.0` range rather than silently clamping them.
+    pub fn set_temperature(&mut self, value: &str) -> anyhow::Result<()> {
+        let parsed: f32 = value.trim().parse().map_err(|_| anyhow::anyhow!("'{value}' is not a number"))?;
+        if !(0.0..=1.0).contains(&parsed) {
+            anyhow::bail!("temperature must be between 0.0 and 1.0, got {parsed}");
+        }
+        self.temperature = Some(parsed);
+        Ok(())
+    }
+
+    /// The conversation's total estimated token count, for deciding when
+    /// to compact and for the TUI's context-usage display.
+    pub fn context_tokens(&self) -> usize {
+        self.messages.iter().map(|m| estimate_tokens(&message_text(m))).sum()
+    }
+}
+
+impl Default for Conversation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Summarizes a conversation's messages into an excerpt that fits within
+/// `token_budget`, taking the most recent messages first since those are
+/// most likely to reflect what was actually decided.
+pub fn summarize_conversation(conversation: &Conversation, token_budget: usize) -> String {
+    let mut kept = Vec::new();
+    let mut used = 0;
+    for message in conversation.messages.iter().rev() {
+        let text = message_text(message);
+        let tokens = estimate_tokens(&text);
+        if used + tokens > token_budget {
+            break;
+        }
+        used += tokens;
+        kept.push(format!("{:?}: {text}", message.role));
+    }
+    kept.reverse();
+    kept.join("\n")
+}
+
+/// Compacts `conversation` in place once its estimated token count passes
+/// `token_threshold`: the most recent [`COMPACTION_KEEP_RECENT`] messages
+/// stay verbatim, everything older collapses into a single summary
+/// message. Returns whether compaction actually ran, so a caller (the
+/// `chat.compact` handler, or the daemon running it automatically after
+/// each turn) can report it back to the operator.
+pub fn compact(conversation: &mut Conversation, token_threshold: usize) -> bool {
+    if conversation.context_tokens() <= token_threshold {
+        return false;
+    }
+    let keep_from = conversation.messages.len().saturating_sub(COMPACTION_KEEP_RECENT);
+    if keep_from == 0 {
+        return false;
+    }
+
+    let older = &conversation.messages[..keep_from];
+    let mut summary = String::from("## Earlier in this conversation\n\n");
+    for message in older {
+        summary.push_str(&format!("- {:?}: {}\n", message.role, message_text(message)));
+    }
+
+    let mut compacted = vec![Message::text(Role::System, summary)];
+    compacted.extend_from_slice(&conversation.messages[keep_from..]);
+    conversation.messages = compacted;
+    true
+}
+
+fn message_text(message: &Message) -> String {
+    message
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            MessageContent::Text { text } => Some(text.clone()),
+            MessageContent::Image { .. } => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_apply_until_overridden() {
+        let mut conversation = Conversation::new();
+        assert_eq!(conversation.model(), DEFAULT_MODEL);
+        conversation.set_model("claude-haiku");
+        assert_eq!(conversation.model(), "claude-haiku");
+    }
+
+    #[test]
+    fn rejects_out_of_range_temperature() {
+        let mut conversation = Conversation::new();
+        assert!(conversation.set_temperature("1.5").is_err());
+        assert_eq!(conversation.temperature(), DEFAULT_TEMPERATURE);
+    }
+
+    #[test]
+    fn accepts_valid_temperature() {
+        let mut conversation = Conversation::new();
+        conversation.set_temperature("0.7").unwrap();
+        assert_eq!(conversation.temperature(), 0.7);
+    }
+
+    #[test]
+    fn summarize_keeps_most_recent_messages_within_budget() {
+        use crate::llm::Role;
+        let mut conversation = Conversation::new();
+        conversation.messages.push(Message::text(Role::User, "we decided to use JWTs"));
+        conversation.messages.push(Message::text(Role::Assistant, "sounds good, noted"));
+        let excerpt = summarize_conversation(&conversation, 1000);
+        assert!(excerpt.contains("JWTs"));
+        assert!(excerpt.contains("noted"));
+    }
+
+    #[test]
+    fn summarize_drops_oldest_messages_when_over_budget() {
+        use crate::llm::Role;
+        let mut conversation = Conversation::new();
+        conversation.messages.push(Message::text(Role::User, "x".repeat(400)));
+        conversation.messages.push(Message::text(Role::User, "recent decision"));
+        let excerpt = summarize_conversation(&conversation, 10);
+        assert!(excerpt.contains("recent decision"));
+        assert!(!excerpt.contains("xxxx"));
+    }
+
+    #[test]
+    fn compact_leaves_a_short_conversation_untouched() {
+        let mut conversation = Conversation::new();
+        conversation.messages.push(Message::text(Role::User, "hello"));
+        assert!(!compact(&mut conversation, COMPACTION_THRESHOLD_TOKENS));
+        assert_eq!(conversation.messages.len(), 1);
+    }
+
+    #[test]
+    fn compact_collapses_older_messages_once_over_the_threshold() {
+        let mut conversation = Conversation::new();
+        for i in 0..10 {
+            conversation.messages.push(Message::text(Role::User, format!("turn {i}: {}", "x".repeat(50))));
+        }
+        conversation.messages.push(Message::text(Role::User, "the latest question"));
+
+        assert!(compact(&mut conversation, 50));
+        assert_eq!(conversation.messages.len(), COMPACTION_KEEP_RECENT + 1);
+        assert_eq!(conversation.messages.last().unwrap().content, Message::text(Role::User, "the latest question").content);
+        assert!(message_text(&conversation.messages[0]).contains("turn 0"));
+    }
+
+    #[test]
+    fn compact_is_idempotent_once_under_the_threshold_again() {
+        let mut conversation = Conversation::new();
+        for i in 0..10 {
+            conversation.messages.push(Message::text(Role::User, format!("turn {i}: {}", "x".repeat(50))));
+        }
+        compact(&mut conversation, 50);
+        assert!(!compact(&mut conversation, 50_000));
+    }
+}
@@ -0,0 +1,81 @@
+/// A recognized natural-language command, extracted from a chat message
+/// before it reaches the loop-operation layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intent {
+    /// "pause the auth spec" -> pause the loop matching "auth spec".
+    Pause { query: String },
+    /// "show me why phase 2 failed" -> explain the last failure for the
+    /// loop matching "phase 2".
+    ExplainFailure { query: String },
+    /// "/plan <task>" or a detected plan-creation request -> draft a plan
+    /// for `task`, pending operator confirmation.
+    CreatePlan { task: String },
+    /// Nothing recognized; pass the message through as plain chat.
+    Unknown,
+}
+
+/// Recognizes a small set of operator phrasings. Deliberately simple
+/// keyword matching rather than an LLM call, so routing stays instant and
+/// deterministic; anything more nuanced falls through to `Unknown` and
+/// gets a normal conversational reply.
+pub fn detect_intent(text: &str) -> Intent {
+    if let Some(task) = text.trim().strip_prefix("/plan ") {
+        return Intent::CreatePlan { task: task.trim().to_string() };
+    }
+
+    let lower = text.to_ascii_lowercase();
+
+    if let Some(rest) = lower.strip_prefix("pause the ").or_else(|| lower.strip_prefix("pause ")) {
+        return Intent::Pause { query: rest.trim().to_string() };
+    }
+
+    if lower.contains("why") && lower.contains("fail") {
+        if let Some(query) = extract_between(&lower, "why ", " failed") {
+            return Intent::ExplainFailure { query };
+        }
+    }
+
+    Intent::Unknown
+}
+
+fn extract_between(text: &str, start: &str, end: &str) -> Option<String> {
+    let after_start = text.split(start).nth(1)?;
+    let before_end = after_start.split(end).next()?;
+    let trimmed = before_end.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_pause_phrasing() {
+        assert_eq!(detect_intent("pause the auth spec"), Intent::Pause { query: "auth spec".into() });
+    }
+
+    #[test]
+    fn recognizes_explain_failure_phrasing() {
+        assert_eq!(
+            detect_intent("show me why phase 2 failed"),
+            Intent::ExplainFailure { query: "phase 2".into() }
+        );
+    }
+
+    #[test]
+    fn falls_back_to_unknown() {
+        assert_eq!(detect_intent("how's it going"), Intent::Unknown);
+    }
+
+    #[test]
+    fn recognizes_plan_slash_command() {
+        assert_eq!(
+            detect_intent("/plan add OAuth login"),
+            Intent::CreatePlan { task: "add OAuth login".into() }
+        );
+    }
+}
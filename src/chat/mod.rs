@@ -0,0 +1,256 @@
+//! The chat subsystem: natural-language commands routed to daemon loop
+//! operations, rendered by the TUI as structured cards rather than plain
+//! text round-trips through `chat.send`.
+
+mod conversation;
+mod intent;
+mod stream;
+
+pub use conversation::{compact, summarize_conversation, Conversation, COMPACTION_THRESHOLD_TOKENS, DEFAULT_MODEL, DEFAULT_TEMPERATURE};
+pub use intent::{detect_intent, Intent};
+pub use stream::{cancel_token, chunk_response};
+
+use crate::domain::{LoopRecord, LoopStatus, LoopType};
+use crate::estimate::{estimate_plan, HistoricalAverages};
+use crate::storage::{ChatSessionRecord, Storage};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A structured response the TUI can render specially, instead of plain
+/// chat text, when a message maps to a concrete loop operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChatCard {
+    /// Plain conversational text with nothing actionable detected.
+    Text(String),
+    /// Confirms a loop was found and describes the pause action taken.
+    PauseConfirmation { loop_id: String, description: String },
+    /// Summarizes the most recent failure for a loop the user asked about.
+    FailureExplanation { loop_id: String, description: String, feedback: Option<String> },
+    /// No loop matched the natural-language reference in the message.
+    NoMatch { query: String },
+    /// A plan was drafted from `/plan <task>` and awaits operator
+    /// confirmation before the `Plan` loop is actually created.
+    PlanConfirmation { task: String, estimated_cost_usd: f64 },
+}
+
+/// How much of the originating conversation is carried into a plan's
+/// context, in estimated tokens.
+const CONVERSATION_CARRYOVER_BUDGET_TOKENS: usize = 1000;
+
+/// Creates the `Plan` loop for a task accepted via a [`ChatCard::PlanConfirmation`].
+pub fn accept_plan(storage: &dyn Storage, task: &str) -> anyhow::Result<LoopRecord> {
+    accept_plan_from_conversation(storage, task, None)
+}
+
+/// Creates the `Plan` loop for a task accepted from a specific chat
+/// conversation, carrying a summarized excerpt of that conversation into
+/// the loop's context and stamping `conversation_id` for traceability.
+pub fn accept_plan_from_conversation(
+    storage: &dyn Storage,
+    task: &str,
+    conversation: Option<&Conversation>,
+) -> anyhow::Result<LoopRecord> {
+    let mut record = LoopRecord::new(LoopType::Plan, None, task);
+    if let Some(conversation) = conversation {
+        record.conversation_id = Some(conversation.id);
+        let excerpt = summarize_conversation(conversation, CONVERSATION_CARRYOVER_BUDGET_TOKENS);
+        if !excerpt.is_empty() {
+            record.carried_context = Some(excerpt);
+        }
+    }
+    storage.save_loop(record.clone())?;
+    Ok(record)
+}
+
+/// A rough single-phase estimate used to show a cost figure before the
+/// planning LLM has actually decomposed the task into specs and phases.
+fn draft_cost_estimate() -> f64 {
+    use crate::artifact::{Phase, Plan, Spec};
+    let draft = Plan {
+        title: String::new(),
+        specs: vec![Spec {
+            name: String::new(),
+            description: String::new(),
+            phases: vec![Phase { name: String::new(), description: String::new() }],
+        }],
+    };
+    estimate_plan(&draft, &HistoricalAverages::default()).predicted_cost_usd
+}
+
+/// Routes a chat message to a loop operation when its intent is
+/// recognized, looking up the referenced loop by fuzzy description match.
+pub fn handle_message(storage: &dyn Storage, text: &str) -> anyhow::Result<ChatCard> {
+    match detect_intent(text) {
+        Intent::Pause { query } => Ok(match find_loop_by_description(storage, &query)? {
+            Some(record) => ChatCard::PauseConfirmation {
+                loop_id: record.id.to_string(),
+                description: record.description,
+            },
+            None => ChatCard::NoMatch { query },
+        }),
+        Intent::ExplainFailure { query } => Ok(match find_loop_by_description(storage, &query)? {
+            Some(record) => {
+                let feedback = record.iterations.iter().rev().find_map(|it| it.feedback.clone());
+                ChatCard::FailureExplanation {
+                    loop_id: record.id.to_string(),
+                    description: record.description,
+                    feedback,
+                }
+            }
+            None => ChatCard::NoMatch { query },
+        }),
+        Intent::CreatePlan { task } => Ok(ChatCard::PlanConfirmation {
+            task,
+            estimated_cost_usd: draft_cost_estimate(),
+        }),
+        Intent::Unknown => Ok(ChatCard::Text(text.to_string())),
+    }
+}
+
+/// Creates a new named chat session. Its id doubles as the
+/// `conversation_id` stamped onto any loop created from it, via
+/// [`accept_plan_from_conversation`].
+pub fn create_session(storage: &dyn Storage, name: impl Into<String>) -> anyhow::Result<ChatSessionRecord> {
+    let session = ChatSessionRecord::new(name);
+    storage.save_chat_session(session.clone())?;
+    Ok(session)
+}
+
+/// Renames an existing session, erroring if it doesn't exist so the TUI
+/// can surface a stale-picker-entry error instead of silently recreating it.
+pub fn rename_session(storage: &dyn Storage, id: Uuid, name: impl Into<String>) -> anyhow::Result<ChatSessionRecord> {
+    let mut session = storage
+        .list_chat_sessions()?
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| anyhow::anyhow!("no chat session with id {id}"))?;
+    session.name = name.into();
+    storage.save_chat_session(session.clone())?;
+    Ok(session)
+}
+
+/// Deletes a session. Returns whether one was present.
+pub fn delete_session(storage: &dyn Storage, id: Uuid) -> anyhow::Result<bool> {
+    storage.delete_chat_session(id)
+}
+
+/// Lists every persisted session, oldest first, for the TUI's session picker.
+pub fn list_sessions(storage: &dyn Storage) -> anyhow::Result<Vec<ChatSessionRecord>> {
+    storage.list_chat_sessions()
+}
+
+fn find_loop_by_description(storage: &dyn Storage, query: &str) -> anyhow::Result<Option<LoopRecord>> {
+    let query = query.to_ascii_lowercase();
+    Ok(storage
+        .list_loops()?
+        .into_iter()
+        .filter(|l| l.status != LoopStatus::Cancelled && l.status != LoopStatus::Invalidated)
+        .find(|l| l.description.to_ascii_lowercase().contains(&query)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn pause_intent_resolves_to_matching_loop() {
+        let storage = InMemoryStorage::new();
+        storage.save_loop(LoopRecord::new(LoopType::Spec, None, "auth spec")).unwrap();
+
+        let card = handle_message(&storage, "pause the auth spec").unwrap();
+        match card {
+            ChatCard::PauseConfirmation { description, .. } => assert_eq!(description, "auth spec"),
+            other => panic!("expected PauseConfirmation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unmatched_query_reports_no_match() {
+        let storage = InMemoryStorage::new();
+        let card = handle_message(&storage, "pause the billing spec").unwrap();
+        assert_eq!(card, ChatCard::NoMatch { query: "billing spec".to_string() });
+    }
+
+    #[test]
+    fn plan_slash_command_drafts_a_confirmation_card() {
+        let storage = InMemoryStorage::new();
+        let card = handle_message(&storage, "/plan add OAuth login").unwrap();
+        match card {
+            ChatCard::PlanConfirmation { task, estimated_cost_usd } => {
+                assert_eq!(task, "add OAuth login");
+                assert!(estimated_cost_usd > 0.0);
+            }
+            other => panic!("expected PlanConfirmation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepting_a_plan_from_a_conversation_carries_context() {
+        use crate::llm::Role;
+        let storage = InMemoryStorage::new();
+        let mut conversation = Conversation::new();
+        conversation.messages.push(crate::llm::Message::text(Role::User, "we decided to use JWTs"));
+        let record = accept_plan_from_conversation(&storage, "add OAuth login", Some(&conversation)).unwrap();
+        assert_eq!(record.conversation_id, Some(conversation.id));
+        assert!(record.carried_context.unwrap().contains("JWTs"));
+    }
+
+    #[test]
+    fn accepting_a_plan_creates_a_pending_plan_loop() {
+        let storage = InMemoryStorage::new();
+        let record = accept_plan(&storage, "add OAuth login").unwrap();
+        assert_eq!(record.loop_type, LoopType::Plan);
+        assert_eq!(record.status, LoopStatus::Pending);
+        assert_eq!(storage.list_loops().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn plain_text_falls_through_unchanged() {
+        let storage = InMemoryStorage::new();
+        let card = handle_message(&storage, "what's the weather like").unwrap();
+        assert_eq!(card, ChatCard::Text("what's the weather like".to_string()));
+    }
+
+    #[test]
+    fn creating_a_session_persists_it() {
+        let storage = InMemoryStorage::new();
+        let session = create_session(&storage, "debugging the flaky test").unwrap();
+        let sessions = list_sessions(&storage).unwrap();
+        assert_eq!(sessions, vec![session]);
+    }
+
+    #[test]
+    fn renaming_a_session_updates_it_in_place() {
+        let storage = InMemoryStorage::new();
+        let session = create_session(&storage, "untitled").unwrap();
+        let renamed = rename_session(&storage, session.id, "auth rewrite").unwrap();
+        assert_eq!(renamed.name, "auth rewrite");
+        assert_eq!(list_sessions(&storage).unwrap(), vec![renamed]);
+    }
+
+    #[test]
+    fn renaming_a_missing_session_errors() {
+        let storage = InMemoryStorage::new();
+        assert!(rename_session(&storage, Uuid::new_v4(), "anything").is_err());
+    }
+
+    #[test]
+    fn deleting_a_session_removes_it_from_the_list() {
+        let storage = InMemoryStorage::new();
+        let session = create_session(&storage, "untitled").unwrap();
+        assert!(delete_session(&storage, session.id).unwrap());
+        assert!(list_sessions(&storage).unwrap().is_empty());
+    }
+
+    #[test]
+    fn accepting_a_plan_from_a_session_conversation_stamps_its_id_onto_the_loop() {
+        let storage = InMemoryStorage::new();
+        let session = create_session(&storage, "auth rewrite").unwrap();
+        let mut conversation = Conversation::new();
+        conversation.id = session.id;
+        let record = accept_plan_from_conversation(&storage, "add OAuth login", Some(&conversation)).unwrap();
+        assert_eq!(record.conversation_id, Some(session.id));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::ipc::DaemonEvent;
+use crate::llm::StreamChunk;
+use uuid::Uuid;
+
+/// Splits a completed chat reply into word-sized [`DaemonEvent::ChatChunk`]s
+/// tagged with `request_id`, so the TUI can render it incrementally with a
+/// typing indicator instead of waiting for the whole response. Real
+/// provider streaming will produce these chunks directly; this keeps the
+/// wire format the same until that lands.
+pub fn chunk_response(request_id: Uuid, text: &str) -> Vec<DaemonEvent> {
+    if text.is_empty() {
+        return vec![DaemonEvent::ChatChunk {
+            request_id,
+            chunk: StreamChunk { text: String::new(), done: true },
+        }];
+    }
+
+    let words: Vec<&str> = text.split_inclusive(' ').collect();
+    let mut events: Vec<DaemonEvent> = words
+        .iter()
+        .map(|word| DaemonEvent::ChatChunk {
+            request_id,
+            chunk: StreamChunk { text: word.to_string(), done: false },
+        })
+        .collect();
+
+    if let Some(DaemonEvent::ChatChunk { chunk, .. }) = events.last_mut() {
+        chunk.done = true;
+    }
+    events
+}
+
+/// A client-side request id a cancel key can reference to stop rendering
+/// further chunks for an in-flight chat stream.
+pub fn cancel_token() -> Uuid {
+    Uuid::new_v4()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marks_only_the_last_chunk_done() {
+        let request_id = Uuid::new_v4();
+        let events = chunk_response(request_id, "hello there friend");
+        let dones: Vec<bool> = events
+            .iter()
+            .map(|e| match e {
+                DaemonEvent::ChatChunk { chunk, .. } => chunk.done,
+                DaemonEvent::OperatorAlert { .. }
+                | DaemonEvent::BudgetAlert { .. }
+                | DaemonEvent::IterationDiffSummary { .. }
+                | DaemonEvent::DescriptionChanged { .. } => {
+                    unreachable!("chunk_response only emits ChatChunk events")
+                }
+            })
+            .collect();
+        assert_eq!(dones, vec![false, false, true]);
+    }
+
+    #[test]
+    fn reassembles_to_the_original_text() {
+        let request_id = Uuid::new_v4();
+        let events = chunk_response(request_id, "hello there friend");
+        let reassembled: String = events
+            .into_iter()
+            .map(|e| match e {
+                DaemonEvent::ChatChunk { chunk, .. } => chunk.text,
+                DaemonEvent::OperatorAlert { .. }
+                | DaemonEvent::BudgetAlert { .. }
+                | DaemonEvent::IterationDiffSummary { .. }
+                | DaemonEvent::DescriptionChanged { .. } => {
+                    unreachable!("chunk_response only emits ChatChunk events")
+                }
+            })
+            .collect();
+        assert_eq!(reassembled, "hello there friend");
+    }
+}
@@ -0,0 +1,86 @@
+//! Named checkpoints of a loop's worktree: a good intermediate state
+//! tagged by an iteration's `checkpoint` tool call or by an operator, so
+//! later iterations (or a human) can roll back to it, and the merge step
+//! can pick the checkpoint that passed the most gates instead of always
+//! taking the loop's final state.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A worktree snapshot tagged by name, captured as a unified diff (see
+/// [`crate::patch::capture_diff`]) so it can be reapplied with `git
+/// apply` regardless of which commit HEAD has since moved to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// The iteration this checkpoint was taken during, if any; `None` for
+    /// an operator-tagged checkpoint taken between iterations.
+    pub iteration_index: Option<u32>,
+    pub diff: String,
+    /// The gate results at the time this checkpoint was tagged, used by
+    /// [`best`] to judge it against the loop's other checkpoints.
+    #[serde(default)]
+    pub gate_results: Vec<crate::validation::GateSummary>,
+}
+
+impl Checkpoint {
+    pub fn new(name: impl Into<String>, iteration_index: Option<u32>, diff: impl Into<String>, gate_results: Vec<crate::validation::GateSummary>) -> Self {
+        Self {
+            name: name.into(),
+            created_at: Utc::now(),
+            iteration_index,
+            diff: diff.into(),
+            gate_results,
+        }
+    }
+
+    pub fn passed_gate_count(&self) -> usize {
+        self.gate_results.iter().filter(|gate| gate.passed).count()
+    }
+}
+
+/// The checkpoint the merge step should prefer over the loop's final
+/// state: whichever passed the most gates, breaking ties in favor of the
+/// more recent one. `None` if `checkpoints` is empty.
+pub fn best(checkpoints: &[Checkpoint]) -> Option<&Checkpoint> {
+    checkpoints.iter().max_by_key(|checkpoint| (checkpoint.passed_gate_count(), checkpoint.created_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::GateSummary;
+
+    fn gate(name: &str, passed: bool) -> GateSummary {
+        GateSummary {
+            name: name.to_string(),
+            passed,
+            duration_ms: 0,
+            first_failure_line: None,
+        }
+    }
+
+    #[test]
+    fn best_prefers_the_checkpoint_with_more_passing_gates() {
+        let worse = Checkpoint::new("attempt-1", Some(0), "diff-1", vec![gate("tests", false)]);
+        let better = Checkpoint::new("attempt-2", Some(1), "diff-2", vec![gate("tests", true), gate("lint", true)]);
+        let checkpoints = vec![worse, better.clone()];
+        assert_eq!(best(&checkpoints).unwrap().name, better.name);
+    }
+
+    #[test]
+    fn ties_in_passing_gates_are_broken_by_recency() {
+        let mut earlier = Checkpoint::new("attempt-1", Some(0), "diff-1", vec![gate("tests", true)]);
+        let mut later = Checkpoint::new("attempt-2", Some(1), "diff-2", vec![gate("tests", true)]);
+        earlier.created_at = Utc::now() - chrono::Duration::hours(1);
+        later.created_at = Utc::now();
+        let checkpoints = vec![earlier, later.clone()];
+        assert_eq!(best(&checkpoints).unwrap().name, later.name);
+    }
+
+    #[test]
+    fn no_checkpoints_has_no_best() {
+        assert!(best(&[]).is_none());
+    }
+}
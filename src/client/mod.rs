@@ -0,0 +1,130 @@
+//! Client SDK for embedding loopr control in other Rust tools (bots,
+//! editors), behind the `client` Cargo feature so a thin consumer isn't
+//! forced to pull in the TUI's dependencies. [`Client`] is generic over
+//! a [`Transport`] so the actual socket connection lives in the caller's
+//! binary; this module only owns request encoding, response decoding,
+//! and reconnect backoff math. [`ReconnectPolicy`] and event filtering
+//! are pure and testable without a real daemon; an actual `Transport`
+//! impl (Unix socket, TCP) is left to the binary embedding this crate,
+//! since the daemon side of that connection doesn't exist yet either.
+
+mod reconnect;
+
+pub use reconnect::ReconnectPolicy;
+
+use crate::ipc::messages::{
+    ArtifactHistoryRequest, ArtifactHistoryResponse, IpcMessage, LoopDeleteRequest, LoopDeleteResponse, LoopGetRequest, LoopGetResponse,
+    LoopListRequest, LoopListResponse,
+};
+use crate::ipc::DaemonEvent;
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// How a [`Client`] reaches the daemon: one round-trip call per IPC
+/// method, given the method name and an already-encoded JSON payload.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    async fn call(&self, method: &'static str, payload: Vec<u8>) -> anyhow::Result<Vec<u8>>;
+}
+
+/// A typed view over a [`Transport`], exposing one method per IPC call
+/// instead of making callers build and decode raw JSON themselves.
+pub struct Client<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    async fn request<Req, Resp>(&self, request: &Req) -> anyhow::Result<Resp>
+    where
+        Req: IpcMessage + Serialize + Sync,
+        Resp: DeserializeOwned,
+    {
+        let payload = serde_json::to_vec(request)?;
+        let bytes = self.transport.call(Req::METHOD, payload).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    pub async fn loop_get(&self, id: Uuid) -> anyhow::Result<LoopGetResponse> {
+        self.request(&LoopGetRequest { id }).await
+    }
+
+    pub async fn loop_list(&self, label: Option<String>) -> anyhow::Result<LoopListResponse> {
+        self.request(&LoopListRequest { label }).await
+    }
+
+    pub async fn loop_delete(&self, id: Uuid, force: bool) -> anyhow::Result<LoopDeleteResponse> {
+        self.request(&LoopDeleteRequest { id, force }).await
+    }
+
+    pub async fn artifact_history(&self, loop_id: Uuid) -> anyhow::Result<ArtifactHistoryResponse> {
+        self.request(&ArtifactHistoryRequest { loop_id }).await
+    }
+}
+
+/// Narrows a batch of pushed [`DaemonEvent`]s down to the ones about
+/// `loop_id`, the subscription shape a TUI or bot actually wants instead
+/// of every event the daemon pushes.
+pub fn events_for_loop(events: &[DaemonEvent], loop_id: Uuid) -> Vec<&DaemonEvent> {
+    events
+        .iter()
+        .filter(|event| match event {
+            DaemonEvent::OperatorAlert { loop_id: id, .. } => *id == loop_id,
+            DaemonEvent::IterationDiffSummary { loop_id: id, .. } => *id == loop_id,
+            DaemonEvent::DescriptionChanged { loop_id: id, .. } => *id == loop_id,
+            DaemonEvent::ChatChunk { .. } | DaemonEvent::BudgetAlert { .. } => false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_summary::DiffSummary;
+    use std::sync::Mutex;
+
+    struct MockTransport {
+        response: Mutex<Vec<u8>>,
+        seen_method: Mutex<Option<&'static str>>,
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn call(&self, method: &'static str, _payload: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+            *self.seen_method.lock().unwrap() = Some(method);
+            Ok(self.response.lock().unwrap().clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn loop_get_decodes_the_typed_response_and_calls_the_right_method() {
+        let record = crate::domain::LoopRecord::new(crate::domain::LoopType::Ralph, None, "fix it");
+        let response = LoopGetResponse { record: Some(record.clone()) };
+        let transport = MockTransport { response: Mutex::new(serde_json::to_vec(&response).unwrap()), seen_method: Mutex::new(None) };
+        let client = Client::new(transport);
+
+        let decoded = client.loop_get(record.id).await.unwrap();
+        assert_eq!(decoded.record.unwrap().id, record.id);
+        assert_eq!(*client.transport.seen_method.lock().unwrap(), Some(crate::ipc::Methods::LOOP_GET));
+    }
+
+    #[test]
+    fn events_for_loop_excludes_events_about_other_loops_and_unaddressed_ones() {
+        let loop_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+        let events = vec![
+            DaemonEvent::OperatorAlert { loop_id, message: "stuck".to_string() },
+            DaemonEvent::OperatorAlert { loop_id: other_id, message: "also stuck".to_string() },
+            DaemonEvent::BudgetAlert { message: "over cap".to_string() },
+            DaemonEvent::IterationDiffSummary { loop_id, iteration_index: 0, summary: DiffSummary::default() },
+        ];
+
+        let filtered = events_for_loop(&events, loop_id);
+        assert_eq!(filtered.len(), 2);
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+/// Exponential backoff for reconnecting a [`super::Client`] after the
+/// daemon connection drops, capped so a long outage doesn't grow the
+/// wait unboundedly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub multiplier: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self { initial_backoff_ms: 200, max_backoff_ms: 30_000, multiplier: 2.0 }
+    }
+}
+
+impl ReconnectPolicy {
+    /// The backoff to wait before reconnect attempt number `attempt`
+    /// (0-indexed), growing by `multiplier` each attempt and clamped to
+    /// `max_backoff_ms`.
+    pub fn next_backoff(&self, attempt: u32) -> Duration {
+        let scaled = self.initial_backoff_ms as f64 * self.multiplier.powi(attempt as i32);
+        Duration::from_millis(scaled.min(self.max_backoff_ms as f64) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_each_attempt() {
+        let policy = ReconnectPolicy::default();
+        assert_eq!(policy.next_backoff(0), Duration::from_millis(200));
+        assert_eq!(policy.next_backoff(1), Duration::from_millis(400));
+        assert_eq!(policy.next_backoff(2), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn backoff_is_capped_at_the_maximum() {
+        let policy = ReconnectPolicy::default();
+        assert_eq!(policy.next_backoff(20), Duration::from_millis(policy.max_backoff_ms));
+    }
+}
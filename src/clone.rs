@@ -0,0 +1,75 @@
+//! Duplicating a loop into a fresh attempt, for retrying failed or
+//! abandoned work with a tweaked task description or model instead of
+//! rebuilding the plan/spec/phase hierarchy by hand.
+
+use crate::domain::LoopRecord;
+
+/// Copies `source` into a new, unstarted [`LoopRecord`] of the same
+/// [`LoopType`] and with the same parent, so the clone takes the
+/// original's place in the hierarchy rather than becoming its child.
+/// `task` overrides the description and `model` sets a per-loop model
+/// override; either may be omitted to keep the source's value. Labels and
+/// the linked ticket carry over since they describe the work, not the
+/// attempt; iterations, gate results, pins, and scope do not, since those
+/// belong to the worktree the source loop already ran in.
+pub fn clone_loop(source: &LoopRecord, task: Option<String>, model: Option<String>) -> LoopRecord {
+    let description = task.unwrap_or_else(|| source.description.clone());
+    let mut clone = LoopRecord::new(source.loop_type.clone(), source.parent_id, description).with_labels(source.labels.clone());
+    if let Some(ticket_id) = &source.ticket_id {
+        clone = clone.with_ticket(ticket_id.clone());
+    }
+    if let Some(model) = model.or_else(|| source.model_override.clone()) {
+        clone = clone.with_model_override(model);
+    }
+    clone
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{LoopStatus, LoopType};
+
+    fn failed_loop() -> LoopRecord {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the flaky retry logic").with_labels(vec!["backend".to_string()]).with_ticket("PROJ-42");
+        record.status = LoopStatus::Failed;
+        record.iterations.push(crate::domain::Iteration::new(0));
+        record
+    }
+
+    #[test]
+    fn clone_starts_pending_with_no_iterations() {
+        let clone = clone_loop(&failed_loop(), None, None);
+        assert_eq!(clone.status, LoopStatus::Pending);
+        assert!(clone.iterations.is_empty());
+    }
+
+    #[test]
+    fn clone_keeps_the_same_loop_type_parent_labels_and_ticket() {
+        let source = failed_loop();
+        let clone = clone_loop(&source, None, None);
+        assert_eq!(clone.loop_type, source.loop_type);
+        assert_eq!(clone.parent_id, source.parent_id);
+        assert_eq!(clone.labels, source.labels);
+        assert_eq!(clone.ticket_id, source.ticket_id);
+        assert_eq!(clone.description, source.description);
+    }
+
+    #[test]
+    fn clone_gets_a_fresh_id() {
+        let source = failed_loop();
+        let clone = clone_loop(&source, None, None);
+        assert_ne!(clone.id, source.id);
+    }
+
+    #[test]
+    fn task_override_replaces_the_description() {
+        let clone = clone_loop(&failed_loop(), Some("fix the flaky retry logic, take 2".to_string()), None);
+        assert_eq!(clone.description, "fix the flaky retry logic, take 2");
+    }
+
+    #[test]
+    fn model_override_is_carried_onto_the_clone() {
+        let clone = clone_loop(&failed_loop(), None, Some("claude-haiku".to_string()));
+        assert_eq!(clone.model_override, Some("claude-haiku".to_string()));
+    }
+}
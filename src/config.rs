@@ -0,0 +1,200 @@
+//! Project and user configuration, loaded from `loopr.yml`, plus strict
+//! validation so a typo'd or stale config fails with a diagnostic that
+//! names the field and line instead of silently falling back to a
+//! default.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Cargo workspace members, so the test gate can run `cargo test -p`
+    /// scoped to whatever a loop's diff touches; see
+    /// [`crate::validation::test_gates`].
+    #[serde(default)]
+    pub workspace: crate::validation::WorkspaceConfig,
+    /// Overrides [`crate::profiles::detect`]'s lockfile-based guess when
+    /// set, for a project whose stack isn't detectable (or detected
+    /// wrong) from what's checked in.
+    #[serde(default)]
+    pub profile: Option<crate::profiles::ProjectProfile>,
+    /// Additional repos mounted read-only into every loop's [`crate::tools::ToolContext`]
+    /// (e.g. a shared proto or API-contract repo); see [`crate::reference_repos`].
+    #[serde(default)]
+    pub reference_repos: Vec<crate::reference_repos::ReferenceRepo>,
+}
+
+/// Keys renamed in this struct since an earlier release, `old -> new`. A
+/// renamed key still parses — remapped onto its new name by [`validate`]
+/// — and produces a [`Diagnostic::Deprecated`] warning for one release,
+/// before the grace period ends and its entry is removed here so the old
+/// name starts erroring as an unknown field like any other typo.
+const DEPRECATED_KEYS: &[(&str, &str)] = &[];
+
+/// One problem found while validating a `loopr.yml` or loop-type YAML
+/// file, carrying enough detail that fixing it doesn't require decoding
+/// a raw serde error by hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// Unknown key, wrong type, or other structural problem; `detail` is
+    /// serde_yaml's own message, which names the field and, unless a
+    /// deprecated key was remapped, the line.
+    Invalid { detail: String },
+    /// A renamed key was used; still accepted this release but will
+    /// become an `Invalid` error once its grace period ends.
+    Deprecated { old_key: String, new_key: String },
+}
+
+/// Parses and strictly validates `content` against `T`, applying
+/// `deprecated_keys` renames first so a file written against an older
+/// schema still loads — with a warning per renamed key — during its
+/// one-release grace period, instead of breaking the moment a key is
+/// renamed.
+pub fn validate<T: DeserializeOwned>(content: &str, deprecated_keys: &[(&str, &str)]) -> Result<(T, Vec<Diagnostic>), Diagnostic> {
+    let mut value: Value = serde_yaml::from_str(content).map_err(|error| Diagnostic::Invalid { detail: error.to_string() })?;
+    let mut warnings = Vec::new();
+    if let Value::Mapping(map) = &mut value {
+        for (old_key, new_key) in deprecated_keys {
+            let old_key_value = Value::String(old_key.to_string());
+            if let Some(old_value) = map.remove(&old_key_value) {
+                warnings.push(Diagnostic::Deprecated { old_key: old_key.to_string(), new_key: new_key.to_string() });
+                let new_key_value = Value::String(new_key.to_string());
+                if !map.contains_key(&new_key_value) {
+                    map.insert(new_key_value, old_value);
+                }
+            }
+        }
+    }
+    let parsed = serde_yaml::from_value(value).map_err(|error| {
+        // A `Value` carries no source positions, so re-parse the original
+        // text for an error that names the line; that text only matches
+        // what was deserialized when no deprecated key was remapped.
+        let located = if warnings.is_empty() { serde_yaml::from_str::<T>(content).err() } else { None };
+        Diagnostic::Invalid { detail: located.unwrap_or(error).to_string() }
+    })?;
+    Ok((parsed, warnings))
+}
+
+/// Model family prefixes this build knows how to route; see
+/// [`crate::chat::DEFAULT_MODEL`].
+const VALID_MODEL_PREFIXES: &[&str] = &["claude-"];
+
+/// Parses and validates a `loopr.yml` project config, rejecting a `model`
+/// string that doesn't match a known family on top of the structural
+/// checks [`validate`] already does.
+pub fn validate_config(content: &str) -> Result<(Config, Vec<Diagnostic>), Diagnostic> {
+    let (config, warnings) = validate::<Config>(content, DEPRECATED_KEYS)?;
+    if let Some(model) = &config.model {
+        if !VALID_MODEL_PREFIXES.iter().any(|prefix| model.starts_with(prefix)) {
+            return Err(Diagnostic::Invalid { detail: format!("model {model:?} does not match a known model family (expected one of: {VALID_MODEL_PREFIXES:?})") });
+        }
+    }
+    Ok((config, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_unknown_key_is_reported_as_invalid() {
+        let err = validate_config("model: claude-sonnet\nfoo: bar\n").unwrap_err();
+        match err {
+            Diagnostic::Invalid { detail } => assert!(detail.contains("foo")),
+            other => panic!("expected Invalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn a_wrong_type_is_reported_as_invalid() {
+        let err = validate_config("model:\n  - not-a-string\n").unwrap_err();
+        assert!(matches!(err, Diagnostic::Invalid { .. }));
+    }
+
+    #[test]
+    fn an_unrecognized_model_family_is_reported_as_invalid() {
+        let err = validate_config("model: gpt-4\n").unwrap_err();
+        match err {
+            Diagnostic::Invalid { detail } => assert!(detail.contains("gpt-4")),
+            other => panic!("expected Invalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn a_valid_config_parses_with_no_diagnostics() {
+        let (config, warnings) = validate_config("model: claude-haiku\n").unwrap();
+        assert_eq!(config.model, Some("claude-haiku".to_string()));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn a_workspace_section_parses_into_its_members() {
+        let yaml = "\
+workspace:
+  members:
+    - name: loopr-core
+      path: crates/core/
+";
+        let (config, warnings) = validate_config(yaml).unwrap();
+        assert_eq!(config.workspace.members.len(), 1);
+        assert_eq!(config.workspace.members[0].name, "loopr-core");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn a_reference_repos_section_parses_into_its_entries() {
+        let yaml = "\
+reference_repos:
+  - name: proto
+    path: /repos/proto
+";
+        let (config, warnings) = validate_config(yaml).unwrap();
+        assert_eq!(config.reference_repos.len(), 1);
+        assert_eq!(config.reference_repos[0].name, "proto");
+        assert_eq!(config.reference_repos[0].path, "/repos/proto");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn a_profile_override_parses() {
+        let (config, _) = validate_config("profile: python\n").unwrap();
+        assert_eq!(config.profile, Some(crate::profiles::ProjectProfile::Python));
+    }
+
+    #[test]
+    fn an_empty_config_is_valid() {
+        let (config, warnings) = validate_config("").unwrap();
+        assert_eq!(config.model, None);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn a_deprecated_key_is_remapped_and_warns_instead_of_erroring() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        #[serde(deny_unknown_fields)]
+        struct Example {
+            #[serde(default)]
+            new_name: Option<String>,
+        }
+        let (example, warnings) = validate::<Example>("old_name: value\n", &[("old_name", "new_name")]).unwrap();
+        assert_eq!(example.new_name, Some("value".to_string()));
+        assert_eq!(warnings, vec![Diagnostic::Deprecated { old_key: "old_name".to_string(), new_key: "new_name".to_string() }]);
+    }
+
+    #[test]
+    fn a_deprecated_key_does_not_clobber_a_value_already_set_under_the_new_name() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        #[serde(deny_unknown_fields)]
+        struct Example {
+            #[serde(default)]
+            new_name: Option<String>,
+        }
+        let (example, warnings) = validate::<Example>("old_name: stale\nnew_name: current\n", &[("old_name", "new_name")]).unwrap();
+        assert_eq!(example.new_name, Some("current".to_string()));
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! Model-aware token accounting and graceful degradation when an
+//! assembled prompt would exceed the active model's context window.
+
+use crate::prompts::estimate_tokens;
+
+/// How eagerly a section should be dropped/truncated when the assembled
+/// prompt doesn't fit. Lower-priority sections go first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Optional,
+    Important,
+    Required,
+}
+
+/// One named chunk of prompt content (system prompt, repo context,
+/// artifact, feedback, ...) with a priority used when degrading.
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub name: String,
+    pub content: String,
+    pub priority: Priority,
+}
+
+impl Section {
+    pub fn new(name: impl Into<String>, content: impl Into<String>, priority: Priority) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+            priority,
+        }
+    }
+
+    fn tokens(&self) -> usize {
+        estimate_tokens(&self.content)
+    }
+}
+
+/// Returns the context window, in tokens, for a known model name. Unknown
+/// models get a conservative default so we degrade instead of overshooting.
+pub fn model_window(model: &str) -> usize {
+    match model {
+        m if m.contains("opus") || m.contains("sonnet") => 200_000,
+        m if m.contains("haiku") => 200_000,
+        m if m.contains("gpt-4") => 128_000,
+        _ => 32_000,
+    }
+}
+
+/// What happened to each section while fitting the prompt to the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionOutcome {
+    Included,
+    Truncated,
+    Dropped,
+}
+
+#[derive(Debug, Clone)]
+pub struct FitReport {
+    pub model: String,
+    pub window: usize,
+    pub outcomes: Vec<(String, SectionOutcome)>,
+}
+
+/// Concatenates `sections` into a single prompt body, dropping and then
+/// truncating lowest-priority sections first until the result fits within
+/// `model`'s context window, reserving `reserve_tokens` for the response.
+pub fn fit(model: &str, sections: Vec<Section>, reserve_tokens: usize) -> (String, FitReport) {
+    let window = model_window(model);
+    let budget = window.saturating_sub(reserve_tokens);
+
+    let mut ordered = sections;
+    ordered.sort_by_key(|s| std::cmp::Reverse(s.priority));
+
+    let mut used = 0usize;
+    let mut outcomes = Vec::new();
+    let mut body = String::new();
+
+    for section in ordered {
+        let tokens = section.tokens();
+        if used + tokens <= budget {
+            used += tokens;
+            body.push_str(&format!("## {}\n\n{}\n\n", section.name, section.content));
+            outcomes.push((section.name, SectionOutcome::Included));
+        } else if section.priority == Priority::Required {
+            // Required sections are truncated to whatever room is left
+            // rather than dropped outright.
+            let remaining_chars = budget.saturating_sub(used) * 4;
+            let truncated: String = section.content.chars().take(remaining_chars).collect();
+            used = budget;
+            body.push_str(&format!("## {}\n\n{}\n\n[truncated to fit context window]\n\n", section.name, truncated));
+            outcomes.push((section.name, SectionOutcome::Truncated));
+        } else {
+            outcomes.push((section.name, SectionOutcome::Dropped));
+        }
+    }
+
+    (
+        body,
+        FitReport {
+            model: model.to_string(),
+            window,
+            outcomes,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_optional_sections_before_truncating_required_ones() {
+        let sections = vec![
+            Section::new("system", "short", Priority::Required),
+            Section::new("docs", "x".repeat(200_000), Priority::Optional),
+        ];
+        let (_body, report) = fit("small-model", sections, 0);
+        assert_eq!(report.window, 32_000);
+        assert!(report
+            .outcomes
+            .iter()
+            .any(|(name, outcome)| name == "docs" && *outcome == SectionOutcome::Dropped));
+        assert!(report
+            .outcomes
+            .iter()
+            .any(|(name, outcome)| name == "system" && *outcome == SectionOutcome::Included));
+    }
+
+    #[test]
+    fn truncates_required_sections_that_dont_fit() {
+        let sections = vec![Section::new("feedback", "y".repeat(200_000), Priority::Required)];
+        let (_body, report) = fit("small-model", sections, 0);
+        assert_eq!(report.outcomes[0].1, SectionOutcome::Truncated);
+    }
+}
@@ -0,0 +1,196 @@
+//! API key storage and rotation. Beyond a single `ANTHROPIC_API_KEY`
+//! environment variable, a project can hold several named keys and
+//! rotate between them; [`check_health`] reports expiry and invalid-key
+//! conditions through the daemon's health check instead of surfacing a
+//! generic LLM failure the first time a loop tries to use a dead key.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Where named keys are read from and written to. The daemon's default
+/// backend reads process environment variables; a production deployment
+/// would add an OS-keychain-backed implementation (macOS Keychain,
+/// Secret Service, Windows Credential Manager) behind this same trait,
+/// the way [`crate::storage::Storage`] has `InMemoryStorage` today and a
+/// durable backend later.
+pub trait KeyStore: Send + Sync {
+    fn get(&self, name: &str) -> anyhow::Result<Option<String>>;
+}
+
+/// Reads each named key from the environment variable of the same name.
+pub struct EnvKeyStore;
+
+impl KeyStore for EnvKeyStore {
+    fn get(&self, name: &str) -> anyhow::Result<Option<String>> {
+        Ok(std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+}
+
+/// One named API key and when it was added/expires, so a `KeyRing` can
+/// carry more than just `ANTHROPIC_API_KEY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub name: String,
+    pub value: String,
+    pub added_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            added_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    pub fn with_expiry(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// A project's set of named keys plus which one is currently active.
+/// Rotation just repoints `active` at another already-added key, so a
+/// compromised or expiring key can be swapped without restarting loops
+/// mid-iteration.
+#[derive(Debug, Clone, Default)]
+pub struct KeyRing {
+    keys: Vec<ApiKey>,
+    active: Option<String>,
+}
+
+impl KeyRing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key`, making it active if it's the first key added.
+    pub fn add(&mut self, key: ApiKey) {
+        if self.active.is_none() {
+            self.active = Some(key.name.clone());
+        }
+        self.keys.push(key);
+    }
+
+    /// Points `active` at `name`, erroring if no key with that name was added.
+    pub fn rotate_to(&mut self, name: &str) -> anyhow::Result<()> {
+        if !self.keys.iter().any(|k| k.name == name) {
+            anyhow::bail!("no key named {name:?} in the key ring");
+        }
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    pub fn active_key(&self) -> Option<&ApiKey> {
+        let name = self.active.as_ref()?;
+        self.keys.iter().find(|k| &k.name == name)
+    }
+
+    /// Removes a key by name. Clears `active` if it was the removed key,
+    /// without falling back to another key automatically — an operator
+    /// must rotate explicitly rather than silently keep running on
+    /// whichever key happens to be left.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.keys.len();
+        self.keys.retain(|k| k.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        self.keys.len() != before
+    }
+}
+
+/// The daemon's credential health, reported through its health check
+/// instead of a generic LLM failure the first time a dead key is used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CredentialHealth {
+    Ok,
+    NoActiveKey,
+    Expired { name: String },
+}
+
+/// Checks the active key's presence and expiry as of `now`. Does not
+/// validate the key against the provider — that requires a live
+/// completion call, which belongs to the onboarding wizard's
+/// connectivity check rather than a cheap local health probe.
+pub fn check_health(keyring: &KeyRing, now: DateTime<Utc>) -> CredentialHealth {
+    match keyring.active_key() {
+        None => CredentialHealth::NoActiveKey,
+        Some(key) if key.is_expired(now) => CredentialHealth::Expired { name: key.name.clone() },
+        Some(_) => CredentialHealth::Ok,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn the_first_added_key_becomes_active() {
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1"));
+        assert_eq!(ring.active_key().unwrap().name, "primary");
+    }
+
+    #[test]
+    fn rotating_to_an_unknown_key_errors() {
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1"));
+        assert!(ring.rotate_to("missing").is_err());
+        assert_eq!(ring.active_key().unwrap().name, "primary");
+    }
+
+    #[test]
+    fn rotating_switches_the_active_key() {
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1"));
+        ring.add(ApiKey::new("backup", "sk-ant-2"));
+        ring.rotate_to("backup").unwrap();
+        assert_eq!(ring.active_key().unwrap().name, "backup");
+    }
+
+    #[test]
+    fn removing_the_active_key_clears_the_active_pointer() {
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1"));
+        assert!(ring.remove("primary"));
+        assert!(ring.active_key().is_none());
+    }
+
+    #[test]
+    fn health_reports_no_active_key_when_the_ring_is_empty() {
+        assert_eq!(check_health(&KeyRing::new(), Utc::now()), CredentialHealth::NoActiveKey);
+    }
+
+    #[test]
+    fn health_reports_expired_for_a_lapsed_key() {
+        let now = Utc::now();
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1").with_expiry(now - Duration::seconds(1)));
+        assert_eq!(check_health(&ring, now), CredentialHealth::Expired { name: "primary".to_string() });
+    }
+
+    #[test]
+    fn health_is_ok_for_a_present_unexpired_key() {
+        let now = Utc::now();
+        let mut ring = KeyRing::new();
+        ring.add(ApiKey::new("primary", "sk-ant-1").with_expiry(now + Duration::hours(1)));
+        assert_eq!(check_health(&ring, now), CredentialHealth::Ok);
+    }
+
+    #[test]
+    fn env_key_store_treats_empty_values_as_absent() {
+        std::env::set_var("LOOPR_TEST_EMPTY_KEY", "");
+        let store = EnvKeyStore;
+        assert_eq!(store.get("LOOPR_TEST_EMPTY_KEY").unwrap(), None);
+        std::env::remove_var("LOOPR_TEST_EMPTY_KEY");
+    }
+}
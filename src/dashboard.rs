@@ -0,0 +1,128 @@
+//! Read-only web dashboard: a single static HTML page rendering the loop
+//! tree, statuses, costs, and artifacts, for teammates without terminal
+//! access to monitor a shared daemon from a browser. This module only
+//! builds the page's HTML from already-fetched data, the same split as
+//! [`crate::manifest`]'s JSON rendering; serving it from the daemon's
+//! HTTP listener is left to the daemon, which doesn't have one yet.
+
+use crate::domain::{LoopRecord, LoopStatus, LoopType};
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// One loop's row in the dashboard table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashboardRow {
+    pub id: Uuid,
+    pub description: String,
+    pub loop_type: LoopType,
+    pub status: LoopStatus,
+    pub cost_usd: f64,
+    pub iterations: usize,
+    pub artifact_versions: usize,
+}
+
+fn row(storage: &dyn Storage, record: &LoopRecord) -> anyhow::Result<DashboardRow> {
+    Ok(DashboardRow {
+        id: record.id,
+        description: record.description.clone(),
+        loop_type: record.loop_type.clone(),
+        status: record.status,
+        cost_usd: record.iterations.iter().map(|iteration| iteration.cost_usd).sum(),
+        iterations: record.iterations.len(),
+        artifact_versions: storage.artifact_history(record.id)?.len(),
+    })
+}
+
+/// Builds one [`DashboardRow`] per loop in storage.
+pub fn build_rows(storage: &dyn Storage) -> anyhow::Result<Vec<DashboardRow>> {
+    storage.list_loops()?.iter().map(|record| row(storage, record)).collect()
+}
+
+/// Escapes the handful of characters that matter inside HTML text
+/// content, since a loop's description is operator-supplied text, not
+/// markup.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Renders `rows` as a single static HTML page: a table of every loop's
+/// type, status, cost, iteration count, and artifact version count.
+pub fn render_html(rows: &[DashboardRow]) -> String {
+    let mut body = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>loopr dashboard</title></head><body>\n\
+         <h1>loopr dashboard</h1>\n<table border=\"1\">\n\
+         <tr><th>id</th><th>description</th><th>type</th><th>status</th><th>cost</th><th>iterations</th><th>artifacts</th></tr>\n",
+    );
+    for row in rows {
+        body.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:?}</td><td>{:?}</td><td>${:.2}</td><td>{}</td><td>{}</td></tr>\n",
+            row.id,
+            escape_html(&row.description),
+            row.loop_type,
+            row.status,
+            row.cost_usd,
+            row.iterations,
+            row.artifact_versions,
+        ));
+    }
+    body.push_str("</table>\n</body></html>\n");
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Iteration;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn build_rows_aggregates_cost_iterations_and_artifacts() {
+        let storage = InMemoryStorage::new();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let mut iteration = Iteration::new(0);
+        iteration.cost_usd = 1.5;
+        record.iterations = vec![iteration];
+        let id = record.id;
+        storage.save_loop(record).unwrap();
+        storage.save_artifact_version(id, 0, "draft".to_string()).unwrap();
+
+        let rows = build_rows(&storage).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].cost_usd, 1.5);
+        assert_eq!(rows[0].iterations, 1);
+        assert_eq!(rows[0].artifact_versions, 1);
+    }
+
+    #[test]
+    fn render_html_includes_a_row_per_loop() {
+        let rows = vec![DashboardRow {
+            id: Uuid::new_v4(),
+            description: "fix the bug".to_string(),
+            loop_type: LoopType::Ralph,
+            status: LoopStatus::Running,
+            cost_usd: 1.5,
+            iterations: 1,
+            artifact_versions: 1,
+        }];
+        let html = render_html(&rows);
+        assert!(html.contains("<!DOCTYPE html>"));
+        assert!(html.contains("fix the bug"));
+        assert!(html.contains("$1.50"));
+    }
+
+    #[test]
+    fn render_html_escapes_description_markup() {
+        let rows = vec![DashboardRow {
+            id: Uuid::new_v4(),
+            description: "<script>alert(1)</script>".to_string(),
+            loop_type: LoopType::Ralph,
+            status: LoopStatus::Running,
+            cost_usd: 0.0,
+            iterations: 0,
+            artifact_versions: 0,
+        }];
+        let html = render_html(&rows);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+}
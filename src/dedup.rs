@@ -0,0 +1,88 @@
+//! Detects near-duplicate pending loops, which show up after plan
+//! iteration spawns two specs whose phases end up describing the same
+//! task. Flagging them before they run avoids paying for two loops' worth
+//! of API spend on identical work.
+
+use crate::domain::{LoopRecord, LoopStatus};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Two pending loops at or above this description similarity are treated
+/// as the same work.
+pub const DUPLICATE_THRESHOLD: f64 = 0.6;
+
+/// A pair of pending loops judged to be near-duplicate work, left for an
+/// operator (or an auto-merge policy) to resolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicatePair {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub similarity_pct: u32,
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).map(|word| word.to_lowercase()).collect()
+}
+
+fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// Finds every pair of `Pending` loops among `records` whose description
+/// similarity is at or above `threshold`.
+pub fn find_duplicates(records: &[LoopRecord], threshold: f64) -> Vec<DuplicatePair> {
+    let pending: Vec<&LoopRecord> = records.iter().filter(|record| record.status == LoopStatus::Pending).collect();
+    let mut pairs = Vec::new();
+    for i in 0..pending.len() {
+        for j in (i + 1)..pending.len() {
+            let similarity = jaccard_similarity(&words(&pending[i].description), &words(&pending[j].description));
+            if similarity >= threshold {
+                pairs.push(DuplicatePair {
+                    a: pending[i].id,
+                    b: pending[j].id,
+                    similarity_pct: (similarity * 100.0).round() as u32,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+
+    #[test]
+    fn flags_two_pending_loops_with_near_identical_descriptions() {
+        let a = LoopRecord::new(LoopType::Phase, None, "add input validation to the login handler");
+        let b = LoopRecord::new(LoopType::Phase, None, "add input validation to the login handler endpoint");
+        let duplicates = find_duplicates(&[a.clone(), b.clone()], DUPLICATE_THRESHOLD);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(HashSet::from([duplicates[0].a, duplicates[0].b]), HashSet::from([a.id, b.id]));
+    }
+
+    #[test]
+    fn unrelated_descriptions_are_not_flagged() {
+        let a = LoopRecord::new(LoopType::Phase, None, "add input validation to the login handler");
+        let b = LoopRecord::new(LoopType::Phase, None, "rewrite the README install instructions");
+        assert!(find_duplicates(&[a, b], DUPLICATE_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn loops_that_are_not_pending_are_excluded() {
+        let mut a = LoopRecord::new(LoopType::Phase, None, "add input validation to the login handler");
+        a.status = LoopStatus::Completed;
+        let b = LoopRecord::new(LoopType::Phase, None, "add input validation to the login handler");
+        assert!(find_duplicates(&[a, b], DUPLICATE_THRESHOLD).is_empty());
+    }
+}
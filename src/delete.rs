@@ -0,0 +1,107 @@
+//! Cascading deletion of a loop and its descendants. A Plan's Specs, a
+//! Spec's Phases, and so on down the hierarchy all reference their parent
+//! via `parent_id`, so deleting just the requested loop would leave its
+//! children behind with a dangling `parent_id` forever.
+
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// What a cascading delete removed, in the order records were dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletionSummary {
+    pub deleted: Vec<Uuid>,
+}
+
+/// Every loop id reachable from `root` (inclusive) by following
+/// `parent_id` downward.
+pub(crate) fn descendants_of(storage: &dyn Storage, root: Uuid) -> anyhow::Result<Vec<Uuid>> {
+    let all = storage.list_loops()?;
+    let mut ids = vec![root];
+    let mut frontier = vec![root];
+    while let Some(parent) = frontier.pop() {
+        for record in &all {
+            if record.parent_id == Some(parent) && !ids.contains(&record.id) {
+                ids.push(record.id);
+                frontier.push(record.id);
+            }
+        }
+    }
+    Ok(ids)
+}
+
+/// Deletes `id` and its storage-layer data. If `id` has descendants, this
+/// refuses unless `force` is set, so a plan isn't deleted out from under
+/// specs that are still running without the operator meaning to.
+pub fn delete_loop(storage: &dyn Storage, id: Uuid, force: bool) -> anyhow::Result<DeletionSummary> {
+    let ids = descendants_of(storage, id)?;
+    if ids.len() > 1 && !force {
+        anyhow::bail!("loop {id} has {} descendant loop(s); pass --force to delete them too", ids.len() - 1);
+    }
+    let mut deleted = Vec::new();
+    for candidate in ids {
+        if storage.delete_loop(candidate)? {
+            deleted.push(candidate);
+        }
+    }
+    Ok(DeletionSummary { deleted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{LoopRecord, LoopType};
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn deletes_a_leaf_loop_with_no_descendants() {
+        let storage = InMemoryStorage::new();
+        let record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let id = record.id;
+        storage.save_loop(record).unwrap();
+
+        let summary = delete_loop(&storage, id, false).unwrap();
+        assert_eq!(summary.deleted, vec![id]);
+        assert!(storage.get_loop(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn refuses_to_delete_a_loop_with_descendants_without_force() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship feature x");
+        let plan_id = plan.id;
+        let spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "spec 1");
+        storage.save_loop(plan).unwrap();
+        storage.save_loop(spec).unwrap();
+
+        let err = delete_loop(&storage, plan_id, false).unwrap_err();
+        assert!(err.to_string().contains("descendant"));
+        assert!(storage.get_loop(plan_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn force_cascades_through_every_descendant() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship feature x");
+        let plan_id = plan.id;
+        let spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "spec 1");
+        let spec_id = spec.id;
+        let phase = LoopRecord::new(LoopType::Phase, Some(spec_id), "phase 1");
+        let phase_id = phase.id;
+        storage.save_loop(plan).unwrap();
+        storage.save_loop(spec).unwrap();
+        storage.save_loop(phase).unwrap();
+
+        let summary = delete_loop(&storage, plan_id, true).unwrap();
+        assert_eq!(summary.deleted.len(), 3);
+        assert!(storage.get_loop(plan_id).unwrap().is_none());
+        assert!(storage.get_loop(spec_id).unwrap().is_none());
+        assert!(storage.get_loop(phase_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn deleting_an_unknown_id_removes_nothing() {
+        let storage = InMemoryStorage::new();
+        let summary = delete_loop(&storage, Uuid::new_v4(), false).unwrap();
+        assert!(summary.deleted.is_empty());
+    }
+}
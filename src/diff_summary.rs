@@ -0,0 +1,105 @@
+//! Compact per-iteration change summaries: files touched, added/removed
+//! line counts, and new tests added, computed from a worktree diff and
+//! attached to each [`crate::domain::Iteration`] and its completion
+//! event so the TUI tree and `loopr status --detailed` show progress at
+//! a glance without opening the diff viewer.
+
+use serde::{Deserialize, Serialize};
+
+/// A compact summary of one iteration's diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub files_changed: Vec<String>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    /// Count of added lines that look like a new test declaration, a
+    /// rough proxy across the stacks in [`crate::profiles`] (Rust, Node,
+    /// Python, Go) that doesn't require parsing each language's AST.
+    pub tests_added: usize,
+}
+
+impl DiffSummary {
+    pub fn is_empty(&self) -> bool {
+        self.files_changed.is_empty() && self.lines_added == 0 && self.lines_removed == 0
+    }
+}
+
+/// Substrings that mark an added line as declaring a new test.
+const TEST_MARKERS: &[&str] = &["#[test]", "fn test_", "it(", "def test_", "func Test"];
+
+/// Parses a unified diff (`git diff` output) into a [`DiffSummary`].
+/// Diff headers and unchanged context lines are ignored.
+pub fn summarize(diff: &str) -> DiffSummary {
+    let mut summary = DiffSummary::default();
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ b/") {
+            if path != "/dev/null" {
+                summary.files_changed.push(path.to_string());
+            }
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if let Some(added) = line.strip_prefix('+') {
+            summary.lines_added += 1;
+            if TEST_MARKERS.iter().any(|marker| added.contains(marker)) {
+                summary.tests_added += 1;
+            }
+        } else if line.starts_with('-') {
+            summary.lines_removed += 1;
+        }
+    }
+    summary.files_changed.sort();
+    summary.files_changed.dedup();
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,4 @@
+ fn existing() {}
++fn added() {}
++#[test]
++fn test_added() {}
+-fn removed() {}
+";
+
+    #[test]
+    fn counts_added_and_removed_lines() {
+        let summary = summarize(DIFF);
+        assert_eq!(summary.lines_added, 3);
+        assert_eq!(summary.lines_removed, 1);
+    }
+
+    #[test]
+    fn tracks_the_touched_file() {
+        let summary = summarize(DIFF);
+        assert_eq!(summary.files_changed, vec!["src/lib.rs".to_string()]);
+    }
+
+    #[test]
+    fn counts_a_new_test_function() {
+        let summary = summarize(DIFF);
+        assert_eq!(summary.tests_added, 2);
+    }
+
+    #[test]
+    fn an_empty_diff_summarizes_to_nothing() {
+        assert!(summarize("").is_empty());
+    }
+
+    #[test]
+    fn a_deleted_file_is_not_counted_as_changed() {
+        let diff = "diff --git a/old.rs b/old.rs\n--- a/old.rs\n+++ /dev/null\n-fn gone() {}\n";
+        let summary = summarize(diff);
+        assert!(summary.files_changed.is_empty());
+        assert_eq!(summary.lines_removed, 1);
+    }
+}
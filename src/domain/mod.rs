@@ -0,0 +1,366 @@
+//! Core domain types shared across the daemon, storage, and TUI layers.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The loop hierarchy. `Plan`/`Spec`/`Phase`/`Ralph` are the built-ins;
+/// `Custom` carries the name of a project-defined loop type declared in
+/// YAML (see [`crate::loop_types`]), so teams can add types like
+/// "Research" or "Docs" without forking the enum.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopType {
+    Plan,
+    Spec,
+    Phase,
+    Ralph,
+    Custom(String),
+}
+
+impl LoopType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LoopType::Plan => "plan",
+            LoopType::Spec => "spec",
+            LoopType::Phase => "phase",
+            LoopType::Ralph => "ralph",
+            LoopType::Custom(name) => name,
+        }
+    }
+}
+
+/// Lifecycle status of a loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopStatus {
+    Pending,
+    Running,
+    AwaitingApproval,
+    Validating,
+    Completed,
+    Failed,
+    Cancelled,
+    Invalidated,
+}
+
+/// Classification of why a validation gate failed. Used to tailor the
+/// feedback phrasing fed back into the next iteration's prompt; see
+/// [`crate::failure::classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCategory {
+    CompileError,
+    TestAssertion,
+    Lint,
+    FormatStructure,
+    JudgeSubjective,
+    Timeout,
+    Infra,
+}
+
+/// A single iteration attempt within a `Ralph` loop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Iteration {
+    pub index: u32,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub feedback: Option<String>,
+    pub failure_category: Option<FailureCategory>,
+    /// Hash of the prompt template used to render this iteration's prompt,
+    /// so we can tell which wording produced which outcome.
+    pub prompt_version: Option<String>,
+    /// Estimated USD cost of the LLM calls made during this iteration.
+    pub cost_usd: f64,
+    /// The model that actually served this iteration's completion, which
+    /// may be a fallback rather than the project's configured primary;
+    /// see [`crate::llm::FallbackLlmClient`].
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub input_tokens: u32,
+    #[serde(default)]
+    pub output_tokens: u32,
+    /// Compact summary of this iteration's diff, so the TUI tree and
+    /// `loopr status --detailed` can show progress without opening the
+    /// diff viewer; see [`crate::diff_summary::summarize`].
+    #[serde(default)]
+    pub diff_summary: Option<crate::diff_summary::DiffSummary>,
+}
+
+impl Iteration {
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            started_at: Utc::now(),
+            finished_at: None,
+            feedback: None,
+            failure_category: None,
+            prompt_version: None,
+            cost_usd: 0.0,
+            model: None,
+            input_tokens: 0,
+            output_tokens: 0,
+            diff_summary: None,
+        }
+    }
+}
+
+/// The persisted record of a loop: its identity, place in the hierarchy,
+/// and iteration history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRecord {
+    pub id: Uuid,
+    pub loop_type: LoopType,
+    pub parent_id: Option<Uuid>,
+    pub description: String,
+    pub status: LoopStatus,
+    pub created_at: DateTime<Utc>,
+    pub iterations: Vec<Iteration>,
+    /// The chat conversation this loop was created from, if any. Lets a
+    /// plan created via `/plan` be traced back to the discussion that
+    /// shaped it.
+    pub conversation_id: Option<Uuid>,
+    /// A summarized excerpt of that conversation, carried into this loop's
+    /// prompts so the planning LLM knows what was already decided.
+    pub carried_context: Option<String>,
+    /// Free-form tags an operator attaches for filtering large histories,
+    /// e.g. `["backend", "urgent"]`.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// An operator's manual override of this loop's scheduling priority;
+    /// see [`crate::priority`]. `None` means the automatic priority applies.
+    #[serde(default)]
+    pub priority_override: Option<i32>,
+    /// The issue-tracker ticket this plan is linked to, e.g. `PROJ-123`;
+    /// see [`crate::ticket`].
+    #[serde(default)]
+    pub ticket_id: Option<String>,
+    /// Per-gate results from this loop's last validation run, for the
+    /// TUI's gate checklist; see [`crate::validation::summarize`].
+    #[serde(default)]
+    pub last_gate_results: Vec<crate::validation::GateSummary>,
+    /// Worktree-relative paths an operator has pinned so their contents
+    /// are always included in this loop's prompt; see [`crate::pins`].
+    #[serde(default)]
+    pub pinned_files: Vec<String>,
+    /// Restricts this loop (and every descendant spawned from it) to a
+    /// subtree of a monorepo, so multiple teams can run loopr concurrently
+    /// against different areas of the same repository; see
+    /// [`crate::tools::ToolContext::with_scope_path`].
+    #[serde(default)]
+    pub scope_path: Option<String>,
+    /// An operator's override of the model this loop's iterations use,
+    /// in place of the project's configured default; see
+    /// [`crate::clone::clone_loop`]. `None` means the project default
+    /// applies.
+    #[serde(default)]
+    pub model_override: Option<String>,
+    /// The local branch this loop took over from an in-progress human
+    /// change, for a `Ralph` loop created by [`crate::adopt::adopt_branch`]
+    /// rather than spawned from a parent `Phase`. `None` for every loop
+    /// spawned the normal way.
+    #[serde(default)]
+    pub adopted_branch: Option<String>,
+    /// The shell command an adopted loop's work must pass, supplied by
+    /// the operator since there's no parent `Phase` artifact to derive
+    /// one from; see [`crate::adopt::adopt_branch`].
+    #[serde(default)]
+    pub validation_command: Option<String>,
+    /// Named snapshots of a good intermediate worktree state, tagged by
+    /// the loop itself or an operator; see [`crate::checkpoint`].
+    #[serde(default)]
+    pub checkpoints: Vec<crate::checkpoint::Checkpoint>,
+    /// The predicted iterations, duration, and cost sized for this plan
+    /// before it ran, so an operator reviewing an approval (or `loopr
+    /// tree`) can see what was predicted alongside what actually
+    /// happened; see [`crate::estimate::estimate_plan`]. `None` until a
+    /// plan has been fully decomposed into specs and phases.
+    #[serde(default)]
+    pub estimate: Option<crate::estimate::PlanEstimate>,
+}
+
+impl LoopRecord {
+    pub fn new(loop_type: LoopType, parent_id: Option<Uuid>, description: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            loop_type,
+            parent_id,
+            description: description.into(),
+            status: LoopStatus::Pending,
+            created_at: Utc::now(),
+            iterations: Vec::new(),
+            conversation_id: None,
+            carried_context: None,
+            labels: Vec::new(),
+            priority_override: None,
+            ticket_id: None,
+            last_gate_results: Vec::new(),
+            pinned_files: Vec::new(),
+            scope_path: None,
+            model_override: None,
+            adopted_branch: None,
+            validation_command: None,
+            checkpoints: Vec::new(),
+            estimate: None,
+        }
+    }
+
+    /// Tally of this loop's iterations by [`FailureCategory`], used to
+    /// surface which kind of failure a loop keeps hitting.
+    pub fn failure_counts(&self) -> std::collections::HashMap<FailureCategory, u32> {
+        let mut counts = std::collections::HashMap::new();
+        for iteration in &self.iterations {
+            if let Some(category) = iteration.failure_category {
+                *counts.entry(category).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn has_label(&self, label: &str) -> bool {
+        self.labels.iter().any(|existing| existing == label)
+    }
+
+    pub fn with_ticket(mut self, ticket_id: impl Into<String>) -> Self {
+        self.ticket_id = Some(ticket_id.into());
+        self
+    }
+
+    /// Records the per-gate breakdown of this loop's last validation run,
+    /// replacing any previous run's results.
+    pub fn with_gate_results(mut self, gate_results: Vec<crate::validation::GateSummary>) -> Self {
+        self.last_gate_results = gate_results;
+        self
+    }
+
+    /// Attaches the predicted iterations, duration, and cost sized for
+    /// this plan; see [`crate::estimate::estimate_plan`].
+    pub fn with_estimate(mut self, estimate: crate::estimate::PlanEstimate) -> Self {
+        self.estimate = Some(estimate);
+        self
+    }
+
+    /// Pins `path` so its contents are always included in this loop's
+    /// prompt; a no-op if it's already pinned.
+    pub fn pin_file(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if !self.pinned_files.iter().any(|existing| existing == &path) {
+            self.pinned_files.push(path);
+        }
+    }
+
+    pub fn with_scope_path(mut self, scope_path: impl Into<String>) -> Self {
+        self.scope_path = Some(scope_path.into());
+        self
+    }
+
+    /// Changes this loop's description after creation (fixing a typo or
+    /// clarifying scope), returning the event that records the change so
+    /// callers can push it to connected clients. Every prompt builder
+    /// reads `description` fresh off the record, so this takes effect on
+    /// the next iteration without any further wiring.
+    pub fn update_description(&mut self, new_description: impl Into<String>) -> crate::ipc::DaemonEvent {
+        let new_description = new_description.into();
+        let old_description = std::mem::replace(&mut self.description, new_description.clone());
+        crate::ipc::DaemonEvent::DescriptionChanged { loop_id: self.id, old_description, new_description }
+    }
+
+    pub fn with_model_override(mut self, model: impl Into<String>) -> Self {
+        self.model_override = Some(model.into());
+        self
+    }
+
+    pub fn with_adopted_branch(mut self, branch: impl Into<String>) -> Self {
+        self.adopted_branch = Some(branch.into());
+        self
+    }
+
+    pub fn with_validation_command(mut self, command: impl Into<String>) -> Self {
+        self.validation_command = Some(command.into());
+        self
+    }
+
+    /// Tags `checkpoint` under its name, replacing any earlier checkpoint
+    /// of the same name (a re-tag) rather than accumulating duplicates.
+    pub fn add_checkpoint(&mut self, checkpoint: crate::checkpoint::Checkpoint) {
+        self.checkpoints.retain(|existing| existing.name != checkpoint.name);
+        self.checkpoints.push(checkpoint);
+    }
+
+    pub fn find_checkpoint(&self, name: &str) -> Option<&crate::checkpoint::Checkpoint> {
+        self.checkpoints.iter().find(|checkpoint| checkpoint.name == name)
+    }
+}
+
+/// Every record in `records` carrying `label`, for `loop.list --label` and
+/// the equivalent TUI/CLI filters.
+pub fn filter_by_label(records: &[LoopRecord], label: &str) -> Vec<LoopRecord> {
+    records.iter().filter(|record| record.has_label(label)).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn re_tagging_a_checkpoint_name_replaces_the_earlier_one() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the flaky test");
+        record.add_checkpoint(crate::checkpoint::Checkpoint::new("good-attempt", Some(0), "diff-1", vec![]));
+        record.add_checkpoint(crate::checkpoint::Checkpoint::new("good-attempt", Some(2), "diff-2", vec![]));
+        assert_eq!(record.checkpoints.len(), 1);
+        assert_eq!(record.find_checkpoint("good-attempt").unwrap().diff, "diff-2");
+    }
+
+    #[test]
+    fn update_description_replaces_the_description_and_reports_both_values() {
+        let mut record = LoopRecord::new(LoopType::Plan, None, "fix the the login bug");
+        let loop_id = record.id;
+        let event = record.update_description("fix the login bug");
+        assert_eq!(record.description, "fix the login bug");
+        assert_eq!(
+            event,
+            crate::ipc::DaemonEvent::DescriptionChanged {
+                loop_id,
+                old_description: "fix the the login bug".to_string(),
+                new_description: "fix the login bug".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn with_scope_path_sets_the_scope() {
+        let record = LoopRecord::new(LoopType::Plan, None, "backend work").with_scope_path("services/backend/");
+        assert_eq!(record.scope_path, Some("services/backend/".to_string()));
+    }
+
+    #[test]
+    fn pinning_a_file_twice_only_keeps_one_entry() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix auth");
+        record.pin_file("src/auth/service.rs");
+        record.pin_file("src/auth/service.rs");
+        assert_eq!(record.pinned_files, vec!["src/auth/service.rs".to_string()]);
+    }
+
+    #[test]
+    fn new_loop_record_starts_pending() {
+        let record = LoopRecord::new(LoopType::Plan, None, "do the thing");
+        assert_eq!(record.status, LoopStatus::Pending);
+        assert!(record.iterations.is_empty());
+    }
+
+    #[test]
+    fn filter_by_label_keeps_only_matching_records() {
+        let backend = LoopRecord::new(LoopType::Phase, None, "fix the queue").with_labels(vec!["backend".to_string()]);
+        let frontend = LoopRecord::new(LoopType::Phase, None, "fix the modal").with_labels(vec!["frontend".to_string()]);
+        let backend_id = backend.id;
+        let matches = filter_by_label(&[backend, frontend], "backend");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, backend_id);
+    }
+}
@@ -0,0 +1,146 @@
+//! Opens a loop's worktree, artifact, or failing `file:line` in the
+//! operator's configured editor, from a TUI keybinding or `loopr open`.
+//! Building the argv is pure and tested; actually spawning the editor
+//! process is not, the same split [`crate::tools::executor`] uses for
+//! shelling out to a real command.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The editor to launch, detected from `$EDITOR`/`$VISUAL` or an
+/// explicit override. `Custom` covers anything else, launched with the
+/// `file:line` / `+line file` convention closest to `vi`'s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditorTarget {
+    VsCode,
+    Neovim,
+    Custom(String),
+}
+
+impl EditorTarget {
+    pub fn program(&self) -> &str {
+        match self {
+            EditorTarget::VsCode => "code",
+            EditorTarget::Neovim => "nvim",
+            EditorTarget::Custom(program) => program,
+        }
+    }
+}
+
+/// Picks an [`EditorTarget`] from an explicit `--editor` override, then
+/// `$VISUAL`, then `$EDITOR`, defaulting to [`EditorTarget::Neovim`]
+/// since that's this project's own default `nvim`-based workflow.
+pub fn detect_editor(override_editor: Option<&str>, visual: Option<&str>, editor_env: Option<&str>) -> EditorTarget {
+    match override_editor.or(visual).or(editor_env) {
+        Some("code") => EditorTarget::VsCode,
+        Some("nvim") | Some("vim") | Some("vi") => EditorTarget::Neovim,
+        Some(other) => EditorTarget::Custom(other.to_string()),
+        None => EditorTarget::Neovim,
+    }
+}
+
+/// What to open: a path, and an optional line to jump to (for a failing
+/// `file:line`, rather than just a worktree root or artifact file).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenTarget {
+    pub path: PathBuf,
+    pub line: Option<u32>,
+}
+
+impl OpenTarget {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), line: None }
+    }
+
+    pub fn at_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+/// Builds the argv for launching `editor` on `target`, matching each
+/// editor's own goto-line convention.
+pub fn build_args(editor: &EditorTarget, target: &OpenTarget) -> Vec<String> {
+    let path = target.path.to_string_lossy().into_owned();
+    match (editor, target.line) {
+        (EditorTarget::VsCode, Some(line)) => vec!["--goto".to_string(), format!("{path}:{line}")],
+        (EditorTarget::VsCode, None) => vec![path],
+        (EditorTarget::Neovim, Some(line)) => vec![format!("+{line}"), path],
+        (EditorTarget::Neovim, None) => vec![path],
+        (EditorTarget::Custom(_), Some(line)) => vec![format!("+{line}"), path],
+        (EditorTarget::Custom(_), None) => vec![path],
+    }
+}
+
+/// Launches `editor` on `target`, not waiting for it to exit so the TUI
+/// isn't blocked until the editor closes.
+pub fn open(editor: &EditorTarget, target: &OpenTarget) -> anyhow::Result<()> {
+    Command::new(editor.program()).args(build_args(editor, target)).spawn()?;
+    Ok(())
+}
+
+/// The `file:line` an operator wants to jump to for a gate's first
+/// failure, parsed from a gate's captured output, e.g. `src/main.rs:42`.
+pub fn parse_file_line(text: &str) -> Option<OpenTarget> {
+    let (path, line) = text.split_once(':')?;
+    let line: u32 = line.split(':').next()?.parse().ok()?;
+    if path.is_empty() {
+        return None;
+    }
+    Some(OpenTarget::new(Path::new(path)).at_line(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_explicit_override_wins_over_environment_variables() {
+        assert_eq!(detect_editor(Some("code"), Some("nvim"), Some("nvim")), EditorTarget::VsCode);
+    }
+
+    #[test]
+    fn visual_is_preferred_over_editor() {
+        assert_eq!(detect_editor(None, Some("code"), Some("nvim")), EditorTarget::VsCode);
+    }
+
+    #[test]
+    fn an_unset_environment_defaults_to_neovim() {
+        assert_eq!(detect_editor(None, None, None), EditorTarget::Neovim);
+    }
+
+    #[test]
+    fn an_unrecognized_program_is_kept_as_custom() {
+        assert_eq!(detect_editor(None, None, Some("emacs")), EditorTarget::Custom("emacs".to_string()));
+    }
+
+    #[test]
+    fn vs_code_uses_goto_with_a_colon_separated_line() {
+        let args = build_args(&EditorTarget::VsCode, &OpenTarget::new("src/main.rs").at_line(42));
+        assert_eq!(args, vec!["--goto".to_string(), "src/main.rs:42".to_string()]);
+    }
+
+    #[test]
+    fn neovim_uses_a_plus_line_argument() {
+        let args = build_args(&EditorTarget::Neovim, &OpenTarget::new("src/main.rs").at_line(42));
+        assert_eq!(args, vec!["+42".to_string(), "src/main.rs".to_string()]);
+    }
+
+    #[test]
+    fn opening_without_a_line_just_passes_the_path() {
+        let args = build_args(&EditorTarget::VsCode, &OpenTarget::new("src/main.rs"));
+        assert_eq!(args, vec!["src/main.rs".to_string()]);
+    }
+
+    #[test]
+    fn parse_file_line_extracts_the_path_and_line() {
+        let target = parse_file_line("src/main.rs:42: compile error").unwrap();
+        assert_eq!(target.path, Path::new("src/main.rs"));
+        assert_eq!(target.line, Some(42));
+    }
+
+    #[test]
+    fn parse_file_line_rejects_text_with_no_colon() {
+        assert!(parse_file_line("no line reference here").is_none());
+    }
+}
@@ -0,0 +1,134 @@
+//! Automatic escalation for a Ralph loop stuck repeating the same
+//! feedback: steps through switching to a stronger model,
+//! enabling extended thinking, and finally asking for the phase to be
+//! split, per a ladder configurable per loop type. Feedback counts as
+//! repeated only when it is exactly equal to the last iteration's;
+//! near-identical wording breaks the run, and which gate failed is not
+//! compared.
+
+use crate::domain::Iteration;
+
+/// How many consecutive iterations with identical feedback trigger the
+/// next escalation step, absent a loop-type-specific override.
+pub const DEFAULT_STUCK_THRESHOLD: usize = 3;
+
+/// One step of an escalation ladder, tried in order as a loop keeps
+/// failing the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EscalationStep {
+    /// Re-run with `model` instead of the loop type's configured default.
+    StrongerModel { model: String },
+    /// Re-run with extended thinking enabled; see
+    /// [`crate::llm::CompletionRequest::extended_thinking`].
+    ExtendedThinking,
+    /// Give up iterating and ask the parent Phase to split the work into
+    /// smaller phases.
+    SplitPhase,
+}
+
+/// A loop type's escalation ladder and the feedback-repeat threshold that
+/// advances through it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationPolicy {
+    pub stuck_threshold: usize,
+    pub steps: Vec<EscalationStep>,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            stuck_threshold: DEFAULT_STUCK_THRESHOLD,
+            steps: vec![
+                EscalationStep::StrongerModel { model: "claude-opus".to_string() },
+                EscalationStep::ExtendedThinking,
+                EscalationStep::SplitPhase,
+            ],
+        }
+    }
+}
+
+/// How many of `iterations`' most recent entries carry feedback
+/// identical to the last one — the run length of "same wording"
+/// [`next_step`] checks against a policy's threshold.
+fn repeated_feedback_run(iterations: &[Iteration]) -> usize {
+    let Some(last) = iterations.last().and_then(|iteration| iteration.feedback.as_deref()) else {
+        return 0;
+    };
+    iterations.iter().rev().take_while(|iteration| iteration.feedback.as_deref() == Some(last)).count()
+}
+
+/// Decides the next escalation step for a loop, if its most recent
+/// feedback has repeated at least `policy.stuck_threshold` times in a
+/// row. `already_tried` is how many steps of the ladder have already
+/// been applied, so a loop that's still stuck after an escalation
+/// advances to the next step instead of re-triggering the same one; once
+/// every step has been tried, `None` signals there's nothing left but to
+/// give up.
+pub fn next_step(iterations: &[Iteration], policy: &EscalationPolicy, already_tried: usize) -> Option<EscalationStep> {
+    if repeated_feedback_run(iterations) < policy.stuck_threshold {
+        return None;
+    }
+    policy.steps.get(already_tried).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iteration_with_feedback(index: u32, feedback: &str) -> Iteration {
+        let mut iteration = Iteration::new(index);
+        iteration.feedback = Some(feedback.to_string());
+        iteration
+    }
+
+    #[test]
+    fn below_the_threshold_does_not_escalate() {
+        let iterations = vec![iteration_with_feedback(0, "compile error"), iteration_with_feedback(1, "compile error")];
+        let policy = EscalationPolicy::default();
+        assert_eq!(next_step(&iterations, &policy, 0), None);
+    }
+
+    #[test]
+    fn reaching_the_threshold_triggers_the_first_step() {
+        let iterations = vec![
+            iteration_with_feedback(0, "compile error"),
+            iteration_with_feedback(1, "compile error"),
+            iteration_with_feedback(2, "compile error"),
+        ];
+        let policy = EscalationPolicy::default();
+        assert_eq!(next_step(&iterations, &policy, 0), Some(EscalationStep::StrongerModel { model: "claude-opus".to_string() }));
+    }
+
+    #[test]
+    fn still_stuck_after_one_escalation_advances_to_the_next_step() {
+        let iterations = vec![
+            iteration_with_feedback(0, "compile error"),
+            iteration_with_feedback(1, "compile error"),
+            iteration_with_feedback(2, "compile error"),
+        ];
+        let policy = EscalationPolicy::default();
+        assert_eq!(next_step(&iterations, &policy, 1), Some(EscalationStep::ExtendedThinking));
+    }
+
+    #[test]
+    fn exhausting_the_ladder_stops_escalating() {
+        let iterations = vec![
+            iteration_with_feedback(0, "compile error"),
+            iteration_with_feedback(1, "compile error"),
+            iteration_with_feedback(2, "compile error"),
+        ];
+        let policy = EscalationPolicy::default();
+        assert_eq!(next_step(&iterations, &policy, policy.steps.len()), None);
+    }
+
+    #[test]
+    fn differing_feedback_resets_the_repeat_run() {
+        let iterations = vec![
+            iteration_with_feedback(0, "compile error"),
+            iteration_with_feedback(1, "lint error"),
+            iteration_with_feedback(2, "lint error"),
+        ];
+        let policy = EscalationPolicy::default();
+        assert_eq!(next_step(&iterations, &policy, 0), None);
+    }
+}
@@ -0,0 +1,149 @@
+//! Sizing plans before they run: predicted iterations, wall-clock
+//! duration, and cost, blending historical per-type averages with the
+//! shape of the plan itself.
+
+use crate::artifact::Plan;
+use crate::domain::{LoopStatus, LoopType};
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Per-loop-type averages pulled from completed history, used to size new
+/// work of the same type.
+#[derive(Debug, Clone, Copy)]
+pub struct HistoricalAverages {
+    pub avg_iterations_per_phase: f64,
+    pub avg_minutes_per_iteration: f64,
+    pub avg_cost_per_iteration: f64,
+}
+
+impl Default for HistoricalAverages {
+    /// Conservative defaults used until a project has enough completed
+    /// Ralph loops to compute real averages from.
+    fn default() -> Self {
+        Self {
+            avg_iterations_per_phase: 3.0,
+            avg_minutes_per_iteration: 4.0,
+            avg_cost_per_iteration: 0.25,
+        }
+    }
+}
+
+/// Computes [`HistoricalAverages`] from every completed `Ralph` loop in
+/// storage, falling back to the defaults when there isn't enough history.
+pub fn historical_averages(storage: &dyn Storage) -> anyhow::Result<HistoricalAverages> {
+    let completed: Vec<_> = storage
+        .list_loops()?
+        .into_iter()
+        .filter(|l| l.loop_type == LoopType::Ralph && l.status == LoopStatus::Completed)
+        .collect();
+
+    if completed.is_empty() {
+        return Ok(HistoricalAverages::default());
+    }
+
+    let total_iterations: usize = completed.iter().map(|l| l.iterations.len()).sum();
+    let avg_iterations_per_phase = total_iterations as f64 / completed.len() as f64;
+
+    let finished_iterations: Vec<_> = completed.iter().flat_map(|l| &l.iterations).filter(|it| it.finished_at.is_some()).collect();
+
+    let defaults = HistoricalAverages::default();
+    let (avg_minutes_per_iteration, avg_cost_per_iteration) = if finished_iterations.is_empty() {
+        (defaults.avg_minutes_per_iteration, defaults.avg_cost_per_iteration)
+    } else {
+        let total_minutes: f64 = finished_iterations
+            .iter()
+            .map(|it| (it.finished_at.unwrap() - it.started_at).num_seconds() as f64 / 60.0)
+            .sum();
+        let total_cost: f64 = finished_iterations.iter().map(|it| it.cost_usd).sum();
+        let count = finished_iterations.len() as f64;
+        (total_minutes / count, total_cost / count)
+    };
+
+    Ok(HistoricalAverages { avg_iterations_per_phase, avg_minutes_per_iteration, avg_cost_per_iteration })
+}
+
+/// Predicted iterations, duration, and cost for executing a plan.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlanEstimate {
+    pub phase_count: u32,
+    pub predicted_iterations: f64,
+    pub predicted_minutes: f64,
+    pub predicted_cost_usd: f64,
+}
+
+/// Sizes `plan` by summing its phases and scaling by `history`'s
+/// per-iteration averages.
+pub fn estimate_plan(plan: &Plan, history: &HistoricalAverages) -> PlanEstimate {
+    let phase_count: u32 = plan.specs.iter().map(|s| s.phases.len() as u32).sum();
+    let predicted_iterations = phase_count as f64 * history.avg_iterations_per_phase;
+    PlanEstimate {
+        phase_count,
+        predicted_iterations,
+        predicted_minutes: predicted_iterations * history.avg_minutes_per_iteration,
+        predicted_cost_usd: predicted_iterations * history.avg_cost_per_iteration,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::{Phase, Spec};
+    use crate::domain::{Iteration, LoopRecord};
+    use crate::storage::InMemoryStorage;
+    use chrono::Duration;
+
+    #[test]
+    fn falls_back_to_defaults_with_no_completed_ralph_loops() {
+        let storage = InMemoryStorage::new();
+        let averages = historical_averages(&storage).unwrap();
+        assert_eq!(averages.avg_minutes_per_iteration, HistoricalAverages::default().avg_minutes_per_iteration);
+        assert_eq!(averages.avg_cost_per_iteration, HistoricalAverages::default().avg_cost_per_iteration);
+    }
+
+    #[test]
+    fn derives_minutes_and_cost_from_completed_iterations() {
+        let storage = InMemoryStorage::new();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        record.status = LoopStatus::Completed;
+
+        let mut first = Iteration::new(0);
+        first.finished_at = Some(first.started_at + Duration::minutes(4));
+        first.cost_usd = 0.20;
+
+        let mut second = Iteration::new(1);
+        second.finished_at = Some(second.started_at + Duration::minutes(6));
+        second.cost_usd = 0.40;
+
+        record.iterations = vec![first, second];
+        storage.save_loop(record).unwrap();
+
+        let averages = historical_averages(&storage).unwrap();
+        assert_eq!(averages.avg_minutes_per_iteration, 5.0);
+        assert!((averages.avg_cost_per_iteration - 0.30).abs() < 1e-9);
+    }
+
+    #[test]
+    fn scales_with_phase_count_and_history() {
+        let plan = Plan {
+            title: "example".into(),
+            specs: vec![Spec {
+                name: "spec".into(),
+                description: String::new(),
+                phases: vec![
+                    Phase { name: "a".into(), description: String::new() },
+                    Phase { name: "b".into(), description: String::new() },
+                ],
+            }],
+        };
+        let history = HistoricalAverages {
+            avg_iterations_per_phase: 2.0,
+            avg_minutes_per_iteration: 5.0,
+            avg_cost_per_iteration: 0.5,
+        };
+        let estimate = estimate_plan(&plan, &history);
+        assert_eq!(estimate.phase_count, 2);
+        assert_eq!(estimate.predicted_iterations, 4.0);
+        assert_eq!(estimate.predicted_minutes, 20.0);
+        assert_eq!(estimate.predicted_cost_usd, 2.0);
+    }
+}
@@ -0,0 +1,69 @@
+//! Automatic classification of validation gate output into a
+//! [`FailureCategory`], and the feedback phrasing that goes with each.
+
+use crate::domain::FailureCategory;
+
+/// Classifies a gate's raw output by keyword heuristics. Order matters:
+/// timeout and infrastructure signals are checked first, then compile
+/// errors, then test failures, so, e.g., a panic inside a test binary
+/// whose output also mentions a timeout is reported as a timeout.
+pub fn classify(gate_output: &str) -> FailureCategory {
+    let lower = gate_output.to_ascii_lowercase();
+    if lower.contains("timed out") || lower.contains("timeout") {
+        FailureCategory::Timeout
+    } else if lower.contains("connection refused") || lower.contains("could not resolve") || lower.contains("rate limit") {
+        FailureCategory::Infra
+    } else if lower.contains("error[e") || lower.contains("cannot find") || lower.contains("expected") && lower.contains("found") {
+        FailureCategory::CompileError
+    } else if lower.contains("assertion") || lower.contains("panicked at") || lower.contains("test result: failed") {
+        FailureCategory::TestAssertion
+    } else if lower.contains("clippy") || lower.contains("warning:") {
+        FailureCategory::Lint
+    } else if lower.contains("rustfmt") || lower.contains("formatting") {
+        FailureCategory::FormatStructure
+    } else {
+        FailureCategory::JudgeSubjective
+    }
+}
+
+/// Short phrasing to prepend to feedback for a given category, so the next
+/// prompt nudges the model toward the right kind of fix.
+pub fn phrasing(category: FailureCategory) -> &'static str {
+    match category {
+        FailureCategory::CompileError => "The code does not compile. Fix the error below before anything else:",
+        FailureCategory::TestAssertion => "A test failed. The implementation doesn't match the expected behavior:",
+        FailureCategory::Lint => "Lint warnings were raised. Address them without changing behavior:",
+        FailureCategory::FormatStructure => "The formatting gate failed. Run the formatter rather than hand-editing whitespace:",
+        FailureCategory::JudgeSubjective => "The reviewer pass flagged the following concern:",
+        FailureCategory::Timeout => "The previous attempt timed out. Consider a narrower or more efficient approach:",
+        FailureCategory::Infra => "The gate failed for an infrastructure reason unrelated to the code; retry once this clears:",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_compile_errors() {
+        assert_eq!(classify("error[E0308]: mismatched types"), FailureCategory::CompileError);
+    }
+
+    #[test]
+    fn classifies_test_assertions() {
+        assert_eq!(
+            classify("thread 'main' panicked at 'assertion failed: left == right'"),
+            FailureCategory::TestAssertion
+        );
+    }
+
+    #[test]
+    fn classifies_timeouts() {
+        assert_eq!(classify("command timed out after 300s"), FailureCategory::Timeout);
+    }
+
+    #[test]
+    fn falls_back_to_judge_subjective() {
+        assert_eq!(classify("the reviewer thinks this needs more detail"), FailureCategory::JudgeSubjective);
+    }
+}
@@ -0,0 +1,74 @@
+//! [`Forge`] implementation for Bitbucket Cloud's REST API.
+
+use super::{Forge, ForgeKind, ForgeRequest, IssueParams, MergeRequestParams, StatusCommentParams};
+use serde_json::json;
+
+pub struct BitbucketForge;
+
+impl Forge for BitbucketForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Bitbucket
+    }
+
+    fn open_merge_request(&self, params: &MergeRequestParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repositories/{}/pullrequests", params.project),
+            body: json!({
+                "title": params.title,
+                "description": params.description,
+                "source": { "branch": { "name": params.source_branch } },
+                "destination": { "branch": { "name": params.target_branch } },
+            }),
+        }
+    }
+
+    fn create_issue(&self, params: &IssueParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repositories/{}/issues", params.project),
+            body: json!({ "title": params.title, "content": { "raw": params.body } }),
+        }
+    }
+
+    fn post_status_comment(&self, params: &StatusCommentParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repositories/{}/commit/{}/comments", params.project, params.commit_sha),
+            body: json!({ "content": { "raw": params.body } }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_a_pull_request_with_nested_branch_objects() {
+        let request = BitbucketForge.open_merge_request(&MergeRequestParams {
+            project: "scottidler/loopr",
+            title: "add retry budget",
+            description: "implements the retry budget",
+            source_branch: "feature/retry-budget",
+            target_branch: "main",
+        });
+        assert_eq!(request.path, "/repositories/scottidler/loopr/pullrequests");
+        assert_eq!(request.body["source"]["branch"]["name"], "feature/retry-budget");
+        assert_eq!(request.body["destination"]["branch"]["name"], "main");
+    }
+
+    #[test]
+    fn creates_an_issue_with_a_nested_content_raw_field() {
+        let request = BitbucketForge.create_issue(&IssueParams { project: "scottidler/loopr", title: "flaky test", body: "fails intermittently" });
+        assert_eq!(request.path, "/repositories/scottidler/loopr/issues");
+        assert_eq!(request.body["content"]["raw"], "fails intermittently");
+    }
+
+    #[test]
+    fn posts_a_status_comment_with_a_nested_content_raw_field() {
+        let request = BitbucketForge.post_status_comment(&StatusCommentParams { project: "scottidler/loopr", commit_sha: "abc123", body: "validation passed" });
+        assert_eq!(request.path, "/repositories/scottidler/loopr/commit/abc123/comments");
+        assert_eq!(request.body["content"]["raw"], "validation passed");
+    }
+}
@@ -0,0 +1,75 @@
+//! [`Forge`] implementation for GitHub's REST API.
+
+use super::{Forge, ForgeKind, ForgeRequest, IssueParams, MergeRequestParams, StatusCommentParams};
+use serde_json::json;
+
+pub struct GitHubForge;
+
+impl Forge for GitHubForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitHub
+    }
+
+    fn open_merge_request(&self, params: &MergeRequestParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repos/{}/pulls", params.project),
+            body: json!({
+                "title": params.title,
+                "body": params.description,
+                "head": params.source_branch,
+                "base": params.target_branch,
+            }),
+        }
+    }
+
+    fn create_issue(&self, params: &IssueParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repos/{}/issues", params.project),
+            body: json!({ "title": params.title, "body": params.body }),
+        }
+    }
+
+    fn post_status_comment(&self, params: &StatusCommentParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/repos/{}/commits/{}/comments", params.project, params.commit_sha),
+            body: json!({ "body": params.body }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_a_pull_request_against_the_repo_pulls_endpoint() {
+        let request = GitHubForge.open_merge_request(&MergeRequestParams {
+            project: "scottidler/loopr",
+            title: "add retry budget",
+            description: "implements the retry budget",
+            source_branch: "feature/retry-budget",
+            target_branch: "main",
+        });
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/repos/scottidler/loopr/pulls");
+        assert_eq!(request.body["head"], "feature/retry-budget");
+        assert_eq!(request.body["base"], "main");
+    }
+
+    #[test]
+    fn creates_an_issue_against_the_repo_issues_endpoint() {
+        let request = GitHubForge.create_issue(&IssueParams { project: "scottidler/loopr", title: "flaky test", body: "fails intermittently" });
+        assert_eq!(request.path, "/repos/scottidler/loopr/issues");
+        assert_eq!(request.body["title"], "flaky test");
+    }
+
+    #[test]
+    fn posts_a_status_comment_against_the_commit() {
+        let request = GitHubForge.post_status_comment(&StatusCommentParams { project: "scottidler/loopr", commit_sha: "abc123", body: "validation passed" });
+        assert_eq!(request.path, "/repos/scottidler/loopr/commits/abc123/comments");
+        assert_eq!(request.body["body"], "validation passed");
+    }
+}
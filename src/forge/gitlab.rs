@@ -0,0 +1,79 @@
+//! [`Forge`] implementation for GitLab's REST API.
+
+use super::{Forge, ForgeKind, ForgeRequest, IssueParams, MergeRequestParams, StatusCommentParams};
+use serde_json::json;
+
+pub struct GitLabForge;
+
+/// GitLab's API addresses projects by URL-encoded `namespace/project`
+/// rather than the two separate path segments GitHub uses.
+fn project_path(project: &str) -> String {
+    project.replace('/', "%2F")
+}
+
+impl Forge for GitLabForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitLab
+    }
+
+    fn open_merge_request(&self, params: &MergeRequestParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/projects/{}/merge_requests", project_path(params.project)),
+            body: json!({
+                "title": params.title,
+                "description": params.description,
+                "source_branch": params.source_branch,
+                "target_branch": params.target_branch,
+            }),
+        }
+    }
+
+    fn create_issue(&self, params: &IssueParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/projects/{}/issues", project_path(params.project)),
+            body: json!({ "title": params.title, "description": params.body }),
+        }
+    }
+
+    fn post_status_comment(&self, params: &StatusCommentParams) -> ForgeRequest {
+        ForgeRequest {
+            method: "POST",
+            path: format!("/projects/{}/repository/commits/{}/comments", project_path(params.project), params.commit_sha),
+            body: json!({ "note": params.body }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_a_merge_request_against_the_url_encoded_project_path() {
+        let request = GitLabForge.open_merge_request(&MergeRequestParams {
+            project: "scottidler/loopr",
+            title: "add retry budget",
+            description: "implements the retry budget",
+            source_branch: "feature/retry-budget",
+            target_branch: "main",
+        });
+        assert_eq!(request.path, "/projects/scottidler%2Floopr/merge_requests");
+        assert_eq!(request.body["source_branch"], "feature/retry-budget");
+    }
+
+    #[test]
+    fn creates_an_issue_with_a_description_field_instead_of_body() {
+        let request = GitLabForge.create_issue(&IssueParams { project: "scottidler/loopr", title: "flaky test", body: "fails intermittently" });
+        assert_eq!(request.path, "/projects/scottidler%2Floopr/issues");
+        assert_eq!(request.body["description"], "fails intermittently");
+    }
+
+    #[test]
+    fn posts_a_status_comment_with_a_note_field_instead_of_body() {
+        let request = GitLabForge.post_status_comment(&StatusCommentParams { project: "scottidler/loopr", commit_sha: "abc123", body: "validation passed" });
+        assert_eq!(request.path, "/projects/scottidler%2Floopr/repository/commits/abc123/comments");
+        assert_eq!(request.body["note"], "validation passed");
+    }
+}
@@ -0,0 +1,118 @@
+//! VCS forge integration, abstracted behind a [`Forge`] trait so GitHub,
+//! GitLab, and Bitbucket (merge/pull request creation, issue intake,
+//! status comments) can be selected per project by remote URL detection
+//! or explicit config, the same way [`crate::profiles`] picks a project's
+//! stack. Each implementation only builds the exact REST request it
+//! wants executed, as plain data; actually sending it is left to an HTTP
+//! client layer, same split as [`crate::self_review`]'s prompt-building
+//! vs completion-calling.
+
+pub mod bitbucket;
+pub mod github;
+pub mod gitlab;
+
+use serde::{Deserialize, Serialize};
+
+pub use bitbucket::BitbucketForge;
+pub use github::GitHubForge;
+pub use gitlab::GitLabForge;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Bitbucket,
+}
+
+/// Remote URL substrings that identify each forge, checked in order.
+const HOSTS: &[(&str, ForgeKind)] = &[("github.com", ForgeKind::GitHub), ("gitlab.com", ForgeKind::GitLab), ("bitbucket.org", ForgeKind::Bitbucket)];
+
+/// Guesses a project's forge from its git remote URL, by host.
+pub fn detect(remote_url: &str) -> Option<ForgeKind> {
+    HOSTS.iter().find(|(host, _)| remote_url.contains(host)).map(|(_, kind)| *kind)
+}
+
+/// Resolves a project's forge, preferring an explicit config override
+/// over [`detect`]'s guess from the remote URL, for a self-hosted
+/// instance `detect` can't recognize by host.
+pub fn resolve(remote_url: Option<&str>, override_kind: Option<ForgeKind>) -> Option<ForgeKind> {
+    override_kind.or_else(|| remote_url.and_then(detect))
+}
+
+/// One HTTP request a [`Forge`] implementation wants executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeRequest {
+    pub method: &'static str,
+    pub path: String,
+    pub body: serde_json::Value,
+}
+
+/// Parameters for opening a merge/pull request.
+pub struct MergeRequestParams<'a> {
+    pub project: &'a str,
+    pub title: &'a str,
+    pub description: &'a str,
+    pub source_branch: &'a str,
+    pub target_branch: &'a str,
+}
+
+/// Parameters for filing an issue.
+pub struct IssueParams<'a> {
+    pub project: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+}
+
+/// Parameters for posting a status comment against a commit.
+pub struct StatusCommentParams<'a> {
+    pub project: &'a str,
+    pub commit_sha: &'a str,
+    pub body: &'a str,
+}
+
+/// A VCS forge's merge/pull request creation, issue intake, and status
+/// comment endpoints, as request-building functions rather than methods
+/// that perform the call.
+pub trait Forge {
+    fn kind(&self) -> ForgeKind;
+    fn open_merge_request(&self, params: &MergeRequestParams) -> ForgeRequest;
+    fn create_issue(&self, params: &IssueParams) -> ForgeRequest;
+    fn post_status_comment(&self, params: &StatusCommentParams) -> ForgeRequest;
+}
+
+/// Builds the [`Forge`] implementation matching `kind`.
+pub fn for_kind(kind: ForgeKind) -> Box<dyn Forge> {
+    match kind {
+        ForgeKind::GitHub => Box::new(GitHubForge),
+        ForgeKind::GitLab => Box::new(GitLabForge),
+        ForgeKind::Bitbucket => Box::new(BitbucketForge),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_each_forge_by_remote_host() {
+        assert_eq!(detect("git@github.com:scottidler/loopr.git"), Some(ForgeKind::GitHub));
+        assert_eq!(detect("https://gitlab.com/scottidler/loopr.git"), Some(ForgeKind::GitLab));
+        assert_eq!(detect("git@bitbucket.org:scottidler/loopr.git"), Some(ForgeKind::Bitbucket));
+    }
+
+    #[test]
+    fn an_unrecognized_host_detects_nothing() {
+        assert_eq!(detect("git@git.internal.example.com:scottidler/loopr.git"), None);
+    }
+
+    #[test]
+    fn an_explicit_override_wins_over_detection() {
+        assert_eq!(resolve(Some("git@github.com:scottidler/loopr.git"), Some(ForgeKind::GitLab)), Some(ForgeKind::GitLab));
+    }
+
+    #[test]
+    fn falls_back_to_detection_without_an_override() {
+        assert_eq!(resolve(Some("git@github.com:scottidler/loopr.git"), None), Some(ForgeKind::GitHub));
+    }
+}
@@ -0,0 +1,116 @@
+//! Diff-size and blast-radius guardrails: reject an iteration's diff
+//! before the expensive validation gates run when it changed more than
+//! the phase declared, so a runaway edit gets specific feedback instead
+//! of burning a gate run (and, potentially, landing).
+
+use glob::Pattern;
+
+/// Thresholds a worktree diff is checked against.
+#[derive(Debug, Clone, Default)]
+pub struct GuardrailConfig {
+    pub max_lines_changed: Option<usize>,
+    pub max_files_touched: Option<usize>,
+    /// Glob patterns (migrations, CI config, ...) no iteration may touch
+    /// regardless of what it declared.
+    pub protected_paths: Vec<String>,
+}
+
+/// Line/file counts pulled from a worktree diff.
+#[derive(Debug, Clone, Default)]
+pub struct DiffStat {
+    pub files_changed: Vec<String>,
+    pub lines_changed: usize,
+}
+
+/// One guardrail's rejection, worded so it can be fed straight back into
+/// the next iteration's prompt as feedback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardrailViolation {
+    pub reason: String,
+}
+
+/// Checks `stat` against `config` and the phase's declared `Files to
+/// Modify`. Runs every check rather than stopping at the first violation,
+/// so the feedback covers everything wrong with the diff at once.
+pub fn check(stat: &DiffStat, declared_files: &[String], config: &GuardrailConfig) -> Vec<GuardrailViolation> {
+    let mut violations = Vec::new();
+
+    if let Some(max) = config.max_lines_changed {
+        if stat.lines_changed > max {
+            violations.push(GuardrailViolation {
+                reason: format!("diff changed {} lines, exceeding the {max}-line limit", stat.lines_changed),
+            });
+        }
+    }
+
+    if let Some(max) = config.max_files_touched {
+        if stat.files_changed.len() > max {
+            violations.push(GuardrailViolation {
+                reason: format!("diff touched {} files, exceeding the {max}-file limit", stat.files_changed.len()),
+            });
+        }
+    }
+
+    if !declared_files.is_empty() {
+        for file in &stat.files_changed {
+            if !declared_files.iter().any(|declared| declared == file) {
+                violations.push(GuardrailViolation {
+                    reason: format!("{file} was modified but is not in the declared Files to Modify"),
+                });
+            }
+        }
+    }
+
+    for pattern in &config.protected_paths {
+        let Ok(glob) = Pattern::new(pattern) else { continue };
+        for file in &stat.files_changed {
+            if glob.matches(file) {
+                violations.push(GuardrailViolation {
+                    reason: format!("{file} matches the protected path pattern \"{pattern}\""),
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_lines_changed_over_the_limit() {
+        let stat = DiffStat { files_changed: vec!["src/lib.rs".to_string()], lines_changed: 500 };
+        let config = GuardrailConfig { max_lines_changed: Some(200), ..Default::default() };
+        let violations = check(&stat, &[], &config);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("500 lines"));
+    }
+
+    #[test]
+    fn flags_files_outside_the_declared_set() {
+        let stat = DiffStat {
+            files_changed: vec!["src/lib.rs".to_string(), "src/sneaky.rs".to_string()],
+            lines_changed: 10,
+        };
+        let violations = check(&stat, &["src/lib.rs".to_string()], &GuardrailConfig::default());
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("src/sneaky.rs"));
+    }
+
+    #[test]
+    fn flags_protected_path_matches() {
+        let stat = DiffStat { files_changed: vec!["migrations/2026_add_column.sql".to_string()], lines_changed: 5 };
+        let config = GuardrailConfig { protected_paths: vec!["migrations/*".to_string()], ..Default::default() };
+        let violations = check(&stat, &[], &config);
+        assert_eq!(violations.len(), 1);
+    }
+
+    #[test]
+    fn clean_diff_passes_with_no_violations() {
+        let stat = DiffStat { files_changed: vec!["src/lib.rs".to_string()], lines_changed: 10 };
+        let config = GuardrailConfig { max_lines_changed: Some(200), max_files_touched: Some(5), ..Default::default() };
+        assert!(check(&stat, &["src/lib.rs".to_string()], &config).is_empty());
+    }
+}
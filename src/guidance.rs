@@ -0,0 +1,60 @@
+//! Operator guidance injected into a running loop: a short note, e.g.
+//! "use the existing AuthService, don't create a new one", carried into
+//! the next iteration's prompt without pausing or restarting the loop.
+//! Folded into the current iteration's feedback so it flows through the
+//! same [`crate::prompts::build_user_prompt`] path as gate failures.
+
+use crate::domain::LoopRecord;
+
+/// Appends `note` to `record`'s latest iteration's feedback, under its own
+/// heading so it reads as operator input rather than gate output. Returns
+/// `false` (and does nothing) if the loop has no iterations yet.
+pub fn add_guidance(record: &mut LoopRecord, note: impl Into<String>) -> bool {
+    let Some(iteration) = record.iterations.last_mut() else {
+        return false;
+    };
+    let block = format!("## Operator guidance\n\n{}\n", note.into());
+    match &mut iteration.feedback {
+        Some(feedback) => {
+            feedback.push('\n');
+            feedback.push_str(&block);
+        }
+        None => iteration.feedback = Some(block),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopType};
+
+    #[test]
+    fn guidance_is_appended_to_existing_feedback() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the login flow");
+        let mut iteration = Iteration::new(0);
+        iteration.feedback = Some("compile error on line 4".to_string());
+        record.iterations.push(iteration);
+
+        assert!(add_guidance(&mut record, "use the existing AuthService, don't create a new one"));
+        let feedback = record.iterations[0].feedback.as_ref().unwrap();
+        assert!(feedback.contains("compile error on line 4"));
+        assert!(feedback.contains("Operator guidance"));
+        assert!(feedback.contains("use the existing AuthService"));
+    }
+
+    #[test]
+    fn guidance_sets_feedback_when_none_existed_yet() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the login flow");
+        record.iterations.push(Iteration::new(0));
+
+        assert!(add_guidance(&mut record, "prefer the repo's existing error type"));
+        assert!(record.iterations[0].feedback.as_ref().unwrap().contains("prefer the repo's existing error type"));
+    }
+
+    #[test]
+    fn guidance_is_a_no_op_on_a_loop_with_no_iterations() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the login flow");
+        assert!(!add_guidance(&mut record, "too early"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Short, human-friendly display ids for loops: a [`Uuid`]'s first 8 hex
+//! characters, plus unique-prefix resolution so `loopr status a3f` works
+//! without typing the full id. Centralized here so the CLI, TUI, and IPC
+//! lookup paths all agree on the same format and the same collision
+//! handling.
+
+use uuid::Uuid;
+
+/// How many hex characters of a [`Uuid`] (no hyphens) make up its short
+/// display id.
+const SHORT_ID_LEN: usize = 8;
+
+/// The short id shown in the CLI and TUI for `id`, e.g. `a3f1c2d4`.
+pub fn short_id(id: Uuid) -> String {
+    id.simple().to_string()[..SHORT_ID_LEN].to_string()
+}
+
+/// Why [`resolve_prefix`] couldn't return a single id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// No id in the candidate set starts with the given prefix.
+    NotFound,
+    /// More than one id starts with the given prefix; the caller needs to
+    /// type more of it to disambiguate.
+    Ambiguous(Vec<Uuid>),
+}
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolveError::NotFound => write!(f, "no id matches that prefix"),
+            ResolveError::Ambiguous(candidates) => write!(f, "prefix matches {} ids, type more of it", candidates.len()),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Resolves `prefix` against `candidates`, matching case-insensitively
+/// against both the short id and the full hyphenated form, so either a
+/// short id or a full id works. Errors if zero or more than one candidate
+/// matches.
+pub fn resolve_prefix(prefix: &str, candidates: &[Uuid]) -> Result<Uuid, ResolveError> {
+    let prefix = prefix.to_ascii_lowercase();
+    let matches: Vec<Uuid> = candidates
+        .iter()
+        .copied()
+        .filter(|id| id.simple().to_string().starts_with(&prefix) || id.to_string().starts_with(&prefix))
+        .collect();
+
+    match matches.len() {
+        0 => Err(ResolveError::NotFound),
+        1 => Ok(matches[0]),
+        _ => Err(ResolveError::Ambiguous(matches)),
+    }
+}
+
+/// Parses `input` as a full [`Uuid`], for CLI entry points that accept
+/// either a short id or a full one but have no candidate list on hand
+/// (e.g. while there's no daemon connection to resolve a prefix against).
+pub fn parse_full_or_prefix(input: &str, candidates: &[Uuid]) -> Result<Uuid, ResolveError> {
+    if let Ok(id) = Uuid::parse_str(input) {
+        return Ok(id);
+    }
+    resolve_prefix(input, candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_id_is_the_first_eight_hex_characters() {
+        let id = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(short_id(id), "a3f1c2d4");
+    }
+
+    #[test]
+    fn resolve_prefix_finds_a_unique_match() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        let b = Uuid::parse_str("b9012345-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(resolve_prefix("a3f", &[a, b]).unwrap(), a);
+    }
+
+    #[test]
+    fn resolve_prefix_is_case_insensitive() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(resolve_prefix("A3F", &[a]).unwrap(), a);
+    }
+
+    #[test]
+    fn resolve_prefix_reports_no_match() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(resolve_prefix("zzz", &[a]), Err(ResolveError::NotFound));
+    }
+
+    #[test]
+    fn resolve_prefix_reports_ambiguity() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        let b = Uuid::parse_str("a3f9c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        match resolve_prefix("a3f", &[a, b]) {
+            Err(ResolveError::Ambiguous(candidates)) => assert_eq!(candidates.len(), 2),
+            other => panic!("expected Ambiguous, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_full_or_prefix_accepts_a_full_id_with_no_candidates() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(parse_full_or_prefix(&a.to_string(), &[]).unwrap(), a);
+    }
+
+    #[test]
+    fn parse_full_or_prefix_falls_back_to_prefix_resolution() {
+        let a = Uuid::parse_str("a3f1c2d4-5678-4abc-9def-0123456789ab").unwrap();
+        assert_eq!(parse_full_or_prefix("a3f", &[a]).unwrap(), a);
+    }
+}
@@ -0,0 +1,97 @@
+//! Idle shutdown: the daemon checkpoints and exits once no loops are
+//! running and no clients are connected for the configured timeout,
+//! relying on client auto-start (or systemd socket activation) to
+//! relaunch it on demand. Saves memory on laptops that would otherwise
+//! keep an idle daemon resident forever.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// How long the daemon may sit idle before it shuts itself down.
+#[derive(Debug, Clone, Copy)]
+pub struct IdleShutdownPolicy {
+    pub idle_timeout: Duration,
+}
+
+impl Default for IdleShutdownPolicy {
+    fn default() -> Self {
+        Self { idle_timeout: Duration::minutes(30) }
+    }
+}
+
+/// A point-in-time snapshot of what the daemon is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaemonActivity {
+    pub running_loops: usize,
+    pub connected_clients: usize,
+}
+
+impl DaemonActivity {
+    pub fn is_idle(&self) -> bool {
+        self.running_loops == 0 && self.connected_clients == 0
+    }
+}
+
+/// Tracks how long the daemon has been continuously idle and decides when
+/// it's crossed the shutdown threshold.
+#[derive(Debug)]
+pub struct IdleTracker {
+    policy: IdleShutdownPolicy,
+    idle_since: Option<DateTime<Utc>>,
+}
+
+impl IdleTracker {
+    pub fn new(policy: IdleShutdownPolicy) -> Self {
+        Self { policy, idle_since: None }
+    }
+
+    /// Updates idle tracking from the latest activity snapshot, observed
+    /// at `now`. Any activity resets the idle clock.
+    pub fn observe(&mut self, activity: DaemonActivity, now: DateTime<Utc>) {
+        if activity.is_idle() {
+            self.idle_since.get_or_insert(now);
+        } else {
+            self.idle_since = None;
+        }
+    }
+
+    /// True once the daemon has been continuously idle for at least the
+    /// policy's timeout as of `now`.
+    pub fn should_shutdown(&self, now: DateTime<Utc>) -> bool {
+        self.idle_since.is_some_and(|since| now - since >= self.policy.idle_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(minutes: i64) -> IdleShutdownPolicy {
+        IdleShutdownPolicy { idle_timeout: Duration::minutes(minutes) }
+    }
+
+    #[test]
+    fn does_not_shut_down_before_the_timeout_elapses() {
+        let mut tracker = IdleTracker::new(policy(10));
+        let start = Utc::now();
+        tracker.observe(DaemonActivity { running_loops: 0, connected_clients: 0 }, start);
+        assert!(!tracker.should_shutdown(start + Duration::minutes(5)));
+    }
+
+    #[test]
+    fn shuts_down_once_continuously_idle_past_the_timeout() {
+        let mut tracker = IdleTracker::new(policy(10));
+        let start = Utc::now();
+        tracker.observe(DaemonActivity { running_loops: 0, connected_clients: 0 }, start);
+        assert!(tracker.should_shutdown(start + Duration::minutes(11)));
+    }
+
+    #[test]
+    fn activity_resets_the_idle_clock() {
+        let mut tracker = IdleTracker::new(policy(10));
+        let start = Utc::now();
+        tracker.observe(DaemonActivity { running_loops: 0, connected_clients: 0 }, start);
+        tracker.observe(DaemonActivity { running_loops: 1, connected_clients: 0 }, start + Duration::minutes(9));
+        tracker.observe(DaemonActivity { running_loops: 0, connected_clients: 0 }, start + Duration::minutes(9));
+        assert!(!tracker.should_shutdown(start + Duration::minutes(15)));
+    }
+}
@@ -0,0 +1,195 @@
+//! A local repo index and `semantic_search` tool, so a loop can find
+//! relevant files by meaning rather than exact grep terms. Real embedding
+//! models need a local model or an API call this crate doesn't bundle, so
+//! each file is instead vectorized by term frequency over its path,
+//! summary, and content — good enough for "find files about X" queries,
+//! entirely offline, and persisted under `~/.loopr/index/<project>`.
+//! Re-indexing is incremental: a file whose content hash hasn't changed
+//! keeps its existing entry instead of being re-summarized.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One file's entry in the index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedFile {
+    pub path: String,
+    pub content_hash: String,
+    pub summary: String,
+    pub terms: HashMap<String, f64>,
+}
+
+/// The full index for one project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoIndex {
+    pub entries: Vec<IndexedFile>,
+}
+
+/// A search result: the matched file and its cosine similarity to the
+/// query, in `[0, 1]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub path: String,
+    pub summary: String,
+    pub score: f64,
+}
+
+fn term_frequencies(text: &str) -> HashMap<String, f64> {
+    let mut counts: HashMap<String, f64> = HashMap::new();
+    let mut total = 0.0;
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        *counts.entry(word.to_lowercase()).or_insert(0.0) += 1.0;
+        total += 1.0;
+    }
+    if total > 0.0 {
+        for value in counts.values_mut() {
+            *value /= total;
+        }
+    }
+    counts
+}
+
+fn cosine_similarity(a: &HashMap<String, f64>, b: &HashMap<String, f64>) -> f64 {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let dot: f64 = shorter.iter().map(|(term, value)| value * longer.get(term).copied().unwrap_or(0.0)).sum();
+    let norm_a = a.values().map(|v| v * v).sum::<f64>().sqrt();
+    let norm_b = b.values().map(|v| v * v).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+fn hash_content(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let digest = hasher.finalize();
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Re-indexes `files` (relative path to content), reusing an `existing`
+/// entry whose content hash is unchanged and calling `summarize` only for
+/// new or modified files. Files no longer present are dropped.
+pub fn reindex_incremental(existing: &RepoIndex, files: &[(String, String)], mut summarize: impl FnMut(&str, &str) -> String) -> RepoIndex {
+    let existing_by_path: HashMap<&str, &IndexedFile> = existing.entries.iter().map(|entry| (entry.path.as_str(), entry)).collect();
+    let entries = files
+        .iter()
+        .map(|(path, content)| {
+            let content_hash = hash_content(content);
+            if let Some(previous) = existing_by_path.get(path.as_str()) {
+                if previous.content_hash == content_hash {
+                    return (*previous).clone();
+                }
+            }
+            let summary = summarize(path, content);
+            let terms = term_frequencies(&format!("{path} {summary} {content}"));
+            IndexedFile { path: path.clone(), content_hash, summary, terms }
+        })
+        .collect();
+    RepoIndex { entries }
+}
+
+/// Finds the `top_n` files in `index` most similar to `query`, highest
+/// score first.
+pub fn semantic_search(index: &RepoIndex, query: &str, top_n: usize) -> Vec<SearchHit> {
+    let query_terms = term_frequencies(query);
+    let mut hits: Vec<SearchHit> = index
+        .entries
+        .iter()
+        .map(|entry| SearchHit { path: entry.path.clone(), summary: entry.summary.clone(), score: cosine_similarity(&query_terms, &entry.terms) })
+        .collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(top_n);
+    hits
+}
+
+/// Where a project's index is persisted.
+pub fn index_path(home: &Path, project: &str) -> PathBuf {
+    home.join(".loopr").join("index").join(project).join("index.json")
+}
+
+pub fn save_index(path: &Path, index: &RepoIndex) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(index)?)?;
+    Ok(())
+}
+
+pub fn load_index(path: &Path) -> anyhow::Result<RepoIndex> {
+    if !path.exists() {
+        return Ok(RepoIndex::default());
+    }
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_the_more_relevant_file_higher() {
+        let index = reindex_incremental(
+            &RepoIndex::default(),
+            &[
+                ("src/tools/executor.rs".to_string(), "runs shell commands under a timeout budget".to_string()),
+                ("src/chat/mod.rs".to_string(), "renders chat cards for the tui".to_string()),
+            ],
+            |_, content| content.to_string(),
+        );
+        let hits = semantic_search(&index, "timeout budget for shell commands", 1);
+        assert_eq!(hits[0].path, "src/tools/executor.rs");
+    }
+
+    #[test]
+    fn reindex_incremental_reuses_unchanged_entries() {
+        let initial = reindex_incremental(&RepoIndex::default(), &[("a.rs".to_string(), "fn main() {}".to_string())], |_, c| c.to_string());
+
+        let mut summarize_calls = 0;
+        let reindexed = reindex_incremental(&initial, &[("a.rs".to_string(), "fn main() {}".to_string())], |_, c| {
+            summarize_calls += 1;
+            c.to_string()
+        });
+
+        assert_eq!(summarize_calls, 0);
+        assert_eq!(reindexed, initial);
+    }
+
+    #[test]
+    fn reindex_incremental_resummarizes_changed_files_and_drops_removed_ones() {
+        let initial = reindex_incremental(
+            &RepoIndex::default(),
+            &[("a.rs".to_string(), "v1".to_string()), ("b.rs".to_string(), "v1".to_string())],
+            |_, c| c.to_string(),
+        );
+
+        let mut summarize_calls = 0;
+        let reindexed = reindex_incremental(&initial, &[("a.rs".to_string(), "v2".to_string())], |_, c| {
+            summarize_calls += 1;
+            c.to_string()
+        });
+
+        assert_eq!(summarize_calls, 1);
+        assert_eq!(reindexed.entries.len(), 1);
+        assert_eq!(reindexed.entries[0].summary, "v2");
+    }
+
+    #[test]
+    fn save_and_load_round_trip_the_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = index_path(dir.path(), "loopr");
+        let index = reindex_incremental(&RepoIndex::default(), &[("a.rs".to_string(), "fn main() {}".to_string())], |_, c| c.to_string());
+        save_index(&path, &index).unwrap();
+        assert_eq!(load_index(&path).unwrap(), index);
+    }
+
+    #[test]
+    fn loading_a_missing_index_returns_an_empty_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = load_index(&index_path(dir.path(), "nonexistent")).unwrap();
+        assert!(index.entries.is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+//! Bounded per-loop queues for [`DaemonEvent`]s pushed to connected
+//! clients, so a chatty loop (many iterations, verbose streamed chat
+//! text) can't grow a client's backlog without bound or stall the
+//! runner waiting on a slow client to drain. Progress-style updates for
+//! the same loop and iteration coalesce into the newest one instead of
+//! queuing every intermediate update; a full queue drops its oldest
+//! entry rather than blocking the push, since the runner must never wait
+//! on a client. Wiring this to an actual channel between the daemon and
+//! its clients, and exposing `dropped` through [`crate::ipc::Methods::METRICS_GET`],
+//! is left to the daemon's transport layer, which doesn't have one yet.
+
+use super::DaemonEvent;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// Identifies which already-queued event a new one should replace rather
+/// than queue alongside, so a burst of progress updates for the same
+/// loop/iteration collapses to the latest one.
+fn coalesce_key(event: &DaemonEvent) -> Option<(Uuid, u32)> {
+    match event {
+        DaemonEvent::IterationDiffSummary { loop_id, iteration_index, .. } => Some((*loop_id, *iteration_index)),
+        _ => None,
+    }
+}
+
+/// A bounded queue of events awaiting delivery for one loop.
+pub struct EventQueue {
+    capacity: usize,
+    events: VecDeque<DaemonEvent>,
+    dropped: u64,
+}
+
+impl EventQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, events: VecDeque::new(), dropped: 0 }
+    }
+
+    /// Queues `event`, coalescing with an already-queued progress update
+    /// for the same key, or dropping the oldest entry to make room if the
+    /// queue is already at capacity.
+    pub fn push(&mut self, event: DaemonEvent) {
+        if let Some(key) = coalesce_key(&event) {
+            if let Some(existing) = self.events.iter_mut().find(|queued| coalesce_key(queued) == Some(key)) {
+                *existing = event;
+                return;
+            }
+        }
+        if self.events.len() >= self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// Removes and returns every queued event, oldest first.
+    pub fn drain(&mut self) -> Vec<DaemonEvent> {
+        self.events.drain(..).collect()
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Per-loop [`EventQueue`]s, all created with the same capacity, so one
+/// chatty loop's backlog can't starve another loop's clients.
+pub struct EventQueues {
+    capacity: usize,
+    queues: HashMap<Uuid, EventQueue>,
+}
+
+impl EventQueues {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, queues: HashMap::new() }
+    }
+
+    /// Queues `event` for `loop_id`, creating that loop's queue on first use.
+    pub fn push(&mut self, loop_id: Uuid, event: DaemonEvent) {
+        self.queues.entry(loop_id).or_insert_with(|| EventQueue::new(self.capacity)).push(event);
+    }
+
+    pub fn drain(&mut self, loop_id: Uuid) -> Vec<DaemonEvent> {
+        self.queues.get_mut(&loop_id).map(|queue| queue.drain()).unwrap_or_default()
+    }
+
+    /// Total events dropped across every loop's queue, for `metrics.get`.
+    pub fn total_dropped(&self) -> u64 {
+        self.queues.values().map(|queue| queue.dropped()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_summary::DiffSummary;
+
+    fn diff_event(loop_id: Uuid, iteration_index: u32) -> DaemonEvent {
+        DaemonEvent::IterationDiffSummary { loop_id, iteration_index, summary: DiffSummary::default() }
+    }
+
+    #[test]
+    fn repeated_progress_updates_for_the_same_iteration_coalesce() {
+        let mut queue = EventQueue::new(10);
+        let loop_id = Uuid::new_v4();
+        queue.push(diff_event(loop_id, 0));
+        queue.push(diff_event(loop_id, 0));
+        queue.push(diff_event(loop_id, 0));
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.dropped(), 0);
+    }
+
+    #[test]
+    fn distinct_iterations_do_not_coalesce() {
+        let mut queue = EventQueue::new(10);
+        let loop_id = Uuid::new_v4();
+        queue.push(diff_event(loop_id, 0));
+        queue.push(diff_event(loop_id, 1));
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn a_full_queue_drops_the_oldest_entry_instead_of_blocking() {
+        let mut queue = EventQueue::new(2);
+        queue.push(DaemonEvent::BudgetAlert { message: "a".to_string() });
+        queue.push(DaemonEvent::BudgetAlert { message: "b".to_string() });
+        queue.push(DaemonEvent::BudgetAlert { message: "c".to_string() });
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+        let drained = queue.drain();
+        assert_eq!(drained[0], DaemonEvent::BudgetAlert { message: "b".to_string() });
+    }
+
+    #[test]
+    fn event_queues_keeps_one_loops_backlog_from_affecting_another() {
+        let mut queues = EventQueues::new(1);
+        let loop_a = Uuid::new_v4();
+        let loop_b = Uuid::new_v4();
+        queues.push(loop_a, DaemonEvent::BudgetAlert { message: "a1".to_string() });
+        queues.push(loop_a, DaemonEvent::BudgetAlert { message: "a2".to_string() });
+        queues.push(loop_b, DaemonEvent::BudgetAlert { message: "b1".to_string() });
+
+        assert_eq!(queues.total_dropped(), 1);
+        assert_eq!(queues.drain(loop_b).len(), 1);
+    }
+}
@@ -0,0 +1,157 @@
+//! Large-payload handling for the IPC codec: splitting an oversized
+//! result (a big plan's artifact, a long transcript) into frames a
+//! length-prefixed JSON codec is comfortable with, and negotiating which
+//! compression algorithm a client and the daemon both support.
+//!
+//! [`CompressionAlgorithm::Zstd`] is a placeholder for a future
+//! compressor — this crate has no compression dependency, so
+//! [`compress`]/[`decompress`] only know how to handle `None`.
+//! [`negotiate_compression`] never selects `Zstd` even when both sides
+//! list it as supported, so a negotiated algorithm is always one
+//! [`compress`]/[`decompress`] can actually carry out.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    None,
+    Zstd,
+}
+
+impl CompressionAlgorithm {
+    /// Whether [`compress`]/[`decompress`] actually do something for
+    /// this algorithm, rather than just carrying the payload through.
+    fn is_implemented(&self) -> bool {
+        matches!(self, CompressionAlgorithm::None)
+    }
+}
+
+/// Picks the highest-preference *implemented* algorithm both sides
+/// support, in the order `client_supported` lists them, falling back to
+/// `None` when the two sides share nothing else implemented yet — so a
+/// negotiation can never select `Zstd`, which would otherwise silently
+/// degrade to sending chunks uncompressed under a misleading name.
+pub fn negotiate_compression(client_supported: &[CompressionAlgorithm], server_supported: &[CompressionAlgorithm]) -> CompressionAlgorithm {
+    client_supported
+        .iter()
+        .filter(|algorithm| algorithm.is_implemented())
+        .find(|algorithm| server_supported.contains(algorithm))
+        .copied()
+        .unwrap_or(CompressionAlgorithm::None)
+}
+
+/// Identity for [`CompressionAlgorithm::None`]; [`negotiate_compression`]
+/// never selects [`CompressionAlgorithm::Zstd`], so callers should never
+/// reach it here, but the fallback is kept identity rather than panicking
+/// since a stray direct call shouldn't crash the connection.
+pub fn compress(payload: &[u8], algorithm: CompressionAlgorithm) -> Vec<u8> {
+    match algorithm {
+        CompressionAlgorithm::None | CompressionAlgorithm::Zstd => payload.to_vec(),
+    }
+}
+
+/// Identity for [`CompressionAlgorithm::None`]; see [`compress`].
+pub fn decompress(payload: &[u8], algorithm: CompressionAlgorithm) -> Vec<u8> {
+    match algorithm {
+        CompressionAlgorithm::None | CompressionAlgorithm::Zstd => payload.to_vec(),
+    }
+}
+
+/// One frame of a large payload split across multiple IPC messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chunk {
+    pub message_id: Uuid,
+    pub index: u32,
+    pub total: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Splits `payload` into `Chunk`s of at most `max_chunk_bytes` each,
+/// tagged with a shared `message_id` so the receiver can reassemble them
+/// even if another message's chunks interleave on the wire.
+pub fn chunk_payload(message_id: Uuid, payload: &[u8], max_chunk_bytes: usize) -> Vec<Chunk> {
+    if payload.is_empty() {
+        return vec![Chunk { message_id, index: 0, total: 1, payload: Vec::new() }];
+    }
+    let total = payload.len().div_ceil(max_chunk_bytes) as u32;
+    payload
+        .chunks(max_chunk_bytes)
+        .enumerate()
+        .map(|(index, slice)| Chunk { message_id, index: index as u32, total, payload: slice.to_vec() })
+        .collect()
+}
+
+/// Reassembles `chunks` back into the original payload, ordering by
+/// index regardless of arrival order. Errors if any index is missing or
+/// the chunks don't all share one `message_id`/`total`.
+pub fn reassemble(mut chunks: Vec<Chunk>) -> anyhow::Result<Vec<u8>> {
+    if chunks.is_empty() {
+        anyhow::bail!("no chunks to reassemble");
+    }
+    chunks.sort_by_key(|chunk| chunk.index);
+    let message_id = chunks[0].message_id;
+    let total = chunks[0].total;
+    for (expected_index, chunk) in chunks.iter().enumerate() {
+        if chunk.message_id != message_id || chunk.total != total {
+            anyhow::bail!("chunks belong to different messages");
+        }
+        if chunk.index != expected_index as u32 {
+            anyhow::bail!("missing chunk {expected_index}");
+        }
+    }
+    if chunks.len() as u32 != total {
+        anyhow::bail!("expected {total} chunks, got {}", chunks.len());
+    }
+    Ok(chunks.into_iter().flat_map(|chunk| chunk.payload).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiation_picks_the_clients_first_mutually_supported_choice() {
+        let result = negotiate_compression(&[CompressionAlgorithm::Zstd, CompressionAlgorithm::None], &[CompressionAlgorithm::None]);
+        assert_eq!(result, CompressionAlgorithm::None);
+    }
+
+    #[test]
+    fn negotiation_falls_back_to_none_with_nothing_in_common() {
+        let result = negotiate_compression(&[CompressionAlgorithm::Zstd], &[]);
+        assert_eq!(result, CompressionAlgorithm::None);
+    }
+
+    #[test]
+    fn negotiation_never_selects_zstd_even_when_both_sides_list_it() {
+        let result = negotiate_compression(&[CompressionAlgorithm::Zstd], &[CompressionAlgorithm::Zstd]);
+        assert_eq!(result, CompressionAlgorithm::None);
+    }
+
+    #[test]
+    fn chunking_and_reassembly_round_trips() {
+        let message_id = Uuid::new_v4();
+        let payload = vec![1u8; 25];
+        let chunks = chunk_payload(message_id, &payload, 10);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(reassemble(chunks).unwrap(), payload);
+    }
+
+    #[test]
+    fn reassembly_tolerates_out_of_order_chunks() {
+        let message_id = Uuid::new_v4();
+        let payload = b"hello world".to_vec();
+        let mut chunks = chunk_payload(message_id, &payload, 4);
+        chunks.reverse();
+        assert_eq!(reassemble(chunks).unwrap(), payload);
+    }
+
+    #[test]
+    fn reassembly_errors_on_a_missing_chunk() {
+        let message_id = Uuid::new_v4();
+        let mut chunks = chunk_payload(message_id, &[0u8; 20], 5);
+        chunks.remove(1);
+        assert!(reassemble(chunks).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Message codecs for the IPC transport: how a request/response is
+//! serialized onto (and parsed off of) the wire, negotiated at
+//! `initialize` alongside [`crate::ipc::CompressionAlgorithm`].
+//!
+//! [`Codec::MessagePack`] is a placeholder for a future codec — this
+//! crate has no MessagePack dependency, so `encode`/`decode` don't
+//! handle it. [`negotiate_codec`] never selects `MessagePack` even when
+//! both sides list it as supported, so a negotiated codec is always one
+//! `encode`/`decode` can actually carry out; a connection can never pick
+//! a codec that then fails every subsequent call. `Json` stays the
+//! default: it's the one codec a developer can read off the wire with no
+//! extra tooling.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codec {
+    #[default]
+    Json,
+    NdJson,
+    MessagePack,
+}
+
+impl Codec {
+    /// Whether [`encode`](Self::encode)/[`decode`](Self::decode) are
+    /// actually implemented for this codec.
+    fn is_implemented(&self) -> bool {
+        matches!(self, Codec::Json | Codec::NdJson)
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Codec::Json => Ok(serde_json::to_vec(value)?),
+            Codec::NdJson => {
+                let mut bytes = serde_json::to_vec(value)?;
+                bytes.push(b'\n');
+                Ok(bytes)
+            }
+            Codec::MessagePack => anyhow::bail!("MessagePack is not yet implemented; negotiate_codec never selects it"),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> anyhow::Result<T> {
+        match self {
+            Codec::Json | Codec::NdJson => Ok(serde_json::from_slice(bytes.strip_suffix(b"\n").unwrap_or(bytes))?),
+            Codec::MessagePack => anyhow::bail!("MessagePack is not yet implemented; negotiate_codec never selects it"),
+        }
+    }
+}
+
+/// Picks the first *implemented* codec both sides support, preferring
+/// the client's order, falling back to [`Codec::Json`] when the two
+/// sides share nothing else implemented yet — so negotiation can never
+/// select a codec whose `encode`/`decode` would then fail every call on
+/// the connection.
+pub fn negotiate_codec(client_supported: &[Codec], server_supported: &[Codec]) -> Codec {
+    client_supported
+        .iter()
+        .filter(|codec| codec.is_implemented())
+        .find(|codec| server_supported.contains(codec))
+        .copied()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Sample {
+        name: String,
+        count: u32,
+    }
+
+    fn sample() -> Sample {
+        Sample { name: "loops".to_string(), count: 3 }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let bytes = Codec::Json.encode(&sample()).unwrap();
+        assert_eq!(Codec::Json.decode::<Sample>(&bytes).unwrap(), sample());
+    }
+
+    #[test]
+    fn nd_json_appends_and_tolerates_a_trailing_newline() {
+        let bytes = Codec::NdJson.encode(&sample()).unwrap();
+        assert_eq!(bytes.last(), Some(&b'\n'));
+        assert_eq!(Codec::NdJson.decode::<Sample>(&bytes).unwrap(), sample());
+    }
+
+    #[test]
+    fn message_pack_refuses_to_encode_or_decode() {
+        assert!(Codec::MessagePack.encode(&sample()).is_err());
+        assert!(Codec::MessagePack.decode::<Sample>(&[]).is_err());
+    }
+
+    #[test]
+    fn negotiation_never_selects_message_pack_even_when_both_sides_list_it() {
+        let codec = negotiate_codec(&[Codec::MessagePack], &[Codec::MessagePack]);
+        assert_eq!(codec, Codec::Json);
+    }
+
+    #[test]
+    fn negotiation_prefers_the_clients_order_among_mutual_support() {
+        let codec = negotiate_codec(&[Codec::MessagePack, Codec::NdJson, Codec::Json], &[Codec::Json, Codec::NdJson]);
+        assert_eq!(codec, Codec::NdJson);
+    }
+
+    #[test]
+    fn negotiation_falls_back_to_json_with_nothing_in_common() {
+        let codec = negotiate_codec(&[Codec::MessagePack], &[Codec::NdJson]);
+        assert_eq!(codec, Codec::Json);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::diff_summary::DiffSummary;
+use crate::llm::StreamChunk;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Events the daemon pushes to connected clients outside of a direct
+/// request/response, such as streamed chat text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DaemonEvent {
+    /// One chunk of a streaming `chat.send` reply, tagged with the request
+    /// id so a client juggling multiple in-flight chats can route it.
+    ChatChunk { request_id: Uuid, chunk: StreamChunk },
+    /// A loop repeatedly tried to modify a protected path; surfaced to the
+    /// operator rather than just refused silently, since repeated attempts
+    /// suggest the loop is stuck rather than just exploring.
+    OperatorAlert { loop_id: Uuid, message: String },
+    /// A spending cap was crossed and the scheduler has throttled down to
+    /// critical-labeled loops; see [`crate::budget`].
+    BudgetAlert { message: String },
+    /// An iteration finished; carries its diff summary so the TUI tree
+    /// can update a loop's progress without re-fetching the whole
+    /// `LoopRecord`.
+    IterationDiffSummary { loop_id: Uuid, iteration_index: u32, summary: DiffSummary },
+    /// A loop's description was edited after creation; see
+    /// [`crate::domain::LoopRecord::update_description`]. Carries both
+    /// values so a client can show what changed rather than just the
+    /// new text.
+    DescriptionChanged { loop_id: Uuid, old_description: String, new_description: String },
+}
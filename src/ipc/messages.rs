@@ -0,0 +1,479 @@
+//! Typed request/response pairs for the [`Methods`] constants, so an
+//! `IpcClient` can deserialize straight into a named struct instead of
+//! pattern-matching on raw `serde_json::Value` (`result["loops"]`). Each
+//! pair is named `<Verb>Request`/`<Verb>Response` after its method, and
+//! shares the same derive set the daemon would serialize with, so one
+//! definition serves both ends of the connection. `SPEC_CREATE`,
+//! `PHASE_CREATE` and `RALPH_CREATE` share [`ManualCreateRequest`], which
+//! has no [`IpcMessage`] impl; `EDITOR_OPEN` has no typed pair.
+
+use super::Methods;
+use crate::budget::BudgetViolation;
+use crate::bulk::{BulkAction, BulkActionResult};
+use crate::changelog::Changelog;
+use crate::chat::ChatCard;
+use crate::credentials::CredentialHealth;
+use crate::domain::{LoopRecord, LoopType};
+use crate::status::StatusSnapshot;
+use crate::storage::{ArtifactVersion, ChatSessionRecord};
+use crate::usage::{GroupBy, UsageRow};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopGetRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopGetResponse {
+    pub record: Option<LoopRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct LoopListRequest {
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopListResponse {
+    pub loops: Vec<LoopRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopDeleteRequest {
+    pub id: Uuid,
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopDeleteResponse {
+    pub deleted: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopBulkActionRequest {
+    pub ids: Vec<Uuid>,
+    pub action: BulkAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopBulkActionResponse {
+    pub result: BulkActionResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopSetPriorityRequest {
+    pub id: Uuid,
+    pub value: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopSetPriorityResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MetricsGetRequest;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MetricsGetResponse {
+    pub dropped_events: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactHistoryRequest {
+    pub loop_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactHistoryResponse {
+    pub versions: Vec<ArtifactVersion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactDiffRequest {
+    pub loop_id: Uuid,
+    pub before_iteration: u32,
+    pub after_iteration: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactDiffResponse {
+    pub rendered: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSendRequest {
+    pub session_id: Uuid,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSendResponse {
+    pub card: ChatCard,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanCreateRequest {
+    pub description: String,
+    pub ticket: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanCreateResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManualCreateRequest {
+    pub loop_type: LoopType,
+    pub parent_id: Option<Uuid>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManualCreateResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRespawnRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRespawnResponse {
+    pub spawned: Vec<LoopRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SchedulerPauseRequest;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SchedulerPauseResponse;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SchedulerResumeRequest;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SchedulerResumeResponse;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BudgetOverrideRequest;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BudgetOverrideResponse {
+    pub cleared_violations: Vec<BudgetViolation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReportRequest {
+    pub from: chrono::DateTime<chrono::Utc>,
+    pub to: chrono::DateTime<chrono::Utc>,
+    pub group_by: GroupBy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReportResponse {
+    pub rows: Vec<UsageRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSetParamsRequest {
+    pub session_id: Uuid,
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ChatSetParamsResponse;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompactRequest {
+    pub session_id: Uuid,
+    pub token_threshold: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatCompactResponse {
+    pub compacted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionCreateRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionCreateResponse {
+    pub session: ChatSessionRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionRenameRequest {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionRenameResponse {
+    pub session: ChatSessionRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionDeleteRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionDeleteResponse {
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ChatSessionListRequest;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatSessionListResponse {
+    pub sessions: Vec<ChatSessionRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct StatusSnapshotRequest;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusSnapshotResponse {
+    pub snapshot: StatusSnapshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct HealthCheckRequest;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthCheckResponse {
+    pub health: CredentialHealth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopChangelogRequest {
+    pub plan_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopChangelogResponse {
+    pub changelog: Changelog,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopFeedbackRequest {
+    pub id: Uuid,
+    pub iteration: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopFeedbackResponse {
+    pub rendered: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopAddGuidanceRequest {
+    pub id: Uuid,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopAddGuidanceResponse {
+    pub applied: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopPinFileRequest {
+    pub id: Uuid,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopPinFileResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopUpdateRequest {
+    pub id: Uuid,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopUpdateResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopCloneRequest {
+    pub id: Uuid,
+    pub task: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopCloneResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopAdoptRequest {
+    pub branch: String,
+    pub goal: String,
+    pub validation_command: String,
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopAdoptResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopCheckpointRequest {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopCheckpointResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRollbackRequest {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRollbackResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopApproveRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopApproveResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRejectRequest {
+    pub id: Uuid,
+    pub feedback: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopRejectResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopIterateRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopIterateResponse {
+    pub record: LoopRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopSkipRequest {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoopSkipResponse {
+    pub record: LoopRecord,
+}
+
+/// Names the method a typed request/response pair was generated for, so
+/// a dispatcher can log or route on it without re-deriving the method
+/// name from the type.
+pub trait IpcMessage {
+    const METHOD: &'static str;
+}
+
+macro_rules! impl_ipc_message {
+    ($($request:ty => $method:expr),+ $(,)?) => {
+        $(impl IpcMessage for $request {
+            const METHOD: &'static str = $method;
+        })+
+    };
+}
+
+impl_ipc_message! {
+    LoopGetRequest => Methods::LOOP_GET,
+    LoopListRequest => Methods::LOOP_LIST,
+    LoopDeleteRequest => Methods::LOOP_DELETE,
+    LoopBulkActionRequest => Methods::LOOP_BULK_ACTION,
+    LoopSetPriorityRequest => Methods::LOOP_SET_PRIORITY,
+    MetricsGetRequest => Methods::METRICS_GET,
+    ArtifactHistoryRequest => Methods::ARTIFACT_HISTORY,
+    ArtifactDiffRequest => Methods::ARTIFACT_DIFF,
+    ChatSendRequest => Methods::CHAT_SEND,
+    PlanCreateRequest => Methods::PLAN_CREATE,
+    LoopRespawnRequest => Methods::LOOP_RESPAWN,
+    SchedulerPauseRequest => Methods::SCHEDULER_PAUSE,
+    SchedulerResumeRequest => Methods::SCHEDULER_RESUME,
+    BudgetOverrideRequest => Methods::BUDGET_OVERRIDE,
+    UsageReportRequest => Methods::USAGE_REPORT,
+    ChatSetParamsRequest => Methods::CHAT_SET_PARAMS,
+    ChatCompactRequest => Methods::CHAT_COMPACT,
+    ChatSessionCreateRequest => Methods::CHAT_SESSION_CREATE,
+    ChatSessionRenameRequest => Methods::CHAT_SESSION_RENAME,
+    ChatSessionDeleteRequest => Methods::CHAT_SESSION_DELETE,
+    ChatSessionListRequest => Methods::CHAT_SESSION_LIST,
+    StatusSnapshotRequest => Methods::STATUS_SNAPSHOT,
+    HealthCheckRequest => Methods::HEALTH_CHECK,
+    LoopChangelogRequest => Methods::LOOP_CHANGELOG,
+    LoopFeedbackRequest => Methods::LOOP_FEEDBACK,
+    LoopAddGuidanceRequest => Methods::LOOP_ADD_GUIDANCE,
+    LoopPinFileRequest => Methods::LOOP_PIN_FILE,
+    LoopUpdateRequest => Methods::LOOP_UPDATE,
+    LoopCloneRequest => Methods::LOOP_CLONE,
+    LoopAdoptRequest => Methods::LOOP_ADOPT,
+    LoopCheckpointRequest => Methods::LOOP_CHECKPOINT,
+    LoopRollbackRequest => Methods::LOOP_ROLLBACK,
+    LoopApproveRequest => Methods::LOOP_APPROVE,
+    LoopRejectRequest => Methods::LOOP_REJECT,
+    LoopIterateRequest => Methods::LOOP_ITERATE,
+    LoopSkipRequest => Methods::LOOP_SKIP,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_request_type_names_its_method() {
+        assert_eq!(LoopGetRequest::METHOD, Methods::LOOP_GET);
+        assert_eq!(LoopChangelogRequest::METHOD, Methods::LOOP_CHANGELOG);
+    }
+
+    #[test]
+    fn typed_requests_round_trip_through_json() {
+        let request = LoopListRequest { label: Some("backend".to_string()) };
+        let bytes = serde_json::to_vec(&request).unwrap();
+        let decoded: LoopListRequest = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn typed_responses_round_trip_through_json() {
+        let response = LoopDeleteResponse { deleted: vec![Uuid::new_v4(), Uuid::new_v4()] };
+        let bytes = serde_json::to_vec(&response).unwrap();
+        let decoded: LoopDeleteResponse = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, response);
+    }
+}
@@ -0,0 +1,112 @@
+//! IPC method names and message shapes shared between the daemon and its
+//! clients (CLI and TUI).
+
+mod backpressure;
+mod chunking;
+mod codec;
+mod events;
+pub mod messages;
+
+pub use backpressure::{EventQueue, EventQueues};
+pub use chunking::{chunk_payload, compress, decompress, negotiate_compression, reassemble, Chunk, CompressionAlgorithm};
+pub use codec::{negotiate_codec, Codec};
+pub use events::DaemonEvent;
+pub use messages::IpcMessage;
+
+/// Canonical names of the daemon's IPC methods.
+pub struct Methods;
+
+impl Methods {
+    pub const LOOP_GET: &'static str = "loop.get";
+    pub const LOOP_LIST: &'static str = "loop.list";
+    /// Cascading delete of a loop and its descendants; see [`crate::delete::delete_loop`].
+    pub const LOOP_DELETE: &'static str = "loop.delete";
+    /// Applies one action to a batch of loop ids; see [`crate::bulk::apply_bulk_action`].
+    pub const LOOP_BULK_ACTION: &'static str = "loop.bulk_action";
+    /// Sets or clears a loop's operator priority override; see [`crate::priority`].
+    pub const LOOP_SET_PRIORITY: &'static str = "loop.set_priority";
+    pub const METRICS_GET: &'static str = "metrics.get";
+    /// Returns every recorded [`crate::storage::ArtifactVersion`] for a loop.
+    pub const ARTIFACT_HISTORY: &'static str = "artifact.history";
+    /// Returns a rendered diff between two of a loop's artifact versions.
+    pub const ARTIFACT_DIFF: &'static str = "artifact.diff";
+    /// Sends a chat message to the daemon and gets back a [`crate::chat::ChatCard`].
+    pub const CHAT_SEND: &'static str = "chat.send";
+    /// Creates a `Plan` loop, used both by the CLI and by [`crate::chat::accept_plan`]'s caller.
+    pub const PLAN_CREATE: &'static str = "plan.create";
+    /// Creates a `Spec` loop from a hand-written artifact; see [`crate::manual::create_manual_loop`].
+    pub const SPEC_CREATE: &'static str = "spec.create";
+    /// Creates a `Phase` loop from a hand-written artifact; see [`crate::manual::create_manual_loop`].
+    pub const PHASE_CREATE: &'static str = "phase.create";
+    /// Creates a `Ralph` loop from a hand-written artifact; see [`crate::manual::create_manual_loop`].
+    pub const RALPH_CREATE: &'static str = "ralph.create";
+    /// Re-parses a loop's stored artifact and spawns any missed children; see [`crate::respawn::respawn`].
+    pub const LOOP_RESPAWN: &'static str = "loop.respawn";
+    /// Stops the scheduler from starting new iterations; see [`crate::scheduler::SchedulerControl::pause`].
+    pub const SCHEDULER_PAUSE: &'static str = "scheduler.pause";
+    /// Resumes the scheduler starting new iterations; see [`crate::scheduler::SchedulerControl::resume`].
+    pub const SCHEDULER_RESUME: &'static str = "scheduler.resume";
+    /// Clears a tripped spending-cap throttle; see [`crate::budget::BudgetThrottle::override_throttle`].
+    pub const BUDGET_OVERRIDE: &'static str = "budget.override";
+    /// Aggregates token/cost usage over a time period; see [`crate::usage::build_report`].
+    pub const USAGE_REPORT: &'static str = "usage.report";
+    /// Sets the model/temperature override on a conversation.
+    pub const CHAT_SET_PARAMS: &'static str = "chat.set_params";
+    /// Summarizes older turns of a conversation once it's grown past the
+    /// token threshold; see [`crate::chat::compact`].
+    pub const CHAT_COMPACT: &'static str = "chat.compact";
+    /// Creates a named chat session; see [`crate::chat::create_session`].
+    pub const CHAT_SESSION_CREATE: &'static str = "chat.session_create";
+    /// Renames an existing chat session; see [`crate::chat::rename_session`].
+    pub const CHAT_SESSION_RENAME: &'static str = "chat.session_rename";
+    /// Deletes a chat session; see [`crate::chat::delete_session`].
+    pub const CHAT_SESSION_DELETE: &'static str = "chat.session_delete";
+    /// Lists every chat session for the TUI's session picker; see [`crate::chat::list_sessions`].
+    pub const CHAT_SESSION_LIST: &'static str = "chat.session_list";
+    /// Consolidated status for the TUI's segmented status bar; see [`crate::status::build_snapshot`].
+    pub const STATUS_SNAPSHOT: &'static str = "status.snapshot";
+    /// Reports credential health (missing/expired keys); see [`crate::credentials::check_health`].
+    pub const HEALTH_CHECK: &'static str = "health.check";
+    /// Generates (or returns the existing) changelog for a completed plan; see [`crate::changelog::generate`].
+    pub const LOOP_CHANGELOG: &'static str = "loop.changelog";
+    /// Opens a loop's worktree, artifact, or failing `file:line` in the
+    /// operator's editor; see [`crate::editor::open`].
+    pub const EDITOR_OPEN: &'static str = "editor.open";
+    /// Returns a loop's accumulated feedback exactly as it will be rendered
+    /// into the next iteration's prompt; see [`crate::prompts::render_feedback_for_inspection`].
+    pub const LOOP_FEEDBACK: &'static str = "loop.feedback";
+    /// Appends an operator note into a running loop's feedback for the
+    /// next iteration; see [`crate::guidance::add_guidance`].
+    pub const LOOP_ADD_GUIDANCE: &'static str = "loop.add_guidance";
+    /// Pins a worktree-relative file so it's always included in a loop's
+    /// prompt; see [`crate::domain::LoopRecord::pin_file`].
+    pub const LOOP_PIN_FILE: &'static str = "loop.pin_file";
+    /// Edits a loop's description after creation; see
+    /// [`crate::domain::LoopRecord::update_description`].
+    pub const LOOP_UPDATE: &'static str = "loop.update";
+    /// Duplicates a loop into a fresh, unstarted attempt; see
+    /// [`crate::clone::clone_loop`].
+    pub const LOOP_CLONE: &'static str = "loop.clone";
+    /// Imports an existing local branch of in-progress human work as a
+    /// `Ralph` loop; see [`crate::adopt::adopt_branch`].
+    pub const LOOP_ADOPT: &'static str = "loop.adopt";
+    /// Tags the current worktree state as a named checkpoint; see
+    /// [`crate::domain::LoopRecord::add_checkpoint`].
+    pub const LOOP_CHECKPOINT: &'static str = "loop.checkpoint";
+    /// Rolls a loop's worktree back to an earlier named checkpoint; see
+    /// [`crate::checkpoint::Checkpoint`].
+    pub const LOOP_ROLLBACK: &'static str = "loop.rollback";
+    /// Clears a loop out of `AwaitingApproval` as accepted; see
+    /// [`crate::tui::ApprovalAction::Approve`].
+    pub const LOOP_APPROVE: &'static str = "loop.approve";
+    /// Clears a loop out of `AwaitingApproval` with feedback for the next
+    /// iteration; see [`crate::tui::ApprovalAction::Reject`].
+    pub const LOOP_REJECT: &'static str = "loop.reject";
+    /// Sends a loop back for another iteration without specific written
+    /// feedback; see [`crate::tui::ApprovalAction::Iterate`].
+    pub const LOOP_ITERATE: &'static str = "loop.iterate";
+    /// Defers an approval-queue entry without changing the loop's status,
+    /// so the operator can come back to it later; see
+    /// [`crate::tui::ApprovalAction::Skip`].
+    pub const LOOP_SKIP: &'static str = "loop.skip";
+}
@@ -0,0 +1,71 @@
+//! loopr: an autonomous iterate-and-validate loop orchestrator.
+//!
+//! A `Plan` decomposes into `Spec`s, which decompose into `Phase`s, which are
+//! executed by `Ralph` loops that repeatedly call an LLM, run tools against a
+//! worktree, and validate the result until the gates pass or the budget runs
+//! out.
+
+pub mod adopt;
+pub mod analytics;
+pub mod artifact;
+pub mod budget;
+pub mod bulk;
+pub mod changelog;
+pub mod chaos;
+pub mod chat;
+pub mod checkpoint;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod clone;
+pub mod config;
+pub mod context;
+pub mod credentials;
+pub mod dashboard;
+pub mod dedup;
+pub mod delete;
+pub mod diff_summary;
+pub mod domain;
+pub mod editor;
+pub mod escalation;
+pub mod estimate;
+pub mod failure;
+pub mod forge;
+pub mod guardrails;
+pub mod guidance;
+pub mod id;
+pub mod idle;
+pub mod index;
+pub mod ipc;
+pub mod llm;
+pub mod loop_types;
+pub mod manifest;
+pub mod manual;
+pub mod memory;
+pub mod metrics;
+pub mod onboarding;
+pub mod patch;
+pub mod phase;
+pub mod pins;
+pub mod postmortem;
+pub mod priority;
+pub mod profiles;
+pub mod progress;
+pub mod prompts;
+pub mod rbac;
+pub mod reference_repos;
+pub mod respawn;
+pub mod runner;
+pub mod scheduler;
+pub mod self_review;
+pub mod simulate;
+pub mod split;
+pub mod status;
+pub mod storage;
+pub mod templates;
+pub mod ticket;
+pub mod tools;
+pub mod tree;
+pub mod tui;
+pub mod usage;
+pub mod validation;
+pub mod watchdog;
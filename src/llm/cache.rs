@@ -0,0 +1,130 @@
+//! Content-addressed caching for LLM completions: an identical prompt
+//! (same model, system, messages, and temperature) reuses a prior
+//! response within its TTL, instead of paying for another round trip.
+//! Useful for format/judge gates and replayed iterations where the same
+//! prompt recurs verbatim. A request can opt out via
+//! [`CompletionRequest::bypass_cache`].
+
+use super::{CompletionRequest, CompletionResponse, LlmClient, MessageContent};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct CacheEntry {
+    response: CompletionResponse,
+    inserted_at: Instant,
+}
+
+/// Wraps an [`LlmClient`], caching responses by a hash of the request
+/// contents for `ttl`.
+pub struct CachingLlmClient<C: LlmClient> {
+    inner: C,
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl<C: LlmClient> CachingLlmClient<C> {
+    pub fn new(inner: C, ttl: Duration) -> Self {
+        Self { inner, ttl, entries: Mutex::new(HashMap::new()) }
+    }
+}
+
+fn hash_request(request: &CompletionRequest) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(request.model.as_bytes());
+    hasher.update(request.system.as_deref().unwrap_or("").as_bytes());
+    hasher.update(request.temperature.to_bits().to_be_bytes());
+    if let Some(schema) = &request.response_schema {
+        hasher.update(schema.to_string().as_bytes());
+    }
+    hasher.update([request.extended_thinking as u8]);
+    for message in &request.messages {
+        for content in &message.content {
+            match content {
+                MessageContent::Text { text } => hasher.update(text.as_bytes()),
+                MessageContent::Image { media_type, data } => {
+                    hasher.update(media_type.as_bytes());
+                    hasher.update(data);
+                }
+            }
+        }
+    }
+    let digest = hasher.finalize();
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[async_trait]
+impl<C: LlmClient> LlmClient for CachingLlmClient<C> {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+        if request.bypass_cache {
+            return self.inner.complete(request).await;
+        }
+
+        let key = hash_request(&request);
+        {
+            let mut entries = self.entries.lock().unwrap();
+            match entries.get(&key) {
+                Some(entry) if entry.inserted_at.elapsed() < self.ttl => return Ok(entry.response.clone()),
+                Some(_) => {
+                    entries.remove(&key);
+                }
+                None => {}
+            }
+        }
+
+        let response = self.inner.complete(request).await?;
+        self.entries.lock().unwrap().insert(key, CacheEntry { response: response.clone(), inserted_at: Instant::now() });
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, MockLlmClient, Role};
+
+    fn request(text: &str) -> CompletionRequest {
+        CompletionRequest::new("mock", vec![Message::text(Role::User, text)])
+    }
+
+    #[tokio::test]
+    async fn an_identical_prompt_reuses_the_cached_response() {
+        let client = CachingLlmClient::new(MockLlmClient::new(vec!["first".to_string()]), Duration::from_secs(60));
+        let first = client.complete(request("hello")).await.unwrap();
+        let second = client.complete(request("hello")).await.unwrap();
+        assert_eq!(first.text, "first");
+        assert_eq!(second.text, "first");
+    }
+
+    #[tokio::test]
+    async fn bypass_cache_always_calls_through() {
+        let client = CachingLlmClient::new(MockLlmClient::new(vec!["first".to_string(), "second".to_string()]), Duration::from_secs(60));
+        let first = client.complete(request("hello")).await.unwrap();
+        let mut bypassed = request("hello");
+        bypassed.bypass_cache = true;
+        let second = client.complete(bypassed).await.unwrap();
+        assert_eq!(first.text, "first");
+        assert_eq!(second.text, "second");
+    }
+
+    #[tokio::test]
+    async fn an_expired_entry_calls_through_again() {
+        let client = CachingLlmClient::new(MockLlmClient::new(vec!["first".to_string(), "second".to_string()]), Duration::from_millis(1));
+        let first = client.complete(request("hello")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = client.complete(request("hello")).await.unwrap();
+        assert_eq!(first.text, "first");
+        assert_eq!(second.text, "second");
+    }
+
+    #[tokio::test]
+    async fn different_prompts_are_cached_independently() {
+        let client = CachingLlmClient::new(MockLlmClient::new(vec!["first".to_string(), "second".to_string()]), Duration::from_secs(60));
+        let a = client.complete(request("hello")).await.unwrap();
+        let b = client.complete(request("goodbye")).await.unwrap();
+        assert_eq!(a.text, "first");
+        assert_eq!(b.text, "second");
+    }
+}
@@ -0,0 +1,149 @@
+//! Ordered model fallback: when the primary model returns a persistent
+//! error or its daily budget is exhausted, retries the same request
+//! against the next model in the chain (e.g. sonnet -> haiku -> local)
+//! instead of failing the iteration outright. The response always
+//! reports which model actually served it, so
+//! [`CompletionResponse::model`](super::CompletionResponse::model) can be
+//! recorded per iteration for cost/quality analysis.
+
+use super::{CompletionRequest, CompletionResponse, LlmClient};
+use async_trait::async_trait;
+
+/// An ordered list of model names to try after the primary fails, e.g.
+/// `["claude-haiku", "local-llama"]` behind `"claude-sonnet"`.
+#[derive(Debug, Clone, Default)]
+pub struct FallbackChain {
+    pub models: Vec<String>,
+}
+
+impl FallbackChain {
+    pub fn new(models: Vec<String>) -> Self {
+        Self { models }
+    }
+}
+
+/// Wraps a single [`LlmClient`] (the one provider loopr talks to) and
+/// retries a request under each model in `chain` in turn, skipping any
+/// model `is_exhausted` reports as over its daily budget. Stops at the
+/// first model that succeeds.
+pub struct FallbackLlmClient<C: LlmClient> {
+    inner: C,
+    chain: FallbackChain,
+    is_exhausted: Box<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl<C: LlmClient> FallbackLlmClient<C> {
+    pub fn new(inner: C, chain: FallbackChain) -> Self {
+        Self { inner, chain, is_exhausted: Box::new(|_| false) }
+    }
+
+    /// Supplies the per-model budget check consulted before each
+    /// fallback attempt, so a model whose daily cap is already spent is
+    /// skipped without even trying it.
+    pub fn with_budget_check(mut self, is_exhausted: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.is_exhausted = Box::new(is_exhausted);
+        self
+    }
+}
+
+#[async_trait]
+impl<C: LlmClient> LlmClient for FallbackLlmClient<C> {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+        let mut models = vec![request.model.clone()];
+        models.extend(self.chain.models.iter().cloned());
+
+        let mut last_err = None;
+        for model in models {
+            if (self.is_exhausted)(&model) {
+                continue;
+            }
+            let mut attempt = request.clone();
+            attempt.model = model;
+            match self.inner.complete(attempt).await {
+                Ok(response) => return Ok(response),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("every model in the fallback chain is over its daily budget")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, Role};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    fn request() -> CompletionRequest {
+        CompletionRequest::new("claude-sonnet", vec![Message::text(Role::User, "hi")])
+    }
+
+    /// A client that errors for a configured set of models and otherwise
+    /// echoes back which model it was called with.
+    struct FlakyClient {
+        fails_for: Vec<String>,
+    }
+
+    #[async_trait]
+    impl LlmClient for FlakyClient {
+        async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+            if self.fails_for.contains(&request.model) {
+                anyhow::bail!("{} is down", request.model);
+            }
+            Ok(CompletionResponse { text: "ok".to_string(), input_tokens: 1, output_tokens: 1, model: request.model })
+        }
+    }
+
+    #[tokio::test]
+    async fn succeeds_on_the_primary_model_when_it_works() {
+        let client = FallbackLlmClient::new(FlakyClient { fails_for: vec![] }, FallbackChain::new(vec!["claude-haiku".to_string()]));
+        let response = client.complete(request()).await.unwrap();
+        assert_eq!(response.model, "claude-sonnet");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_next_model_after_a_persistent_error() {
+        let client = FallbackLlmClient::new(
+            FlakyClient { fails_for: vec!["claude-sonnet".to_string()] },
+            FallbackChain::new(vec!["claude-haiku".to_string(), "local-llama".to_string()]),
+        );
+        let response = client.complete(request()).await.unwrap();
+        assert_eq!(response.model, "claude-haiku");
+    }
+
+    #[tokio::test]
+    async fn skips_a_model_whose_daily_budget_is_exhausted() {
+        let client = FallbackLlmClient::new(FlakyClient { fails_for: vec![] }, FallbackChain::new(vec!["claude-haiku".to_string()]))
+            .with_budget_check(|model| model == "claude-sonnet");
+        let response = client.complete(request()).await.unwrap();
+        assert_eq!(response.model, "claude-haiku");
+    }
+
+    #[tokio::test]
+    async fn fails_once_every_model_in_the_chain_is_exhausted_or_erroring() {
+        let client = FallbackLlmClient::new(
+            FlakyClient { fails_for: vec!["claude-sonnet".to_string(), "claude-haiku".to_string()] },
+            FallbackChain::new(vec!["claude-haiku".to_string()]),
+        );
+        assert!(client.complete(request()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tried_models_are_reported_in_order() {
+        let attempted = Mutex::new(Vec::new());
+        struct RecordingClient<'a> {
+            attempted: &'a Mutex<Vec<String>>,
+        }
+        #[async_trait]
+        impl<'a> LlmClient for RecordingClient<'a> {
+            async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+                self.attempted.lock().unwrap().push(request.model.clone());
+                anyhow::bail!("always fails")
+            }
+        }
+        let client = FallbackLlmClient::new(RecordingClient { attempted: &attempted }, FallbackChain::new(vec!["claude-haiku".to_string(), "local-llama".to_string()]));
+        let _ = client.complete(request()).await;
+        assert_eq!(*attempted.lock().unwrap(), vec!["claude-sonnet", "claude-haiku", "local-llama"]);
+    }
+}
@@ -0,0 +1,51 @@
+use super::{CompletionRequest, CompletionResponse, LlmClient};
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// A deterministic LLM stand-in for tests: returns a fixed queue of
+/// responses in order, regardless of the request contents.
+pub struct MockLlmClient {
+    responses: Mutex<Vec<String>>,
+}
+
+impl MockLlmClient {
+    pub fn new(responses: Vec<String>) -> Self {
+        Self {
+            responses: Mutex::new(responses),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for MockLlmClient {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+        let mut responses = self.responses.lock().unwrap();
+        let text = if responses.is_empty() {
+            String::new()
+        } else {
+            responses.remove(0)
+        };
+        Ok(CompletionResponse {
+            input_tokens: 0,
+            output_tokens: (text.len() / 4) as u32,
+            text,
+            model: request.model,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, Role};
+
+    #[tokio::test]
+    async fn returns_queued_responses_in_order() {
+        let client = MockLlmClient::new(vec!["first".into(), "second".into()]);
+        let request = CompletionRequest::new("mock", vec![Message::text(Role::User, "hi")]);
+        let first = client.complete(request.clone()).await.unwrap();
+        let second = client.complete(request).await.unwrap();
+        assert_eq!(first.text, "first");
+        assert_eq!(second.text, "second");
+    }
+}
@@ -0,0 +1,126 @@
+//! Provider-agnostic LLM request/response types and the [`LlmClient`] trait.
+
+mod cache;
+mod fallback;
+mod mock;
+pub mod schema;
+mod script;
+
+pub use cache::CachingLlmClient;
+pub use fallback::{FallbackChain, FallbackLlmClient};
+pub use mock::MockLlmClient;
+pub use script::{ScriptedLlmClient, ScriptedTurn};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum MessageContent {
+    Text { text: String },
+    /// A screenshot or diagram attached to the message, e.g. pasted into
+    /// chat by a user or read by the `read_image` tool.
+    Image { media_type: String, data: Vec<u8> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub role: Role,
+    pub content: Vec<MessageContent>,
+}
+
+impl Message {
+    pub fn text(role: Role, text: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: vec![MessageContent::Text { text: text.into() }],
+        }
+    }
+
+    pub fn image(role: Role, media_type: impl Into<String>, data: Vec<u8>) -> Self {
+        Self {
+            role,
+            content: vec![MessageContent::Image { media_type: media_type.into(), data }],
+        }
+    }
+}
+
+/// A request to complete a conversation, provider-agnostic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionRequest {
+    pub model: String,
+    pub system: Option<String>,
+    pub messages: Vec<Message>,
+    pub temperature: f32,
+    pub max_tokens: u32,
+    /// Skips [`CachingLlmClient`] and forces a fresh completion, for
+    /// callers that need a non-deterministic or up-to-date response.
+    #[serde(default)]
+    pub bypass_cache: bool,
+    /// A JSON schema the response text must conform to. How a client
+    /// enforces it is provider-specific (tool-forcing on Anthropic,
+    /// `response_format` on OpenAI); this layer just carries the schema
+    /// through. Used for artifact generation so a plan/spec/phase can
+    /// come back as validated structured data; see [`crate::llm::schema::validate`].
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,
+    /// Requests the model's extended-thinking mode, a [`crate::escalation`]
+    /// step tried on an iteration that keeps getting the same feedback
+    /// before giving up and splitting the phase.
+    #[serde(default)]
+    pub extended_thinking: bool,
+}
+
+impl CompletionRequest {
+    pub fn new(model: impl Into<String>, messages: Vec<Message>) -> Self {
+        Self {
+            model: model.into(),
+            system: None,
+            messages,
+            temperature: 0.0,
+            max_tokens: 4096,
+            bypass_cache: false,
+            response_schema: None,
+            extended_thinking: false,
+        }
+    }
+
+    /// Constrains the response to `schema`, for structured artifact
+    /// generation.
+    pub fn with_response_schema(mut self, schema: serde_json::Value) -> Self {
+        self.response_schema = Some(schema);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionResponse {
+    pub text: String,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    /// The model that actually served this completion, which may differ
+    /// from the request's model after a [`FallbackLlmClient`] chain moves
+    /// past the primary. Recorded per iteration so cost and quality can
+    /// be analyzed per model.
+    pub model: String,
+}
+
+/// A chunk of a streamed completion response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamChunk {
+    pub text: String,
+    pub done: bool,
+}
+
+#[async_trait]
+pub trait LlmClient: Send + Sync {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse>;
+}
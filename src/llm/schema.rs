@@ -0,0 +1,60 @@
+//! A minimal, dependency-free check that a completion's text conforms to
+//! a [`CompletionRequest::response_schema`]. Providers do the real
+//! constraining (tool-forcing, `response_format`); this is a defensive
+//! check against a provider that ignores or only loosely honors it.
+//!
+//! Only `type: "object"` with a top-level `required` array is checked —
+//! enough for the artifact schemas in [`crate::artifact`] without pulling
+//! in a full JSON Schema validator.
+
+use serde_json::Value;
+
+/// Parses `text` as JSON and checks that every field named in `schema`'s
+/// `required` array is present, returning the parsed value.
+pub fn validate(schema: &Value, text: &str) -> anyhow::Result<Value> {
+    let candidate: Value = serde_json::from_str(text)?;
+    let Some(required) = schema.get("required").and_then(Value::as_array) else {
+        return Ok(candidate);
+    };
+    let Some(object) = candidate.as_object() else {
+        anyhow::bail!("response is not a JSON object");
+    };
+    for field in required {
+        let Some(name) = field.as_str() else { continue };
+        if !object.contains_key(name) {
+            anyhow::bail!("response is missing required field {name:?}");
+        }
+    }
+    Ok(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({ "type": "object", "required": ["title"] })
+    }
+
+    #[test]
+    fn accepts_a_response_with_every_required_field() {
+        assert!(validate(&schema(), r#"{"title": "add login"}"#).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_response_missing_a_required_field() {
+        let error = validate(&schema(), r#"{"specs": []}"#).unwrap_err();
+        assert!(error.to_string().contains("title"));
+    }
+
+    #[test]
+    fn rejects_text_that_is_not_valid_json() {
+        assert!(validate(&schema(), "not json").is_err());
+    }
+
+    #[test]
+    fn a_schema_without_a_required_array_accepts_anything() {
+        assert!(validate(&json!({ "type": "object" }), r#"{"anything": true}"#).is_ok());
+    }
+}
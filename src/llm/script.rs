@@ -0,0 +1,175 @@
+//! A scriptable [`LlmClient`] that replays a fixed, YAML-defined
+//! conversation instead of a flat response queue. Where [`MockLlmClient`]
+//! just hands back the next response regardless of what was asked,
+//! [`ScriptedLlmClient`] matches each turn against the request, so a
+//! fixture can encode an actual multi-turn tool-use conversation (plan
+//! asks a question, spec answers it, phase reacts to the answer) and
+//! drive it entirely offline.
+//!
+//! [`MockLlmClient`]: super::MockLlmClient
+
+use super::{CompletionRequest, CompletionResponse, LlmClient, MessageContent};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Mutex;
+
+/// One scripted turn. `matches` is a substring checked against the most
+/// recent user message; the first unconsumed turn whose `matches` is
+/// absent or present in that text is returned and removed from the
+/// script. Turns without a `matches` act as a catch-all.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScriptedTurn {
+    #[serde(default)]
+    pub matches: Option<String>,
+    pub response: String,
+}
+
+/// Replays [`ScriptedTurn`]s in YAML-fixture order, consuming each turn
+/// it matches so the same fixture can be driven end to end by a test.
+pub struct ScriptedLlmClient {
+    turns: Mutex<Vec<ScriptedTurn>>,
+}
+
+impl ScriptedLlmClient {
+    pub fn new(turns: Vec<ScriptedTurn>) -> Self {
+        Self { turns: Mutex::new(turns) }
+    }
+
+    /// Parses a fixture of the form:
+    ///
+    /// ```yaml
+    /// - matches: "break this into phases"
+    ///   response: "phase 1: ..."
+    /// - response: "looks good"
+    /// ```
+    pub fn from_yaml(content: &str) -> anyhow::Result<Self> {
+        let turns: Vec<ScriptedTurn> = serde_yaml::from_str(content)?;
+        Ok(Self::new(turns))
+    }
+}
+
+#[async_trait]
+impl LlmClient for ScriptedLlmClient {
+    async fn complete(&self, request: CompletionRequest) -> anyhow::Result<CompletionResponse> {
+        let last_user_text = last_user_text(&request);
+        let mut turns = self.turns.lock().unwrap();
+        let index = turns
+            .iter()
+            .position(|turn| match &turn.matches {
+                Some(pattern) => last_user_text.contains(pattern.as_str()),
+                None => true,
+            })
+            .ok_or_else(|| anyhow::anyhow!("scripted conversation has no turn matching the request"))?;
+        let turn = turns.remove(index);
+        Ok(CompletionResponse {
+            output_tokens: (turn.response.len() / 4) as u32,
+            input_tokens: 0,
+            text: turn.response,
+            model: request.model,
+        })
+    }
+}
+
+fn last_user_text(request: &CompletionRequest) -> String {
+    request
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == super::Role::User)
+        .map(|message| {
+            message
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    MessageContent::Text { text } => Some(text.as_str()),
+                    MessageContent::Image { .. } => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, Role};
+
+    fn request(text: &str) -> CompletionRequest {
+        CompletionRequest::new("mock", vec![Message::text(Role::User, text)])
+    }
+
+    #[tokio::test]
+    async fn matches_turns_by_substring_regardless_of_order() {
+        let client = ScriptedLlmClient::new(vec![
+            ScriptedTurn { matches: Some("phases".to_string()), response: "phase plan".to_string() },
+            ScriptedTurn { matches: Some("spec".to_string()), response: "spec draft".to_string() },
+        ]);
+        let spec_reply = client.complete(request("write the spec")).await.unwrap();
+        let phase_reply = client.complete(request("break this into phases")).await.unwrap();
+        assert_eq!(spec_reply.text, "spec draft");
+        assert_eq!(phase_reply.text, "phase plan");
+    }
+
+    #[tokio::test]
+    async fn a_turn_without_a_matcher_is_a_catch_all() {
+        let client = ScriptedLlmClient::new(vec![ScriptedTurn { matches: None, response: "fine".to_string() }]);
+        let reply = client.complete(request("anything at all")).await.unwrap();
+        assert_eq!(reply.text, "fine");
+    }
+
+    #[tokio::test]
+    async fn errors_when_no_turn_matches() {
+        let client = ScriptedLlmClient::new(vec![ScriptedTurn { matches: Some("spec".to_string()), response: "spec draft".to_string() }]);
+        assert!(client.complete(request("unrelated question")).await.is_err());
+    }
+
+    #[test]
+    fn parses_a_yaml_fixture() {
+        let yaml = "- matches: phases\n  response: phase plan\n- response: fine\n";
+        let client = ScriptedLlmClient::from_yaml(yaml).unwrap();
+        assert_eq!(client.turns.lock().unwrap().len(), 2);
+    }
+
+    /// Drives a full Plan -> Spec -> Phase -> Ralph hierarchy from one
+    /// scripted fixture, entirely offline: each loop's LLM call consumes
+    /// the next matching turn, and its response seeds the description of
+    /// the loop it spawns.
+    #[tokio::test]
+    async fn scripted_conversation_drives_the_plan_spec_phase_ralph_hierarchy() {
+        use crate::domain::{LoopRecord, LoopType};
+        use crate::storage::{InMemoryStorage, Storage};
+
+        let yaml = r#"
+- matches: "break this feature into specs"
+  response: "spec 1: add the login endpoint"
+- matches: "break this spec into phases"
+  response: "phase 1: write the handler"
+- matches: "break this phase into ralph iterations"
+  response: "ralph 1: implement and test the handler"
+"#;
+        let client = ScriptedLlmClient::from_yaml(yaml).unwrap();
+        let storage = InMemoryStorage::new();
+
+        let plan = LoopRecord::new(LoopType::Plan, None, "add login");
+        storage.save_loop(plan.clone()).unwrap();
+
+        let spec_reply = client.complete(request("break this feature into specs")).await.unwrap();
+        let spec = LoopRecord::new(LoopType::Spec, Some(plan.id), spec_reply.text);
+        storage.save_loop(spec.clone()).unwrap();
+
+        let phase_reply = client.complete(request("break this spec into phases")).await.unwrap();
+        let phase = LoopRecord::new(LoopType::Phase, Some(spec.id), phase_reply.text);
+        storage.save_loop(phase.clone()).unwrap();
+
+        let ralph_reply = client.complete(request("break this phase into ralph iterations")).await.unwrap();
+        let ralph = LoopRecord::new(LoopType::Ralph, Some(phase.id), ralph_reply.text);
+        storage.save_loop(ralph.clone()).unwrap();
+
+        let loops = storage.list_loops().unwrap();
+        assert_eq!(loops.len(), 4);
+        assert_eq!(spec.description, "spec 1: add the login endpoint");
+        assert_eq!(phase.parent_id, Some(spec.id));
+        assert_eq!(ralph.parent_id, Some(phase.id));
+    }
+}
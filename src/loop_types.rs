@@ -0,0 +1,191 @@
+//! Project-defined loop types declared in YAML, so teams can add loop
+//! kinds (e.g. "Research", "Docs") beyond the built-in Plan/Spec/Phase/Ralph
+//! without forking [`crate::domain::LoopType`].
+
+use serde::{Deserialize, Serialize};
+
+/// A custom loop type's declaration: what artifact shape it produces,
+/// what it spawns, and which validation gates and prompts it uses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoopTypeDefinition {
+    pub name: String,
+    /// Name of the [`crate::artifact`] schema this type's artifacts parse
+    /// against ("plan", "freeform", or a project-defined schema name).
+    #[serde(default = "default_artifact_schema")]
+    pub artifact_schema: String,
+    /// Loop type name(s) this type is allowed to spawn children as.
+    #[serde(default)]
+    pub spawns: Vec<String>,
+    /// Shell commands that must pass for this loop type's work to be
+    /// accepted.
+    #[serde(default)]
+    pub validation_commands: Vec<String>,
+    /// System prompt fragment specific to this loop type, appended after
+    /// the shared preamble in `build_system_prompt`.
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Token budget for this loop type's self-review pass (see
+    /// [`crate::self_review`]); `None` disables self-review for it.
+    #[serde(default)]
+    pub self_review_token_budget: Option<usize>,
+    /// Extra denied command patterns on top of the default denylist (see
+    /// [`crate::tools::CommandPolicy::with_extra_denied`]), for loop types
+    /// that need tighter command restrictions than the project default.
+    #[serde(default)]
+    pub extra_denied_commands: Vec<String>,
+}
+
+fn default_artifact_schema() -> String {
+    "freeform".to_string()
+}
+
+/// A project's registry of custom loop types, loaded from `loopr.yml`'s
+/// `loop_types:` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoopTypeRegistry {
+    #[serde(default)]
+    pub types: Vec<LoopTypeDefinition>,
+}
+
+impl LoopTypeRegistry {
+    pub fn get(&self, name: &str) -> Option<&LoopTypeDefinition> {
+        self.types.iter().find(|t| t.name == name)
+    }
+}
+
+/// Keys renamed in loop-type YAML since an earlier release, `old -> new`,
+/// kept separate from the project config's own rename table since the
+/// two schemas evolve independently.
+const DEPRECATED_KEYS: &[(&str, &str)] = &[];
+
+/// Parses and strictly validates a loop-type YAML document (the contents
+/// of `loopr.yml`'s `loop_types:` section), catching unknown keys and
+/// wrong types with file/line diagnostics via [`crate::config::validate`].
+pub fn validate_loop_types(content: &str) -> Result<(LoopTypeRegistry, Vec<crate::config::Diagnostic>), crate::config::Diagnostic> {
+    crate::config::validate(content, DEPRECATED_KEYS)
+}
+
+/// The built-in `Docs` loop type: generates and validates documentation,
+/// spawnable automatically after a plan tree completes (when enabled in
+/// config).
+pub fn builtin_docs() -> LoopTypeDefinition {
+    LoopTypeDefinition {
+        name: "docs".to_string(),
+        artifact_schema: "freeform".to_string(),
+        spawns: Vec::new(),
+        validation_commands: crate::validation::docs_pipeline().into_iter().map(|g| g.command).collect(),
+        prompt: Some(
+            "Write or update documentation for the code changed in this plan. Keep doc comments consistent with \
+             the surrounding module's register and length."
+                .to_string(),
+        ),
+        self_review_token_budget: Some(1500),
+        extra_denied_commands: Vec::new(),
+    }
+}
+
+/// The built-in `Perf` loop type: addresses benchmark regressions flagged
+/// by [`crate::validation::BenchmarkGate`], spawned automatically when a
+/// plan tree's benchmark gate fails (when enabled in config) with the
+/// regression deltas carried into its prompt.
+pub fn builtin_perf() -> LoopTypeDefinition {
+    LoopTypeDefinition {
+        name: "perf".to_string(),
+        artifact_schema: "freeform".to_string(),
+        spawns: Vec::new(),
+        validation_commands: Vec::new(),
+        prompt: Some(
+            "Address the benchmark regressions listed below without changing observable behavior. Re-run the \
+             benchmark after each change to confirm the regression is resolved."
+                .to_string(),
+        ),
+        self_review_token_budget: Some(1500),
+        extra_denied_commands: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_custom_loop_type_from_yaml() {
+        let yaml = "\
+types:
+  - name: docs
+    artifact_schema: freeform
+    spawns: []
+    validation_commands:
+      - cargo doc --no-deps
+";
+        let registry: LoopTypeRegistry = serde_yaml::from_str(yaml).unwrap();
+        let docs = registry.get("docs").expect("docs type present");
+        assert_eq!(docs.validation_commands, vec!["cargo doc --no-deps".to_string()]);
+    }
+
+    #[test]
+    fn unknown_type_name_returns_none() {
+        let registry = LoopTypeRegistry::default();
+        assert!(registry.get("research").is_none());
+    }
+
+    #[test]
+    fn builtin_docs_type_carries_the_docs_pipeline() {
+        let docs = builtin_docs();
+        assert!(!docs.validation_commands.is_empty());
+    }
+
+    #[test]
+    fn builtin_perf_type_has_a_regression_prompt() {
+        let perf = builtin_perf();
+        assert!(perf.prompt.unwrap().contains("benchmark"));
+    }
+
+    #[test]
+    fn builtin_types_enable_self_review_with_a_budget() {
+        assert_eq!(builtin_docs().self_review_token_budget, Some(1500));
+        assert_eq!(builtin_perf().self_review_token_budget, Some(1500));
+    }
+
+    #[test]
+    fn validate_loop_types_rejects_an_unknown_key() {
+        let yaml = "\
+types:
+  - name: research
+    typo_field: oops
+";
+        let err = validate_loop_types(yaml).unwrap_err();
+        match err {
+            crate::config::Diagnostic::Invalid { detail } => assert!(detail.contains("typo_field")),
+            other => panic!("expected Invalid, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_loop_types_accepts_a_well_formed_registry() {
+        let yaml = "\
+types:
+  - name: docs
+    validation_commands:
+      - cargo doc --no-deps
+";
+        let (registry, warnings) = validate_loop_types(yaml).unwrap();
+        assert!(registry.get("docs").is_some());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn custom_type_can_declare_extra_denied_commands() {
+        let yaml = "\
+types:
+  - name: research
+    extra_denied_commands:
+      - git push
+";
+        let registry: LoopTypeRegistry = serde_yaml::from_str(yaml).unwrap();
+        let research = registry.get("research").expect("research type present");
+        assert_eq!(research.extra_denied_commands, vec!["git push".to_string()]);
+    }
+}
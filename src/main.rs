@@ -0,0 +1,481 @@
+use clap::Parser;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// loopr: autonomous iterate-and-validate loop orchestrator.
+#[derive(Parser, Debug)]
+#[command(name = "loopr", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Print the daemon status (stub).
+    Status,
+    /// Create a plan loop from a description (stub).
+    Plan {
+        description: String,
+        /// Preview the would-be tree, artifacts, and cost against a shadow
+        /// copy of the worktree instead of creating a real plan.
+        #[arg(long)]
+        simulate: bool,
+        /// Links the plan to an issue-tracker ticket (e.g. `PROJ-123`),
+        /// fetching its context into the planning prompt and posting
+        /// progress comments as the plan tree progresses.
+        #[arg(long)]
+        ticket: Option<String>,
+        /// Restricts this plan's descendant loops' tool access, worktree
+        /// checkout, and validation to this subtree of a monorepo.
+        #[arg(long)]
+        scope: Option<String>,
+    },
+    /// Inspect or edit this project's remembered lessons.
+    Memory {
+        #[command(subcommand)]
+        action: MemoryAction,
+    },
+    /// First-run setup: checks for an API key and config, scaffolding
+    /// `~/.config/loopr/loopr.yml` if it's missing.
+    Init {
+        /// Model to default new config to.
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// List loops, optionally filtered by label (stub).
+    List {
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// Set or clear a loop's scheduling priority override (stub).
+    Priority {
+        id: String,
+        /// New priority value; omit to clear the override and return to
+        /// the automatic priority.
+        value: Option<i32>,
+    },
+    /// Delete a loop and its storage-layer data (stub).
+    Delete {
+        id: String,
+        /// Also delete descendant loops (specs/phases/ralphs) instead of
+        /// refusing when the loop has children.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Manually create a Spec loop from a hand-written artifact (stub).
+    Spec {
+        #[command(subcommand)]
+        action: ManualCreateAction,
+    },
+    /// Manually create a Phase loop from a hand-written artifact (stub).
+    Phase {
+        #[command(subcommand)]
+        action: ManualCreateAction,
+    },
+    /// Manually create a Ralph loop from a hand-written artifact (stub).
+    Ralph {
+        #[command(subcommand)]
+        action: ManualCreateAction,
+    },
+    /// Re-parse a loop's stored artifact and spawn any children the
+    /// parser missed the first time (stub).
+    Respawn { id: String },
+    /// Daemon-wide scheduler controls (stub).
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
+    },
+    /// Spending-cap controls (stub).
+    Budget {
+        #[command(subcommand)]
+        action: BudgetAction,
+    },
+    /// Show (generating if needed) the post-mortem for a plan that ended
+    /// in failure (stub).
+    Postmortem { id: String },
+    /// Show (generating if needed) the changelog for a plan that
+    /// completed and merged (stub).
+    Changelog { id: String },
+    /// Render a loop's hierarchy as a Mermaid or Graphviz diagram (stub).
+    Tree {
+        id: String,
+        #[arg(long, value_enum, default_value = "mermaid")]
+        format: TreeFormatArg,
+    },
+    /// Run against the daemon and, on completion, write a machine-readable
+    /// manifest of everything the run did for CI to archive (stub).
+    Run {
+        #[arg(long)]
+        manifest: Option<std::path::PathBuf>,
+    },
+    /// Opens a loop's worktree, artifact, or a failing `file:line` in the
+    /// configured editor (stub).
+    Open {
+        id: String,
+        /// A `file:line` to jump to, e.g. from a gate's failure output.
+        #[arg(long)]
+        at: Option<String>,
+        /// Overrides `$VISUAL`/`$EDITOR` detection.
+        #[arg(long)]
+        editor: Option<String>,
+    },
+    /// Pins a worktree-relative file so its contents are always included
+    /// in a loop's prompt (within the context window budget) (stub).
+    Pin { id: String, path: PathBuf },
+    /// Edits a loop's description after creation, e.g. to fix a typo or
+    /// clarify scope (stub).
+    Update { id: String, description: String },
+    /// Duplicates a loop into a fresh, unstarted attempt, for retrying
+    /// failed or abandoned work with a tweaked task or model instead of
+    /// rebuilding the hierarchy by hand (stub).
+    Clone {
+        id: String,
+        #[arg(long)]
+        task: Option<String>,
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Exports a loop's accumulated worktree changes as a unified diff,
+    /// for applying an agent's work by hand instead of through the merge
+    /// queue (stub).
+    Diff {
+        id: String,
+        /// Writes the patch to this path instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Prints a `git diff --stat`-style summary instead of the full diff.
+        #[arg(long)]
+        stat: bool,
+    },
+    /// Imports an existing local branch of in-progress human work as a
+    /// Ralph loop, so loopr can take over finishing or fixing it (stub).
+    Adopt {
+        branch: String,
+        #[arg(long)]
+        goal: String,
+        #[arg(long)]
+        validate: String,
+        #[arg(long)]
+        parent: Option<String>,
+    },
+    /// Tags the current worktree state as a named checkpoint an operator
+    /// or later iteration can roll back to (stub).
+    Checkpoint { id: String, name: String },
+    /// Rolls a loop's worktree back to an earlier named checkpoint (stub).
+    Rollback { id: String, name: String },
+    /// Clears a loop out of the approval queue as accepted (stub).
+    Approve { id: String },
+    /// Clears a loop out of the approval queue with feedback for its next
+    /// iteration (stub).
+    Reject { id: String, feedback: String },
+    /// Sends a loop back for another iteration without specific written
+    /// feedback (stub).
+    Iterate { id: String },
+    /// Defers an approval-queue entry without changing the loop's status
+    /// (stub).
+    Skip { id: String },
+    /// Prints a loop's accumulated feedback exactly as it will appear in
+    /// the next iteration's prompt, for debugging repeated mistakes (stub).
+    Feedback {
+        id: String,
+        /// Show only this iteration's raw feedback instead of the full
+        /// (possibly summarized) history that would be sent next.
+        #[arg(long)]
+        iteration: Option<u32>,
+        /// Open the feedback in the configured editor to trim or correct it
+        /// before the loop resumes.
+        #[arg(long)]
+        edit: bool,
+    },
+    /// Export aggregated token/cost usage for a time period, for finance
+    /// chargeback (stub).
+    Usage {
+        /// Inclusive start date, e.g. 2026-08-01.
+        #[arg(long)]
+        from: String,
+        /// Inclusive end date, e.g. 2026-08-31.
+        #[arg(long)]
+        to: String,
+        #[arg(long, value_enum, default_value = "loop-type")]
+        group_by: UsageGroupBy,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: UsageFormat,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum UsageGroupBy {
+    LoopType,
+    Model,
+    Project,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum UsageFormat {
+    Csv,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum TreeFormatArg {
+    Mermaid,
+    Dot,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum BudgetAction {
+    /// Clear a tripped daily/per-plan spending cap and let throttled
+    /// loops resume.
+    Override,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum DaemonAction {
+    /// Show whether the scheduler is running or in maintenance mode.
+    Status,
+    /// Stop the scheduler from starting new iterations; iterations
+    /// already running finish normally.
+    Pause,
+    /// Resume the scheduler starting new iterations.
+    Resume,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum ManualCreateAction {
+    /// Inject a hand-written artifact as this loop and spawn its children
+    /// from it normally.
+    Create {
+        /// The hierarchy parent this loop is created under.
+        #[arg(long)]
+        parent: String,
+        /// Path to the hand-written artifact.
+        #[arg(long)]
+        file: PathBuf,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MemoryAction {
+    /// List every remembered lesson for the current project.
+    List,
+    /// Forget a remembered lesson by id.
+    Forget { id: Uuid },
+}
+
+/// Parses a loop id argument, accepting either a full id or a short-id
+/// prefix (see [`loopr::id`]). With no daemon connection to fetch a
+/// candidate list from yet, prefix resolution always reports no match;
+/// once a daemon is reachable, callers will pass its known loop ids here.
+fn parse_loop_id(raw: &str) -> anyhow::Result<Uuid> {
+    loopr::id::parse_full_or_prefix(raw, &[]).map_err(|error| anyhow::anyhow!("{raw:?}: {error} (no daemon connection configured yet to resolve short ids against)"))
+}
+
+/// Where the current project's memory file lives, keyed by the current
+/// directory's name under `~/.loopr/memory`.
+fn memory_file_path() -> anyhow::Result<std::path::PathBuf> {
+    let home = std::env::var("HOME").map_err(|_| anyhow::anyhow!("HOME is not set"))?;
+    let project = std::env::current_dir()?
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "default".to_string());
+    Ok(loopr::memory::memory_path(std::path::Path::new(&home), &project))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Some(Command::Status) | None => {
+            println!("loopr: no daemon connection configured yet");
+        }
+        Some(Command::Plan { description, simulate, ticket, scope }) => {
+            let ticket_suffix = ticket.map(|ticket| format!(", linked to {ticket}")).unwrap_or_default();
+            let scope_suffix = scope.map(|scope| format!(", scoped to {scope}")).unwrap_or_default();
+            if simulate {
+                println!("loopr: simulating plan {description:?}{ticket_suffix}{scope_suffix} (no daemon connection configured yet)");
+            } else {
+                println!("loopr: no daemon connection configured yet{ticket_suffix}{scope_suffix}");
+            }
+        }
+        Some(Command::Memory { action }) => {
+            let path = memory_file_path()?;
+            match action {
+                MemoryAction::List => {
+                    let memory = loopr::memory::load_memory(&path)?;
+                    if memory.entries.is_empty() {
+                        println!("loopr: no remembered lessons for this project");
+                    } else {
+                        for entry in &memory.entries {
+                            println!("{}  {}", entry.id, entry.lesson);
+                        }
+                    }
+                }
+                MemoryAction::Forget { id } => {
+                    let mut memory = loopr::memory::load_memory(&path)?;
+                    if memory.forget(id) {
+                        loopr::memory::save_memory(&path, &memory)?;
+                        println!("loopr: forgot lesson {id}");
+                    } else {
+                        println!("loopr: no lesson with id {id}");
+                    }
+                }
+            }
+        }
+        Some(Command::Init { model }) => {
+            let home = std::env::var("HOME").map_err(|_| anyhow::anyhow!("HOME is not set"))?;
+            let home = std::path::Path::new(&home);
+            let api_key_env = std::env::var("ANTHROPIC_API_KEY").ok();
+            let status = loopr::onboarding::check_status(home, api_key_env.as_deref());
+
+            if !status.api_key_present {
+                println!("loopr: ANTHROPIC_API_KEY is not set — loopr will not be able to reach an LLM until it is");
+            }
+
+            let path = loopr::onboarding::config_path(home);
+            if loopr::onboarding::scaffold_config(&path, model)? {
+                println!("loopr: wrote a default config to {}", path.display());
+            } else {
+                println!("loopr: config already exists at {}", path.display());
+            }
+
+            println!("loopr: daemon service installation is not yet implemented — run `loopr daemon status` once it is");
+        }
+        Some(Command::List { label }) => match label {
+            Some(label) => println!("loopr: no daemon connection configured yet (would list loops labeled {label:?})"),
+            None => println!("loopr: no daemon connection configured yet"),
+        },
+        Some(Command::Priority { id, value }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            match value {
+                Some(value) => println!("loopr: no daemon connection configured yet (would set priority of {id} to {value})"),
+                None => println!("loopr: no daemon connection configured yet (would clear the priority override on {id})"),
+            }
+        }
+        Some(Command::Delete { id, force }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            if force {
+                println!("loopr: no daemon connection configured yet (would delete {id} and its descendants)");
+            } else {
+                println!("loopr: no daemon connection configured yet (would delete {id})");
+            }
+        }
+        Some(Command::Spec { action: ManualCreateAction::Create { parent, file } }) => {
+            let parent = loopr::id::short_id(parse_loop_id(&parent)?);
+            println!("loopr: no daemon connection configured yet (would create a spec from {file:?} under parent {parent})");
+        }
+        Some(Command::Phase { action: ManualCreateAction::Create { parent, file } }) => {
+            let parent = loopr::id::short_id(parse_loop_id(&parent)?);
+            println!("loopr: no daemon connection configured yet (would create a phase from {file:?} under parent {parent})");
+        }
+        Some(Command::Ralph { action: ManualCreateAction::Create { parent, file } }) => {
+            let parent = loopr::id::short_id(parse_loop_id(&parent)?);
+            println!("loopr: no daemon connection configured yet (would create a ralph from {file:?} under parent {parent})");
+        }
+        Some(Command::Respawn { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would re-parse {id}'s artifact and spawn any missed children)");
+        }
+        Some(Command::Daemon { action }) => match action {
+            DaemonAction::Status => println!("loopr: no daemon connection configured yet (scheduler state unknown)"),
+            DaemonAction::Pause => println!("loopr: no daemon connection configured yet (would pause the scheduler; in-flight iterations would finish)"),
+            DaemonAction::Resume => println!("loopr: no daemon connection configured yet (would resume the scheduler)"),
+        },
+        Some(Command::Budget { action: BudgetAction::Override }) => {
+            println!("loopr: no daemon connection configured yet (would clear the spending-cap throttle)");
+        }
+        Some(Command::Postmortem { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would show the post-mortem for {id})");
+        }
+        Some(Command::Changelog { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would show the changelog for {id})");
+        }
+        Some(Command::Tree { id, format }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would render the {format:?} tree for {id})");
+        }
+        Some(Command::Open { id, at, editor }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            let target = at.unwrap_or_default();
+            let editor_name = editor.unwrap_or_else(|| "the configured editor".to_string());
+            if target.is_empty() {
+                println!("loopr: no daemon connection configured yet (would open {id}'s worktree in {editor_name})");
+            } else {
+                println!("loopr: no daemon connection configured yet (would open {target} in {editor_name})");
+            }
+        }
+        Some(Command::Pin { id, path }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would pin {} to {id}'s prompt)", path.display());
+        }
+        Some(Command::Update { id, description }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would set {id}'s description to {description:?})");
+        }
+        Some(Command::Clone { id, task, model }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            let task_suffix = task.map(|task| format!(" with task {task:?}")).unwrap_or_default();
+            let model_suffix = model.map(|model| format!(" using model {model:?}")).unwrap_or_default();
+            println!("loopr: no daemon connection configured yet (would clone {id} into a fresh pending loop{task_suffix}{model_suffix})");
+        }
+        Some(Command::Checkpoint { id, name }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would tag {id}'s current worktree state as checkpoint {name:?})");
+        }
+        Some(Command::Rollback { id, name }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would roll {id} back to checkpoint {name:?})");
+        }
+        Some(Command::Approve { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would approve {id})");
+        }
+        Some(Command::Reject { id, feedback }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would reject {id} with feedback {feedback:?})");
+        }
+        Some(Command::Iterate { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would send {id} back for another iteration)");
+        }
+        Some(Command::Skip { id }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            println!("loopr: no daemon connection configured yet (would skip {id} for now)");
+        }
+        Some(Command::Diff { id, output, stat }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            let mode = if stat { "stat summary" } else { "full unified diff" };
+            match output {
+                Some(path) => println!("loopr: no daemon connection configured yet (would write {id}'s {mode} to {})", path.display()),
+                None => println!("loopr: no daemon connection configured yet (would print {id}'s {mode})"),
+            }
+        }
+        Some(Command::Adopt { branch, goal, validate, parent }) => {
+            let parent_suffix = match parent {
+                Some(parent) => format!(" under parent {}", loopr::id::short_id(parse_loop_id(&parent)?)),
+                None => String::new(),
+            };
+            println!(
+                "loopr: no daemon connection configured yet (would adopt branch {branch:?} as a ralph loop{parent_suffix} with goal {goal:?}, validated by `{validate}`)"
+            );
+        }
+        Some(Command::Feedback { id, iteration, edit }) => {
+            let id = loopr::id::short_id(parse_loop_id(&id)?);
+            let iteration_suffix = iteration.map(|index| format!(" (iteration {index})")).unwrap_or_default();
+            if edit {
+                println!("loopr: no daemon connection configured yet (would open {id}'s feedback{iteration_suffix} for editing)");
+            } else {
+                println!("loopr: no daemon connection configured yet (would print {id}'s feedback{iteration_suffix})");
+            }
+        }
+        Some(Command::Run { manifest }) => match manifest {
+            Some(path) => println!("loopr: no daemon connection configured yet (would run and write a manifest to {})", path.display()),
+            None => println!("loopr: no daemon connection configured yet (would run)"),
+        },
+        Some(Command::Usage { from, to, group_by, format }) => {
+            println!("loopr: no daemon connection configured yet (would export {format:?}-format usage from {from} to {to}, grouped by {group_by:?})");
+        }
+    }
+    Ok(())
+}
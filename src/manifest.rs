@@ -0,0 +1,110 @@
+//! Machine-readable run manifest: a structured JSON record of everything
+//! a run did (loops touched, artifact versions, validation feedback,
+//! costs), for CI pipelines to archive and gate subsequent steps on.
+//! Built from the same [`crate::storage::Storage`] data
+//! [`crate::analytics`] reports on, itemized per loop instead of
+//! aggregated.
+
+use crate::domain::{LoopRecord, LoopStatus, LoopType};
+use crate::storage::Storage;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One loop's contribution to a run: what it was, what it cost, and
+/// which gates it failed along the way.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoopManifestEntry {
+    pub id: Uuid,
+    pub loop_type: LoopType,
+    pub description: String,
+    pub status: LoopStatus,
+    pub iterations: usize,
+    pub cost_usd: f64,
+    pub artifact_versions: usize,
+    pub failed_gates: Vec<String>,
+}
+
+/// Everything a run did, suitable for a CI pipeline to archive and gate
+/// subsequent steps on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RunManifest {
+    pub loops: Vec<LoopManifestEntry>,
+    pub total_cost_usd: f64,
+}
+
+fn entry(storage: &dyn Storage, record: &LoopRecord) -> anyhow::Result<LoopManifestEntry> {
+    let cost_usd = record.iterations.iter().map(|iteration| iteration.cost_usd).sum();
+    let failed_gates = record.iterations.iter().filter_map(|iteration| iteration.feedback.clone()).collect();
+    let artifact_versions = storage.artifact_history(record.id)?.len();
+    Ok(LoopManifestEntry {
+        id: record.id,
+        loop_type: record.loop_type.clone(),
+        description: record.description.clone(),
+        status: record.status,
+        iterations: record.iterations.len(),
+        cost_usd,
+        artifact_versions,
+        failed_gates,
+    })
+}
+
+/// Builds a [`RunManifest`] from every loop in storage.
+pub fn build_manifest(storage: &dyn Storage) -> anyhow::Result<RunManifest> {
+    let records = storage.list_loops()?;
+    let loops: Vec<LoopManifestEntry> = records.iter().map(|record| entry(storage, record)).collect::<anyhow::Result<_>>()?;
+    let total_cost_usd = loops.iter().map(|entry| entry.cost_usd).sum();
+    Ok(RunManifest { loops, total_cost_usd })
+}
+
+/// Renders a [`RunManifest`] as pretty-printed JSON, for writing to the
+/// path passed to `loopr run --manifest`.
+pub fn render_json(manifest: &RunManifest) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(manifest)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Iteration;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn build_manifest_itemizes_every_loop_with_cost_and_failed_gates() {
+        let storage = InMemoryStorage::new();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let mut iteration = Iteration::new(0);
+        iteration.cost_usd = 0.75;
+        iteration.feedback = Some("test failed: session expiry".to_string());
+        record.iterations = vec![iteration];
+        let id = record.id;
+        storage.save_loop(record).unwrap();
+        storage.save_artifact_version(id, 0, "draft".to_string()).unwrap();
+
+        let manifest = build_manifest(&storage).unwrap();
+        assert_eq!(manifest.loops.len(), 1);
+        assert_eq!(manifest.loops[0].cost_usd, 0.75);
+        assert_eq!(manifest.loops[0].artifact_versions, 1);
+        assert_eq!(manifest.loops[0].failed_gates, vec!["test failed: session expiry".to_string()]);
+        assert_eq!(manifest.total_cost_usd, 0.75);
+    }
+
+    #[test]
+    fn render_json_round_trips_through_serde() {
+        let manifest = RunManifest {
+            loops: vec![LoopManifestEntry {
+                id: Uuid::new_v4(),
+                loop_type: LoopType::Ralph,
+                description: "fix the bug".to_string(),
+                status: LoopStatus::Completed,
+                iterations: 1,
+                cost_usd: 0.75,
+                artifact_versions: 1,
+                failed_gates: vec![],
+            }],
+            total_cost_usd: 0.75,
+        };
+        let json = render_json(&manifest).unwrap();
+        let round_tripped: RunManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_tripped, manifest);
+    }
+}
@@ -0,0 +1,97 @@
+//! Manual loop creation: an operator who already has a hand-written
+//! spec/phase/ralph artifact can inject it directly into the hierarchy
+//! instead of waiting for the normal LLM-generated one, so its children
+//! spawn exactly as they would have otherwise.
+
+use crate::domain::{LoopRecord, LoopType};
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// The loop type a manually-created `loop_type` loop must be parented
+/// under, mirroring the hierarchy the scheduler spawns automatically.
+/// `Plan` and `Custom` types have no fixed parent requirement.
+fn required_parent_type(loop_type: &LoopType) -> Option<LoopType> {
+    match loop_type {
+        LoopType::Spec => Some(LoopType::Plan),
+        LoopType::Phase => Some(LoopType::Spec),
+        LoopType::Ralph => Some(LoopType::Phase),
+        LoopType::Plan | LoopType::Custom(_) => None,
+    }
+}
+
+/// Creates a `loop_type` loop directly from a hand-written `content`
+/// artifact, recording it as the loop's first artifact version the same
+/// way an LLM-generated one would be. Refuses when `loop_type` requires a
+/// parent and none is given, or the given parent is the wrong type.
+pub fn create_manual_loop(
+    storage: &dyn Storage,
+    loop_type: LoopType,
+    parent_id: Option<Uuid>,
+    content: String,
+) -> anyhow::Result<LoopRecord> {
+    if let Some(expected) = required_parent_type(&loop_type) {
+        let parent_id = parent_id
+            .ok_or_else(|| anyhow::anyhow!("a {} loop needs a --parent {} id", loop_type.as_str(), expected.as_str()))?;
+        let parent = storage
+            .get_loop(parent_id)?
+            .ok_or_else(|| anyhow::anyhow!("parent loop {parent_id} not found"))?;
+        if parent.loop_type != expected {
+            anyhow::bail!(
+                "a {} loop's parent must be a {}, but {parent_id} is a {}",
+                loop_type.as_str(),
+                expected.as_str(),
+                parent.loop_type.as_str()
+            );
+        }
+    }
+
+    let description = content.lines().next().unwrap_or_default().trim_start_matches('#').trim().to_string();
+    let record = LoopRecord::new(loop_type, parent_id, description);
+    storage.save_loop(record.clone())?;
+    storage.save_artifact_version(record.id, 0, content)?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn a_plan_needs_no_parent() {
+        let storage = InMemoryStorage::new();
+        let record = create_manual_loop(&storage, LoopType::Plan, None, "# Ship feature x".to_string()).unwrap();
+        assert_eq!(record.parent_id, None);
+        assert_eq!(record.description, "Ship feature x");
+    }
+
+    #[test]
+    fn a_spec_requires_a_plan_parent() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship feature x");
+        let plan_id = plan.id;
+        storage.save_loop(plan).unwrap();
+
+        let spec = create_manual_loop(&storage, LoopType::Spec, Some(plan_id), "# Spec one".to_string()).unwrap();
+        assert_eq!(spec.parent_id, Some(plan_id));
+        assert_eq!(storage.artifact_history(spec.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn a_spec_without_a_parent_is_refused() {
+        let storage = InMemoryStorage::new();
+        let err = create_manual_loop(&storage, LoopType::Spec, None, "# Spec one".to_string()).unwrap_err();
+        assert!(err.to_string().contains("needs a --parent"));
+    }
+
+    #[test]
+    fn a_mismatched_parent_type_is_refused() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship feature x");
+        let plan_id = plan.id;
+        storage.save_loop(plan).unwrap();
+
+        let err = create_manual_loop(&storage, LoopType::Phase, Some(plan_id), "# Phase one".to_string()).unwrap_err();
+        assert!(err.to_string().contains("must be a spec"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Durable, cross-loop project memory: facts worth keeping long after the
+//! loop that surfaced them ends (e.g. "this repo uses eyre not anyhow",
+//! "tests require DATABASE_URL"), injected into future prompts so the
+//! same mistake or discovery isn't repeated. Extraction is marker-based:
+//! an iteration's feedback may include a `LESSON: <text>` line, which
+//! this module lifts out and persists.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const LESSON_MARKER: &str = "LESSON:";
+
+/// One durable lesson, tied back to the loop that surfaced it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryEntry {
+    pub id: Uuid,
+    pub lesson: String,
+    pub source_loop_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MemoryEntry {
+    pub fn new(lesson: impl Into<String>, source_loop_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            lesson: lesson.into(),
+            source_loop_id,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A project's full set of remembered lessons.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectMemory {
+    pub entries: Vec<MemoryEntry>,
+}
+
+impl ProjectMemory {
+    pub fn remember(&mut self, entry: MemoryEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Removes the entry with `id`, returning whether one was found.
+    pub fn forget(&mut self, id: Uuid) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != before
+    }
+
+    /// Renders every entry as a bullet list ready to splice into a system
+    /// or user prompt; empty when there's nothing remembered yet.
+    pub fn render(&self) -> String {
+        if self.entries.is_empty() {
+            return String::new();
+        }
+        let mut body = String::from("## Project memory\n\n");
+        for entry in &self.entries {
+            body.push_str(&format!("- {}\n", entry.lesson));
+        }
+        body
+    }
+}
+
+/// Extracts every `LESSON: ...` line from a loop's iteration feedback,
+/// the convention loops use to flag something durable enough to remember
+/// past this loop's lifetime.
+pub fn extract_lessons(record: &crate::domain::LoopRecord) -> Vec<MemoryEntry> {
+    record
+        .iterations
+        .iter()
+        .filter_map(|iteration| iteration.feedback.as_deref())
+        .flat_map(|feedback| feedback.lines())
+        .filter_map(|line| line.trim().strip_prefix(LESSON_MARKER))
+        .map(|lesson| MemoryEntry::new(lesson.trim(), record.id))
+        .collect()
+}
+
+/// Where a project's memory is persisted.
+pub fn memory_path(home: &Path, project: &str) -> PathBuf {
+    home.join(".loopr").join("memory").join(project).join("memory.yml")
+}
+
+pub fn load_memory(path: &Path) -> anyhow::Result<ProjectMemory> {
+    if !path.exists() {
+        return Ok(ProjectMemory::default());
+    }
+    Ok(serde_yaml::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+pub fn save_memory(path: &Path, memory: &ProjectMemory) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_yaml::to_string(memory)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+
+    #[test]
+    fn extracts_lesson_marked_lines_from_feedback() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        let mut iteration = Iteration::new(0);
+        iteration.feedback = Some("compile error on line 4\nLESSON: this repo uses eyre not anyhow".to_string());
+        record.iterations.push(iteration);
+
+        let lessons = extract_lessons(&record);
+        assert_eq!(lessons.len(), 1);
+        assert_eq!(lessons[0].lesson, "this repo uses eyre not anyhow");
+        assert_eq!(lessons[0].source_loop_id, record.id);
+    }
+
+    #[test]
+    fn forget_removes_the_matching_entry() {
+        let mut memory = ProjectMemory::default();
+        let entry = MemoryEntry::new("tests require DATABASE_URL", Uuid::new_v4());
+        let id = entry.id;
+        memory.remember(entry);
+        assert!(memory.forget(id));
+        assert!(memory.entries.is_empty());
+        assert!(!memory.forget(id));
+    }
+
+    #[test]
+    fn render_lists_every_entry_as_a_bullet() {
+        let mut memory = ProjectMemory::default();
+        memory.remember(MemoryEntry::new("uses eyre not anyhow", Uuid::new_v4()));
+        memory.remember(MemoryEntry::new("tests require DATABASE_URL", Uuid::new_v4()));
+        let rendered = memory.render();
+        assert!(rendered.contains("- uses eyre not anyhow"));
+        assert!(rendered.contains("- tests require DATABASE_URL"));
+    }
+
+    #[test]
+    fn save_and_load_round_trip_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = memory_path(dir.path(), "loopr");
+        let mut memory = ProjectMemory::default();
+        memory.remember(MemoryEntry::new("uses eyre not anyhow", Uuid::new_v4()));
+        save_memory(&path, &memory).unwrap();
+        let loaded = load_memory(&path).unwrap();
+        assert_eq!(loaded.entries.len(), 1);
+        assert_eq!(loaded.entries[0].lesson, "uses eyre not anyhow");
+    }
+
+    #[test]
+    fn loading_a_missing_memory_file_returns_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let memory = load_memory(&memory_path(dir.path(), "nonexistent")).unwrap();
+        assert!(memory.entries.is_empty());
+    }
+}
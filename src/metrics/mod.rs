@@ -0,0 +1,97 @@
+//! Aggregate reporting over stored loop records.
+
+use crate::domain::LoopStatus;
+use crate::prompts::Experiment;
+use crate::storage::Storage;
+
+/// Success rate and average iteration count for one side of an
+/// [`Experiment`] split.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VariantStats {
+    pub loops: u32,
+    pub completed: u32,
+    pub total_iterations: u32,
+}
+
+impl VariantStats {
+    pub fn success_rate(&self) -> f64 {
+        if self.loops == 0 {
+            0.0
+        } else {
+            self.completed as f64 / self.loops as f64
+        }
+    }
+
+    pub fn avg_iterations(&self) -> f64 {
+        if self.loops == 0 {
+            0.0
+        } else {
+            self.total_iterations as f64 / self.loops as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExperimentReport {
+    pub variant_a: VariantStats,
+    pub variant_b: VariantStats,
+}
+
+/// Aggregates every stored loop whose first iteration was rendered from
+/// one of `experiment`'s two template versions into a success-rate and
+/// iteration-count comparison.
+pub fn get(storage: &dyn Storage, experiment: &Experiment) -> anyhow::Result<ExperimentReport> {
+    let mut report = ExperimentReport::default();
+    for record in storage.list_loops()? {
+        let Some(first) = record.iterations.first() else {
+            continue;
+        };
+        let stats = match first.prompt_version.as_deref() {
+            Some(v) if v == experiment.variant_a.version => &mut report.variant_a,
+            Some(v) if v == experiment.variant_b.version => &mut report.variant_b,
+            _ => continue,
+        };
+        stats.loops += 1;
+        stats.total_iterations += record.iterations.len() as u32;
+        if record.status == LoopStatus::Completed {
+            stats.completed += 1;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use crate::prompts::PromptTemplate;
+    use crate::storage::InMemoryStorage;
+
+    #[test]
+    fn splits_loops_by_prompt_version() {
+        let experiment = Experiment::new(
+            "tone",
+            PromptTemplate::new("a", "terse"),
+            PromptTemplate::new("b", "encouraging"),
+        );
+        let storage = InMemoryStorage::new();
+
+        let mut completed = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        completed.status = LoopStatus::Completed;
+        let mut iteration = Iteration::new(0);
+        iteration.prompt_version = Some(experiment.variant_a.version.clone());
+        completed.iterations.push(iteration);
+        storage.save_loop(completed).unwrap();
+
+        let mut failed = LoopRecord::new(LoopType::Ralph, None, "fix it too");
+        failed.status = LoopStatus::Failed;
+        let mut iteration = Iteration::new(0);
+        iteration.prompt_version = Some(experiment.variant_b.version.clone());
+        failed.iterations.push(iteration);
+        storage.save_loop(failed).unwrap();
+
+        let report = get(&storage, &experiment).unwrap();
+        assert_eq!(report.variant_a.success_rate(), 1.0);
+        assert_eq!(report.variant_b.success_rate(), 0.0);
+    }
+}
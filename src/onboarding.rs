@@ -0,0 +1,99 @@
+//! First-run setup: detects whether an API key and project config are in
+//! place, and scaffolds a default `loopr.yml` when they aren't — used by
+//! `loopr init` and the TUI's onboarding modal. Replaces the old silent
+//! fallback to "LLM not available" with something actionable.
+
+use crate::config::Config;
+use std::path::{Path, PathBuf};
+
+/// Where a user's global config lives.
+pub fn config_path(home: &Path) -> PathBuf {
+    home.join(".config").join("loopr").join("loopr.yml")
+}
+
+/// What's missing before loopr can talk to an LLM and run loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnboardingStatus {
+    pub api_key_present: bool,
+    pub config_exists: bool,
+}
+
+impl OnboardingStatus {
+    /// Whether there's nothing left for the wizard to do.
+    pub fn is_complete(&self) -> bool {
+        self.api_key_present && self.config_exists
+    }
+}
+
+/// Checks that `api_key_env`, the API key variable's value (e.g. what
+/// `ANTHROPIC_API_KEY` holds), is present and non-empty, and that a config
+/// file exists at `home`'s default path. The environment is not read here.
+pub fn check_status(home: &Path, api_key_env: Option<&str>) -> OnboardingStatus {
+    OnboardingStatus {
+        api_key_present: api_key_env.is_some_and(|key| !key.is_empty()),
+        config_exists: config_path(home).exists(),
+    }
+}
+
+/// Writes a default `loopr.yml` at `path` if one doesn't already exist.
+/// Returns whether it actually wrote one, so the caller can tell "created"
+/// from "already configured" apart without a second existence check.
+pub fn scaffold_config(path: &Path, model: Option<String>) -> anyhow::Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let config = Config { model, ..Config::default() };
+    std::fs::write(path, serde_yaml::to_string(&config)?)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_incomplete_when_both_are_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let status = check_status(dir.path(), None);
+        assert!(!status.api_key_present);
+        assert!(!status.config_exists);
+        assert!(!status.is_complete());
+    }
+
+    #[test]
+    fn reports_complete_once_the_key_and_config_are_present() {
+        let dir = tempfile::tempdir().unwrap();
+        scaffold_config(&config_path(dir.path()), None).unwrap();
+        let status = check_status(dir.path(), Some("sk-ant-test"));
+        assert!(status.is_complete());
+    }
+
+    #[test]
+    fn an_empty_env_value_does_not_count_as_present() {
+        let dir = tempfile::tempdir().unwrap();
+        let status = check_status(dir.path(), Some(""));
+        assert!(!status.api_key_present);
+    }
+
+    #[test]
+    fn scaffolding_writes_a_default_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_path(dir.path());
+        assert!(scaffold_config(&path, Some("claude-sonnet".to_string())).unwrap());
+        let loaded: Config = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.model, Some("claude-sonnet".to_string()));
+    }
+
+    #[test]
+    fn scaffolding_never_overwrites_an_existing_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_path(dir.path());
+        scaffold_config(&path, Some("claude-sonnet".to_string())).unwrap();
+        assert!(!scaffold_config(&path, Some("claude-haiku".to_string())).unwrap());
+        let loaded: Config = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.model, Some("claude-sonnet".to_string()));
+    }
+}
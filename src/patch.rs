@@ -0,0 +1,83 @@
+//! Exporting a loop's accumulated worktree changes as a patch file, for a
+//! user who wants to apply an agent's work by hand instead of merging it
+//! through the normal queue.
+
+use std::path::Path;
+use std::process::Command;
+
+/// The unified diff from a plain `git diff` in `worktree`: its unstaged
+/// working-tree changes against the index, ready to apply elsewhere with
+/// `git apply`. Staged changes and commits already made are not included.
+pub fn capture_diff(worktree: &Path) -> anyhow::Result<String> {
+    let output = Command::new("git").arg("diff").current_dir(worktree).output()?;
+    if !output.status.success() {
+        anyhow::bail!("git diff failed in {}: {}", worktree.display(), String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A `git diff --stat`-style summary built from the [`crate::diff_summary::DiffSummary`]
+/// the TUI tree already computes per iteration, rather than shelling out
+/// a second time for a different diff format.
+pub fn render_stat(summary: &crate::diff_summary::DiffSummary) -> String {
+    let mut lines: Vec<String> = summary.files_changed.iter().map(|file| format!(" {file}")).collect();
+    lines.push(format!(
+        " {} file(s) changed, {} insertion(s)(+), {} deletion(s)(-)",
+        summary.files_changed.len(),
+        summary.lines_added,
+        summary.lines_removed
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_summary::DiffSummary;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn init_repo_with_change() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            StdCommand::new("git").args(args).current_dir(dir.path()).output().unwrap();
+        };
+        run(&["init", "-q"]);
+        run(&["config", "user.email", "loop@example.com"]);
+        run(&["config", "user.name", "loopr"]);
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-q", "-m", "initial"]);
+        std::fs::write(dir.path().join("a.txt"), "one\ntwo\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn captures_the_working_tree_diff() {
+        let dir = init_repo_with_change();
+        let diff = capture_diff(dir.path()).unwrap();
+        assert!(diff.contains("+two"));
+    }
+
+    #[test]
+    fn a_clean_worktree_has_an_empty_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        StdCommand::new("git").arg("init").arg("-q").current_dir(dir.path()).output().unwrap();
+        let diff = capture_diff(dir.path()).unwrap();
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn render_stat_lists_each_file_and_a_totals_line() {
+        let summary = DiffSummary {
+            files_changed: vec!["src/lib.rs".to_string(), "src/main.rs".to_string()],
+            lines_added: 10,
+            lines_removed: 3,
+            tests_added: 1,
+        };
+        let stat = render_stat(&summary);
+        assert!(stat.contains(" src/lib.rs"));
+        assert!(stat.contains(" src/main.rs"));
+        assert!(stat.contains("2 file(s) changed, 10 insertion(s)(+), 3 deletion(s)(-)"));
+    }
+}
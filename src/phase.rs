@@ -0,0 +1,47 @@
+//! Test-first execution for `Phase` loops: spawn a tests-writing `Ralph`
+//! loop before the implementation `Ralph` loop, so acceptance criteria
+//! start as failing tests rather than prose the implementation loop grades
+//! its own homework against.
+
+use crate::domain::FailureCategory;
+
+/// The two child-loop descriptions a test-first phase spawns, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFirstPlan {
+    pub tests_description: String,
+    pub implementation_description: String,
+}
+
+/// Builds the tests/implementation description pair for a phase's
+/// acceptance criteria.
+pub fn test_first_plan(phase_description: &str) -> TestFirstPlan {
+    TestFirstPlan {
+        tests_description: format!("Write failing tests covering the acceptance criteria for: {phase_description}. Do not implement the feature."),
+        implementation_description: format!("Make the failing tests pass for: {phase_description}"),
+    }
+}
+
+/// A tests-writing iteration only counts as done once `cargo test` fails
+/// for the right reason (the assertions don't hold yet), not because the
+/// tests don't compile or the harness errored.
+pub fn fails_for_the_right_reason(category: FailureCategory) -> bool {
+    matches!(category, FailureCategory::TestAssertion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_keeps_tests_and_implementation_separate() {
+        let plan = test_first_plan("add a retry budget to the HTTP client");
+        assert!(plan.tests_description.contains("Write failing tests"));
+        assert!(plan.implementation_description.contains("Make the failing tests pass"));
+    }
+
+    #[test]
+    fn only_test_assertion_failures_count_as_right_reason() {
+        assert!(fails_for_the_right_reason(FailureCategory::TestAssertion));
+        assert!(!fails_for_the_right_reason(FailureCategory::CompileError));
+    }
+}
@@ -0,0 +1,47 @@
+//! Renders a loop's [`crate::domain::LoopRecord::pinned_files`] as
+//! [`crate::context::Section`]s so files an operator knows the LLM keeps
+//! missing (a crucial interface definition, say) are always included in
+//! the prompt, subject to the same [`crate::context::fit`] token budget
+//! as every other section.
+
+use crate::context::{Priority, Section};
+use std::path::Path;
+
+/// Reads each of `pinned_files` from `worktree` and wraps it as a
+/// [`Priority::Required`] section named after its path. A file that no
+/// longer exists (renamed or deleted since it was pinned) is skipped
+/// rather than failing the whole prompt.
+pub fn render_pinned_sections(worktree: &Path, pinned_files: &[String]) -> Vec<Section> {
+    pinned_files
+        .iter()
+        .filter_map(|path| {
+            let contents = std::fs::read_to_string(worktree.join(path)).ok()?;
+            Some(Section::new(format!("pinned: {path}"), contents, Priority::Required))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn a_pinned_file_becomes_a_required_section() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("service.rs"), "pub struct AuthService;").unwrap();
+
+        let sections = render_pinned_sections(dir.path(), &["service.rs".to_string()]);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].name, "pinned: service.rs");
+        assert_eq!(sections[0].priority, Priority::Required);
+        assert!(sections[0].content.contains("AuthService"));
+    }
+
+    #[test]
+    fn a_missing_pinned_file_is_skipped() {
+        let dir = tempdir().unwrap();
+        let sections = render_pinned_sections(dir.path(), &["gone.rs".to_string()]);
+        assert!(sections.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Post-mortem generation for a plan tree that ended in failure: what was
+//! attempted, which gates failed, cost spent, and an LLM's suspected root
+//! cause, assembled into a markdown artifact stored alongside the plan
+//! (via [`crate::storage::Storage::save_artifact_version`]) and viewable
+//! via `loopr postmortem <id>`.
+
+use crate::delete::descendants_of;
+use crate::domain::{FailureCategory, LoopRecord, LoopStatus};
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// What was attempted, which gates failed, cost spent, and the LLM's
+/// suspected root cause for a failed plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostMortem {
+    pub plan_id: Uuid,
+    pub attempted: Vec<String>,
+    pub failed_gates: Vec<String>,
+    pub total_cost_usd: f64,
+    pub suspected_root_cause: String,
+}
+
+/// Builds the prompt asking an LLM to suspect a root cause for why `plan`
+/// failed, given the distinct failure categories its descendants hit.
+/// Actually calling an [`crate::llm::LlmClient`] with it is left to the
+/// daemon's orchestration layer, same split as
+/// [`crate::self_review::build_self_review_prompt`].
+pub fn build_root_cause_prompt(plan: &LoopRecord, descendants: &[LoopRecord]) -> String {
+    let mut categories: Vec<FailureCategory> = Vec::new();
+    for category in descendants.iter().flat_map(|record| record.iterations.iter()).filter_map(|iteration| iteration.failure_category) {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    let categories_list = categories.iter().map(|category| format!("{category:?}")).collect::<Vec<_>>().join(", ");
+    format!(
+        "The plan \"{}\" failed. Across its specs, phases, and ralph loops it repeatedly hit: \
+         {categories_list}. In 2-3 sentences, suspect a root cause for why this plan failed overall.",
+        plan.description
+    )
+}
+
+/// Assembles a [`PostMortem`] from a failed plan and its descendants:
+/// what was attempted (every descendant's description), the gates that
+/// failed (iteration feedback across descendants), and total cost.
+/// `root_cause` is the LLM's response to [`build_root_cause_prompt`].
+pub fn build(plan: &LoopRecord, descendants: &[LoopRecord], root_cause: String) -> PostMortem {
+    let attempted = descendants.iter().map(|record| record.description.clone()).collect();
+    let failed_gates = descendants.iter().flat_map(|record| record.iterations.iter()).filter_map(|iteration| iteration.feedback.clone()).collect();
+    let total_cost_usd = descendants.iter().flat_map(|record| record.iterations.iter()).map(|iteration| iteration.cost_usd).sum();
+    PostMortem { plan_id: plan.id, attempted, failed_gates, total_cost_usd, suspected_root_cause: root_cause }
+}
+
+/// Renders a [`PostMortem`] as markdown, for storage as an artifact
+/// version and display by `loopr postmortem <id>`.
+pub fn render(postmortem: &PostMortem) -> String {
+    let mut rendered = String::from("# Post-mortem\n\n## Attempted\n\n");
+    for item in &postmortem.attempted {
+        rendered.push_str(&format!("- {item}\n"));
+    }
+    rendered.push_str("\n## Failed gates\n\n");
+    for gate in &postmortem.failed_gates {
+        rendered.push_str(&format!("- {gate}\n"));
+    }
+    rendered.push_str(&format!("\n## Cost\n\n${:.2} spent\n\n## Suspected root cause\n\n{}\n", postmortem.total_cost_usd, postmortem.suspected_root_cause));
+    rendered
+}
+
+/// Generates a post-mortem for `plan_id` and persists its rendered
+/// markdown as a new artifact version, refusing if the plan hasn't
+/// actually failed. `root_cause` is the LLM's response to
+/// [`build_root_cause_prompt`], supplied by the caller since this layer
+/// doesn't call an [`crate::llm::LlmClient`] itself.
+pub fn generate(storage: &dyn Storage, plan_id: Uuid, root_cause: String) -> anyhow::Result<PostMortem> {
+    let plan = storage.get_loop(plan_id)?.ok_or_else(|| anyhow::anyhow!("no loop with id {plan_id}"))?;
+    if plan.status != LoopStatus::Failed {
+        anyhow::bail!("loop {plan_id} has not failed; refusing to generate a post-mortem for it");
+    }
+    let descendant_ids = descendants_of(storage, plan_id)?;
+    let descendants: Vec<LoopRecord> = descendant_ids
+        .into_iter()
+        .filter(|id| *id != plan_id)
+        .filter_map(|id| storage.get_loop(id).transpose())
+        .collect::<anyhow::Result<_>>()?;
+
+    let postmortem = build(&plan, &descendants, root_cause);
+    storage.save_artifact_version(plan_id, plan.iterations.len() as u32, render(&postmortem))?;
+    Ok(postmortem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use crate::storage::InMemoryStorage;
+
+    fn failed_iteration(index: u32, category: FailureCategory, feedback: &str, cost_usd: f64) -> Iteration {
+        let mut iteration = Iteration::new(index);
+        iteration.failure_category = Some(category);
+        iteration.feedback = Some(feedback.to_string());
+        iteration.cost_usd = cost_usd;
+        iteration
+    }
+
+    #[test]
+    fn build_root_cause_prompt_lists_distinct_failure_categories() {
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let mut ralph = LoopRecord::new(LoopType::Ralph, Some(plan.id), "implement session tokens");
+        ralph.iterations = vec![failed_iteration(0, FailureCategory::CompileError, "compile error", 0.1), failed_iteration(1, FailureCategory::CompileError, "compile error", 0.1)];
+        let prompt = build_root_cause_prompt(&plan, &[ralph]);
+        assert!(prompt.contains("CompileError"));
+        assert!(prompt.contains("ship login flow"));
+    }
+
+    #[test]
+    fn build_collects_attempted_gates_and_cost_across_descendants() {
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let mut ralph = LoopRecord::new(LoopType::Ralph, Some(plan.id), "implement session tokens");
+        ralph.iterations = vec![failed_iteration(0, FailureCategory::TestAssertion, "test failed: session expiry", 1.25)];
+        let postmortem = build(&plan, &[ralph], "gave up mid-refactor".to_string());
+        assert_eq!(postmortem.plan_id, plan.id);
+        assert_eq!(postmortem.attempted, vec!["implement session tokens".to_string()]);
+        assert_eq!(postmortem.failed_gates, vec!["test failed: session expiry".to_string()]);
+        assert_eq!(postmortem.total_cost_usd, 1.25);
+        assert_eq!(postmortem.suspected_root_cause, "gave up mid-refactor");
+    }
+
+    #[test]
+    fn render_includes_every_section() {
+        let postmortem = PostMortem {
+            plan_id: Uuid::new_v4(),
+            attempted: vec!["implement session tokens".to_string()],
+            failed_gates: vec!["test failed: session expiry".to_string()],
+            total_cost_usd: 1.25,
+            suspected_root_cause: "gave up mid-refactor".to_string(),
+        };
+        let rendered = render(&postmortem);
+        assert!(rendered.contains("## Attempted"));
+        assert!(rendered.contains("implement session tokens"));
+        assert!(rendered.contains("## Failed gates"));
+        assert!(rendered.contains("test failed: session expiry"));
+        assert!(rendered.contains("$1.25"));
+        assert!(rendered.contains("gave up mid-refactor"));
+    }
+
+    #[test]
+    fn generate_persists_an_artifact_version_for_a_failed_plan() {
+        let storage = InMemoryStorage::new();
+        let mut plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        plan.status = LoopStatus::Failed;
+        let plan_id = plan.id;
+        let mut spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "Spec: auth");
+        spec.iterations = vec![failed_iteration(0, FailureCategory::Lint, "lint: unused import", 0.4)];
+        storage.save_loop(plan).unwrap();
+        storage.save_loop(spec).unwrap();
+
+        let postmortem = generate(&storage, plan_id, "root cause: scope too broad".to_string()).unwrap();
+        assert_eq!(postmortem.total_cost_usd, 0.4);
+
+        let history = storage.artifact_history(plan_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].content.contains("root cause: scope too broad"));
+    }
+
+    #[test]
+    fn generate_refuses_a_plan_that_has_not_failed() {
+        let storage = InMemoryStorage::new();
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let plan_id = plan.id;
+        storage.save_loop(plan).unwrap();
+
+        let err = generate(&storage, plan_id, "root cause".to_string()).unwrap_err();
+        assert!(err.to_string().contains("has not failed"));
+    }
+}
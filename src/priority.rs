@@ -0,0 +1,76 @@
+//! Loop scheduling priority. Most loops are prioritized automatically
+//! from their status and place in the hierarchy, but an operator
+//! sometimes needs to bump an urgent phase to the front of the queue or
+//! deprioritize background work; [`LoopRecord::priority_override`] always
+//! wins over [`automatic_priority`] when the scheduler orders work.
+
+use crate::domain::{LoopRecord, LoopStatus, LoopType};
+
+/// Higher runs first.
+pub type Priority = i32;
+
+/// The priority a loop gets with no operator override. Awaiting-approval
+/// loops outrank pending ones (an operator is already waiting on them),
+/// and loops lower in the hierarchy outrank their ancestors (a Ralph
+/// close to done outranks the Plan still waiting on several of them).
+pub fn automatic_priority(record: &LoopRecord) -> Priority {
+    let status_weight = match record.status {
+        LoopStatus::AwaitingApproval => 30,
+        LoopStatus::Running | LoopStatus::Validating => 20,
+        LoopStatus::Pending => 10,
+        LoopStatus::Failed => 5,
+        LoopStatus::Completed | LoopStatus::Cancelled | LoopStatus::Invalidated => 0,
+    };
+    let type_weight = match record.loop_type {
+        LoopType::Ralph => 3,
+        LoopType::Phase => 2,
+        LoopType::Spec => 1,
+        LoopType::Plan | LoopType::Custom(_) => 0,
+    };
+    status_weight + type_weight
+}
+
+/// The priority actually used to schedule `record`: its operator override
+/// if one is set, else [`automatic_priority`].
+pub fn effective_priority(record: &LoopRecord) -> Priority {
+    record.priority_override.unwrap_or_else(|| automatic_priority(record))
+}
+
+/// Orders `records` highest effective priority first, for the scheduler
+/// (or a TUI queue view) to consume.
+pub fn order_by_priority(mut records: Vec<LoopRecord>) -> Vec<LoopRecord> {
+    records.sort_by_key(|record| std::cmp::Reverse(effective_priority(record)));
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+
+    #[test]
+    fn awaiting_approval_outranks_pending() {
+        let mut awaiting = LoopRecord::new(LoopType::Phase, None, "needs approval");
+        awaiting.status = LoopStatus::AwaitingApproval;
+        let pending = LoopRecord::new(LoopType::Phase, None, "fresh");
+        assert!(automatic_priority(&awaiting) > automatic_priority(&pending));
+    }
+
+    #[test]
+    fn override_wins_over_the_automatic_priority() {
+        let mut record = LoopRecord::new(LoopType::Plan, None, "background cleanup");
+        record.priority_override = Some(1000);
+        assert_eq!(effective_priority(&record), 1000);
+    }
+
+    #[test]
+    fn order_by_priority_respects_an_override_bump() {
+        let mut urgent = LoopRecord::new(LoopType::Plan, None, "urgent hotfix plan");
+        urgent.priority_override = Some(100);
+        let ralph = LoopRecord::new(LoopType::Ralph, None, "ordinary ralph");
+        let ralph_id = ralph.id;
+        let ordered = order_by_priority(vec![ralph, urgent.clone()]);
+        assert_eq!(ordered[0].id, urgent.id);
+        assert_eq!(ordered[1].id, ralph_id);
+    }
+}
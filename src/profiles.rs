@@ -0,0 +1,110 @@
+//! Language-agnostic project profiles: sensible default validation
+//! commands and protected paths for non-Rust stacks, auto-detected from
+//! a lockfile and overridable via `loopr.yml`'s `profile:` key (see
+//! [`crate::config::Config`]).
+
+use std::path::Path;
+
+/// A stack this build knows sensible defaults for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectProfile {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl ProjectProfile {
+    /// Default validation commands for this stack, in order; feeds
+    /// [`crate::validation::CommandGate`] the way [`crate::loop_types`]'s
+    /// built-in types do for Rust.
+    pub fn validation_commands(&self) -> Vec<String> {
+        match self {
+            ProjectProfile::Rust => vec!["cargo test".to_string(), "cargo clippy --all-targets -- -D warnings".to_string()],
+            ProjectProfile::Node => vec!["npm test".to_string(), "npx eslint .".to_string()],
+            ProjectProfile::Python => vec!["pytest".to_string(), "ruff check .".to_string()],
+            ProjectProfile::Go => vec!["go test ./...".to_string(), "go vet ./...".to_string()],
+        }
+    }
+
+    /// Glob patterns this stack's lockfile and generated artifacts match,
+    /// added to [`crate::guardrails::GuardrailConfig::protected_paths`] so
+    /// an iteration can't hand-edit a lockfile instead of running the
+    /// package manager.
+    pub fn protected_paths(&self) -> Vec<String> {
+        match self {
+            ProjectProfile::Rust => vec!["Cargo.lock".to_string()],
+            ProjectProfile::Node => vec!["package-lock.json".to_string(), "yarn.lock".to_string()],
+            ProjectProfile::Python => vec!["poetry.lock".to_string(), "requirements.txt".to_string()],
+            ProjectProfile::Go => vec!["go.sum".to_string()],
+        }
+    }
+}
+
+/// Lockfiles checked in order; the first one found at `root` decides the
+/// detected profile.
+const MARKERS: &[(&str, ProjectProfile)] = &[
+    ("Cargo.lock", ProjectProfile::Rust),
+    ("package-lock.json", ProjectProfile::Node),
+    ("yarn.lock", ProjectProfile::Node),
+    ("poetry.lock", ProjectProfile::Python),
+    ("requirements.txt", ProjectProfile::Python),
+    ("go.sum", ProjectProfile::Go),
+];
+
+/// Detects a project's profile from whichever lockfile is present at
+/// `root`, or `None` if no known lockfile is found.
+pub fn detect(root: &Path) -> Option<ProjectProfile> {
+    MARKERS.iter().find(|(marker, _)| root.join(marker).exists()).map(|(_, profile)| *profile)
+}
+
+/// Resolves the effective profile for `root`: an explicit `loopr.yml`
+/// override always wins over auto-detection.
+pub fn resolve(root: &Path, override_profile: Option<ProjectProfile>) -> Option<ProjectProfile> {
+    override_profile.or_else(|| detect(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_rust_from_cargo_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.lock"), "").unwrap();
+        assert_eq!(detect(dir.path()), Some(ProjectProfile::Rust));
+    }
+
+    #[test]
+    fn detects_node_from_either_lockfile() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("yarn.lock"), "").unwrap();
+        assert_eq!(detect(dir.path()), Some(ProjectProfile::Node));
+    }
+
+    #[test]
+    fn no_known_lockfile_detects_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect(dir.path()), None);
+    }
+
+    #[test]
+    fn an_explicit_override_wins_over_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.lock"), "").unwrap();
+        assert_eq!(resolve(dir.path(), Some(ProjectProfile::Python)), Some(ProjectProfile::Python));
+    }
+
+    #[test]
+    fn falls_back_to_detection_with_no_override() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("go.sum"), "").unwrap();
+        assert_eq!(resolve(dir.path(), None), Some(ProjectProfile::Go));
+    }
+
+    #[test]
+    fn python_profile_protects_its_lockfiles() {
+        assert!(ProjectProfile::Python.protected_paths().contains(&"poetry.lock".to_string()));
+    }
+}
@@ -0,0 +1,128 @@
+//! Per-loop progress estimation: combines a loop's iteration history with
+//! the historical per-type average from [`crate::analytics::build_report`]
+//! into a completion likelihood and ETA, and flags loops whose failure
+//! count isn't improving for operator attention. Backs the Loops view and
+//! `loop.get`.
+
+use crate::domain::{LoopRecord, LoopType};
+use std::collections::HashMap;
+
+/// How many of a loop's most recent iterations are compared against the
+/// same number before them to decide whether it's stuck.
+const STUCK_WINDOW: usize = 3;
+
+/// One loop's estimated progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressEstimate {
+    /// 0.0-1.0, how close the loop's iteration count is to the historical
+    /// average for its type, capped at 1.0 — an over-running loop reads
+    /// as "basically done", not ">100% done".
+    pub completion_likelihood: f64,
+    /// Iterations still expected: the historical average minus however
+    /// many have run already, never negative.
+    pub estimated_remaining_iterations: u32,
+    /// True once the loop's most recent [`STUCK_WINDOW`] iterations show
+    /// no reduction in failures versus the [`STUCK_WINDOW`] before them.
+    pub stuck: bool,
+}
+
+/// Builds a [`ProgressEstimate`] for `record` against `avg_iterations_by_type`
+/// (see [`crate::analytics::Report::avg_iterations_by_type`]). A loop type
+/// with no historical average yet (too few completed loops) falls back to
+/// the record's own iteration count, reporting it as neither ahead of nor
+/// behind schedule.
+pub fn estimate(record: &LoopRecord, avg_iterations_by_type: &HashMap<LoopType, f64>) -> ProgressEstimate {
+    let completed = record.iterations.len() as f64;
+    let average = avg_iterations_by_type.get(&record.loop_type).copied().unwrap_or_else(|| completed.max(1.0));
+
+    let completion_likelihood = if average <= 0.0 { 1.0 } else { (completed / average).min(1.0) };
+    let estimated_remaining_iterations = (average - completed).max(0.0).round() as u32;
+
+    ProgressEstimate {
+        completion_likelihood,
+        estimated_remaining_iterations,
+        stuck: is_stuck(record),
+    }
+}
+
+/// A loop is stuck when it has run at least `STUCK_WINDOW * 2` iterations
+/// and the failure count of its most recent window is no better than the
+/// window before it.
+fn is_stuck(record: &LoopRecord) -> bool {
+    let failures: Vec<bool> = record.iterations.iter().map(|iteration| iteration.failure_category.is_some()).collect();
+    if failures.len() < STUCK_WINDOW * 2 {
+        return false;
+    }
+    let len = failures.len();
+    let recent_failures = failures[len - STUCK_WINDOW..].iter().filter(|failed| **failed).count();
+    let earlier_failures = failures[len - STUCK_WINDOW * 2..len - STUCK_WINDOW].iter().filter(|failed| **failed).count();
+    recent_failures >= earlier_failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{FailureCategory, Iteration};
+
+    fn record_with_iterations(count: usize) -> LoopRecord {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.iterations = (0..count as u32).map(Iteration::new).collect();
+        record
+    }
+
+    #[test]
+    fn a_loop_behind_the_average_has_a_fractional_likelihood_and_remaining_iterations() {
+        let record = record_with_iterations(2);
+        let averages = HashMap::from([(LoopType::Ralph, 4.0)]);
+        let estimate = estimate(&record, &averages);
+        assert_eq!(estimate.completion_likelihood, 0.5);
+        assert_eq!(estimate.estimated_remaining_iterations, 2);
+    }
+
+    #[test]
+    fn a_loop_past_the_average_caps_at_full_likelihood_with_nothing_remaining() {
+        let record = record_with_iterations(6);
+        let averages = HashMap::from([(LoopType::Ralph, 4.0)]);
+        let estimate = estimate(&record, &averages);
+        assert_eq!(estimate.completion_likelihood, 1.0);
+        assert_eq!(estimate.estimated_remaining_iterations, 0);
+    }
+
+    #[test]
+    fn an_unknown_loop_type_average_falls_back_to_its_own_iteration_count() {
+        let record = record_with_iterations(3);
+        let estimate = estimate(&record, &HashMap::new());
+        assert_eq!(estimate.completion_likelihood, 1.0);
+        assert_eq!(estimate.estimated_remaining_iterations, 0);
+    }
+
+    #[test]
+    fn a_loop_with_non_improving_failures_is_flagged_stuck() {
+        let mut record = record_with_iterations(6);
+        for iteration in &mut record.iterations {
+            iteration.failure_category = Some(FailureCategory::TestAssertion);
+        }
+        let estimate = estimate(&record, &HashMap::new());
+        assert!(estimate.stuck);
+    }
+
+    #[test]
+    fn a_loop_whose_failures_are_clearing_up_is_not_stuck() {
+        let mut record = record_with_iterations(6);
+        for iteration in record.iterations.iter_mut().take(3) {
+            iteration.failure_category = Some(FailureCategory::TestAssertion);
+        }
+        let estimate = estimate(&record, &HashMap::new());
+        assert!(!estimate.stuck);
+    }
+
+    #[test]
+    fn too_few_iterations_to_judge_a_trend_is_never_stuck() {
+        let mut record = record_with_iterations(2);
+        for iteration in &mut record.iterations {
+            iteration.failure_category = Some(FailureCategory::TestAssertion);
+        }
+        let estimate = estimate(&record, &HashMap::new());
+        assert!(!estimate.stuck);
+    }
+}
@@ -0,0 +1,75 @@
+use super::PromptTemplate;
+use uuid::Uuid;
+
+/// Which side of an A/B split a loop was assigned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    A,
+    B,
+}
+
+/// A prompt A/B experiment: two variants of the same template, split across
+/// loops by id so a given loop always sees the same variant across its
+/// iterations.
+#[derive(Debug, Clone)]
+pub struct Experiment {
+    pub name: String,
+    pub variant_a: PromptTemplate,
+    pub variant_b: PromptTemplate,
+}
+
+impl Experiment {
+    pub fn new(name: impl Into<String>, variant_a: PromptTemplate, variant_b: PromptTemplate) -> Self {
+        Self {
+            name: name.into(),
+            variant_a,
+            variant_b,
+        }
+    }
+
+    /// Deterministically assigns a loop to a variant based on its id, so
+    /// repeated lookups for the same loop are stable.
+    pub fn assign(&self, loop_id: Uuid) -> ExperimentAssignment {
+        let variant = if loop_id.as_u128().is_multiple_of(2) {
+            Variant::A
+        } else {
+            Variant::B
+        };
+        let template = match variant {
+            Variant::A => self.variant_a.clone(),
+            Variant::B => self.variant_b.clone(),
+        };
+        ExperimentAssignment {
+            experiment: self.name.clone(),
+            variant,
+            template,
+        }
+    }
+}
+
+/// The variant (and the concrete template to render) a specific loop was
+/// assigned within an [`Experiment`].
+#[derive(Debug, Clone)]
+pub struct ExperimentAssignment {
+    pub experiment: String,
+    pub variant: Variant,
+    pub template: PromptTemplate,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assignment_is_stable_for_the_same_loop_id() {
+        let experiment = Experiment::new(
+            "feedback-tone",
+            PromptTemplate::new("a", "be terse"),
+            PromptTemplate::new("b", "be encouraging"),
+        );
+        let loop_id = Uuid::new_v4();
+        let first = experiment.assign(loop_id);
+        let second = experiment.assign(loop_id);
+        assert_eq!(first.variant, second.variant);
+    }
+}
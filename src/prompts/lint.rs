@@ -0,0 +1,123 @@
+//! Pre-flight checks on a rendered prompt, run before it's sent to the
+//! LLM: unresolved template variables, stray template syntax, duplicate
+//! sections, and a rough token-count-vs-limit check. Catches rendering
+//! bugs with a clear error instead of letting a broken prompt confuse
+//! the model.
+
+use super::estimate_tokens;
+use std::collections::HashSet;
+
+/// One pre-flight check's failure, worded so it can be logged or shown
+/// directly to the operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintViolation {
+    pub reason: String,
+}
+
+/// Checks `prompt` against every rule, running all of them rather than
+/// stopping at the first violation so a single fix cycle can address
+/// everything wrong with the render at once. `model_limit_tokens` is the
+/// target model's context window; `None` skips the token-count check.
+pub fn lint(prompt: &str, model_limit_tokens: Option<usize>) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+
+    violations.extend(unresolved_variables(prompt));
+
+    for marker in ["{%", "%}", "{#", "#}"] {
+        if prompt.contains(marker) {
+            violations.push(LintViolation {
+                reason: format!("stray template syntax {marker:?} was not rendered away"),
+            });
+        }
+    }
+
+    violations.extend(duplicate_sections(prompt));
+
+    if let Some(limit) = model_limit_tokens {
+        let estimated = estimate_tokens(prompt);
+        if estimated > limit {
+            violations.push(LintViolation {
+                reason: format!("prompt is ~{estimated} tokens, exceeding the {limit}-token model limit"),
+            });
+        }
+    }
+
+    violations
+}
+
+/// Finds every `{{name}}` placeholder left unrendered in `prompt`.
+fn unresolved_variables(prompt: &str) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+    let mut rest = prompt;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else { break };
+        let placeholder = &rest[start..start + end + 2];
+        violations.push(LintViolation {
+            reason: format!("unresolved template variable {placeholder:?}"),
+        });
+        rest = &rest[start + end + 2..];
+    }
+    violations
+}
+
+/// Finds markdown headings (`#` through `######`) that repeat verbatim.
+fn duplicate_sections(prompt: &str) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+    let mut seen = HashSet::new();
+    for line in prompt.lines() {
+        let trimmed = line.trim_start();
+        let stripped = trimmed.trim_start_matches('#');
+        let hashes = trimmed.len() - stripped.len();
+        if hashes == 0 || hashes > 6 || !stripped.starts_with(' ') {
+            continue;
+        }
+        let heading = stripped.trim().to_string();
+        if !seen.insert(heading.clone()) {
+            violations.push(LintViolation {
+                reason: format!("section {heading:?} appears more than once"),
+            });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_clean_prompt_has_no_violations() {
+        assert!(lint("## Task\n\nfix the bug", None).is_empty());
+    }
+
+    #[test]
+    fn flags_an_unresolved_variable() {
+        let violations = lint("## Task\n\n{{description}}", None);
+        assert_eq!(violations, vec![LintViolation { reason: "unresolved template variable \"{{description}}\"".to_string() }]);
+    }
+
+    #[test]
+    fn flags_stray_template_syntax() {
+        let violations = lint("{% if x %}hello{% endif %}", None);
+        assert!(violations.iter().any(|v| v.reason.contains("{%")));
+    }
+
+    #[test]
+    fn flags_a_duplicate_section_heading() {
+        let violations = lint("## Task\n\na\n\n## Task\n\nb", None);
+        assert_eq!(violations, vec![LintViolation { reason: "section \"Task\" appears more than once".to_string() }]);
+    }
+
+    #[test]
+    fn flags_a_prompt_over_the_model_limit() {
+        let prompt = "x".repeat(100);
+        let violations = lint(&prompt, Some(10));
+        assert!(violations.iter().any(|v| v.reason.contains("model limit")));
+    }
+
+    #[test]
+    fn runs_every_check_rather_than_stopping_at_the_first() {
+        let prompt = "{{var}} {% tag %}";
+        assert_eq!(lint(prompt, None).len(), 3);
+    }
+}
@@ -0,0 +1,116 @@
+//! Prompt construction: system/user prompt rendering, the Rule of Five
+//! review passes, template versioning, and A/B experiment tracking.
+
+mod experiment;
+mod lint;
+mod passes;
+mod summarize;
+mod template;
+
+pub use experiment::{Experiment, ExperimentAssignment, Variant};
+pub use lint::{lint, LintViolation};
+pub use passes::{default_passes, resolve_passes, PassConfig, PassDefinition};
+pub use summarize::{estimate_tokens, summarize_feedback};
+pub use template::PromptTemplate;
+
+use crate::domain::Iteration;
+
+/// Above this many estimated tokens of combined feedback, `build_user_prompt`
+/// switches from concatenating every prior attempt verbatim to a summarized
+/// "lessons so far" block. See [`summarize_feedback`].
+pub const FEEDBACK_SUMMARY_THRESHOLD_TOKENS: usize = 2000;
+
+/// Runs the configured (or default) review passes and returns the ones
+/// that still apply, in the order they should execute.
+pub fn rule_of_five(config: Option<&PassConfig>) -> Vec<PassDefinition> {
+    resolve_passes(config)
+}
+
+/// The number of passes that will run for a given pass configuration.
+/// Replaces the old fixed `TOTAL_PASSES` constant now that projects can
+/// configure their own pass list.
+pub fn total_passes(config: Option<&PassConfig>) -> usize {
+    resolve_passes(config).len()
+}
+
+/// Renders the system prompt for a loop type. Returns the template used so
+/// callers can stamp its version onto the resulting iteration.
+pub fn build_system_prompt(loop_type: &str) -> (String, PromptTemplate) {
+    let template = PromptTemplate::new(
+        "system",
+        format!("You are loopr, operating a {loop_type} loop. Follow the instructions exactly and report back via tool calls."),
+    );
+    (template.render(), template)
+}
+
+/// Renders the user prompt for the next iteration: the task description
+/// followed by feedback from prior failed iterations, oldest first. Once
+/// the combined feedback grows past [`FEEDBACK_SUMMARY_THRESHOLD_TOKENS`],
+/// older attempts are compressed via [`summarize_feedback`] and only the
+/// latest failure is kept verbatim.
+pub fn build_user_prompt(description: &str, prior_iterations: &[Iteration]) -> (String, PromptTemplate) {
+    let mut body = format!("## Task\n\n{description}\n\n");
+    body.push_str(&summarize_feedback(prior_iterations, FEEDBACK_SUMMARY_THRESHOLD_TOKENS));
+    let template = PromptTemplate::new("user", body);
+    (template.render(), template)
+}
+
+/// Renders a loop's accumulated feedback for `loopr feedback`: by default,
+/// exactly the block [`build_user_prompt`] would append to the next
+/// iteration's prompt; with `iteration` set, just that one attempt's raw
+/// feedback, so an operator can inspect (and, with `--edit`, trim) a single
+/// attempt without wading through the summarized history.
+pub fn render_feedback_for_inspection(prior_iterations: &[Iteration], iteration: Option<u32>) -> String {
+    match iteration {
+        Some(index) => prior_iterations
+            .iter()
+            .find(|it| it.index == index)
+            .and_then(|it| it.feedback.clone())
+            .unwrap_or_default(),
+        None => summarize_feedback(prior_iterations, FEEDBACK_SUMMARY_THRESHOLD_TOKENS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_passes_matches_rule_of_five_len() {
+        assert_eq!(total_passes(None), rule_of_five(None).len());
+    }
+
+    #[test]
+    fn user_prompt_includes_prior_feedback() {
+        let mut iteration = Iteration::new(0);
+        iteration.feedback = Some("compile error on line 4".into());
+        let (rendered, _template) = build_user_prompt("fix the bug", &[iteration]);
+        assert!(rendered.contains("compile error on line 4"));
+    }
+
+    #[test]
+    fn feedback_inspection_defaults_to_what_the_next_prompt_would_contain() {
+        let mut iteration = Iteration::new(0);
+        iteration.feedback = Some("compile error on line 4".into());
+        let rendered = render_feedback_for_inspection(&[iteration.clone()], None);
+        assert_eq!(rendered, summarize_feedback(&[iteration], FEEDBACK_SUMMARY_THRESHOLD_TOKENS));
+    }
+
+    #[test]
+    fn feedback_inspection_can_target_a_single_iteration() {
+        let mut first = Iteration::new(0);
+        first.feedback = Some("first failure".into());
+        let mut second = Iteration::new(1);
+        second.feedback = Some("second failure".into());
+        let rendered = render_feedback_for_inspection(&[first, second], Some(1));
+        assert_eq!(rendered, "second failure");
+    }
+
+    #[test]
+    fn feedback_inspection_returns_empty_for_an_unknown_iteration() {
+        let mut iteration = Iteration::new(0);
+        iteration.feedback = Some("first failure".into());
+        let rendered = render_feedback_for_inspection(&[iteration], Some(99));
+        assert!(rendered.is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// One review pass run over a candidate artifact: a name, the thing it asks
+/// the LLM to focus on, and whether failing it blocks acceptance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassDefinition {
+    pub name: String,
+    pub focus_prompt: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// A project's override of the built-in review passes: which ones run,
+/// in what order, loaded from `loopr.yml`'s `passes:` section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassConfig {
+    #[serde(default)]
+    pub passes: Vec<PassDefinition>,
+}
+
+/// The five passes loopr ships with, used whenever a project hasn't
+/// configured its own `passes:` list.
+pub fn default_passes() -> Vec<PassDefinition> {
+    [
+        ("clarity", "Is the artifact unambiguous to a reader with no other context?"),
+        ("completeness", "Does it cover every requirement in the task?"),
+        ("correctness", "Are the claims and proposed approach technically sound?"),
+        ("consistency", "Does it match conventions used elsewhere in the repo?"),
+        ("conciseness", "Is there anything that can be said in fewer words?"),
+    ]
+    .into_iter()
+    .map(|(name, focus_prompt)| PassDefinition {
+        name: name.to_string(),
+        focus_prompt: focus_prompt.to_string(),
+        required: true,
+    })
+    .collect()
+}
+
+/// Resolves the passes to run for a loop type: the project's configured
+/// list if it has one (already in the order the project wants), otherwise
+/// the built-in defaults.
+pub fn resolve_passes(config: Option<&PassConfig>) -> Vec<PassDefinition> {
+    match config {
+        Some(cfg) if !cfg.passes.is_empty() => cfg.passes.clone(),
+        _ => default_passes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_defaults_when_unconfigured() {
+        let passes = resolve_passes(None);
+        assert_eq!(passes.len(), 5);
+        assert_eq!(passes[0].name, "clarity");
+    }
+
+    #[test]
+    fn project_config_can_reorder_and_drop_passes() {
+        let config = PassConfig {
+            passes: vec![PassDefinition {
+                name: "security".to_string(),
+                focus_prompt: "Does this introduce a vulnerability?".to_string(),
+                required: true,
+            }],
+        };
+        let passes = resolve_passes(Some(&config));
+        assert_eq!(passes.len(), 1);
+        assert_eq!(passes[0].name, "security");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::domain::Iteration;
+
+/// Rough chars-per-token ratio used for the heuristic threshold checks in
+/// this module. Good enough to decide "is this getting too big", not meant
+/// to match a provider's real tokenizer.
+const CHARS_PER_TOKEN: usize = 4;
+
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Compresses all but the most recent feedback entry into a short "lessons
+/// so far" block once the combined feedback would exceed `token_threshold`.
+/// The latest failure is always kept verbatim so the model sees exactly
+/// what it's being asked to fix right now.
+pub fn summarize_feedback(prior_iterations: &[Iteration], token_threshold: usize) -> String {
+    let feedback: Vec<&str> = prior_iterations
+        .iter()
+        .filter_map(|it| it.feedback.as_deref())
+        .collect();
+
+    if feedback.is_empty() {
+        return String::new();
+    }
+
+    let combined: String = feedback.join("\n");
+    if estimate_tokens(&combined) <= token_threshold {
+        return render_verbatim(&feedback);
+    }
+
+    let (latest, older) = feedback.split_last().expect("checked non-empty above");
+    let mut body = String::from("## Lessons so far\n\n");
+    for (index, item) in older.iter().enumerate() {
+        body.push_str(&format!("- (attempt {}) {}\n", index + 1, heuristic_gist(item)));
+    }
+    body.push_str(&format!("\n## Latest failure (attempt {})\n\n{latest}\n", older.len() + 1));
+    body
+}
+
+fn render_verbatim(feedback: &[&str]) -> String {
+    let mut body = String::new();
+    for (index, item) in feedback.iter().enumerate() {
+        body.push_str(&format!("### Attempt {}\n\n{item}\n\n", index + 1));
+    }
+    body
+}
+
+/// Reduces a feedback entry to its first line, which is where this repo's
+/// validation gates put the one-line summary of what failed.
+fn heuristic_gist(feedback: &str) -> &str {
+    feedback.lines().next().unwrap_or(feedback).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iteration_with_feedback(text: &str) -> Iteration {
+        let mut it = Iteration::new(0);
+        it.feedback = Some(text.to_string());
+        it
+    }
+
+    #[test]
+    fn keeps_latest_feedback_verbatim_when_summarizing() {
+        let iterations: Vec<Iteration> = (0..20)
+            .map(|i| iteration_with_feedback(&format!("failure number {i}\nlong detail line repeated many times")))
+            .collect();
+        let summary = summarize_feedback(&iterations, 10);
+        assert!(summary.contains("failure number 19\nlong detail line repeated many times"));
+        assert!(summary.contains("Lessons so far"));
+    }
+
+    #[test]
+    fn stays_verbatim_under_threshold() {
+        let iterations = vec![iteration_with_feedback("short")];
+        let summary = summarize_feedback(&iterations, 1000);
+        assert!(summary.contains("Attempt 1"));
+        assert!(!summary.contains("Lessons so far"));
+    }
+}
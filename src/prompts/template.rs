@@ -0,0 +1,55 @@
+use sha2::{Digest, Sha256};
+
+/// A named prompt body paired with a content hash, so every iteration can
+/// record exactly which wording produced its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub body: String,
+    pub version: String,
+}
+
+impl PromptTemplate {
+    pub fn new(name: impl Into<String>, body: impl Into<String>) -> Self {
+        let body = body.into();
+        let version = hash_content(&body);
+        Self {
+            name: name.into(),
+            body,
+            version,
+        }
+    }
+
+    /// Returns the rendered prompt text. Templates here are pre-rendered,
+    /// so this just hands back the body; kept as a method so call sites
+    /// read naturally and future variable substitution has a home.
+    pub fn render(&self) -> String {
+        self.body.clone()
+    }
+}
+
+fn hash_content(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let digest = hasher.finalize();
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_body_produces_same_version() {
+        let a = PromptTemplate::new("system", "hello");
+        let b = PromptTemplate::new("system", "hello");
+        assert_eq!(a.version, b.version);
+    }
+
+    #[test]
+    fn different_body_produces_different_version() {
+        let a = PromptTemplate::new("system", "hello");
+        let b = PromptTemplate::new("system", "goodbye");
+        assert_ne!(a.version, b.version);
+    }
+}
@@ -0,0 +1,195 @@
+//! User identities and roles for a shared (TCP/HTTP) daemon. A
+//! single-operator local daemon has no need for this — every IPC call is
+//! implicitly trusted — but once a daemon is reachable by a team, each
+//! call needs an attributable caller and a minimum role, and approvals
+//! and cancellations need to land in [`crate::storage::AuditEntry`]
+//! rather than just happening.
+//!
+//! Roles are ordered `Viewer < Operator < Approver < Admin`; a method's
+//! [`required_role`] is the minimum role that may call it, so granting a
+//! higher role always implies every permission of the roles below it.
+
+use crate::domain::LoopStatus;
+use crate::ipc::Methods;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    Viewer,
+    Operator,
+    Approver,
+    Admin,
+}
+
+/// An authenticated caller of the daemon's IPC methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+    pub role: Role,
+}
+
+impl User {
+    pub fn new(name: impl Into<String>, role: Role) -> Self {
+        Self { id: Uuid::new_v4(), name: name.into(), role }
+    }
+}
+
+/// Where the daemon looks up a caller's identity and role. A production
+/// deployment would add a config- or SSO-backed implementation behind
+/// this same trait, the way [`crate::storage::Storage`] has
+/// `InMemoryStorage` today and a durable backend later.
+pub trait UserStore: Send + Sync {
+    fn find(&self, id: Uuid) -> anyhow::Result<Option<User>>;
+}
+
+/// The daemon refused a call because the caller's role was below the
+/// method's [`required_role`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessDenied {
+    pub method: &'static str,
+    pub required: Role,
+    pub actual: Role,
+}
+
+impl std::fmt::Display for AccessDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} requires {:?}, caller has {:?}", self.method, self.required, self.actual)
+    }
+}
+
+impl std::error::Error for AccessDenied {}
+
+/// The minimum [`Role`] allowed to call `method`. Read-only methods need
+/// only `Viewer`; methods that change scheduling or loop state need
+/// `Operator`; approving a gated loop or clearing a spending cap needs
+/// `Approver`; pausing the whole daemon or bulk-deleting needs `Admin`.
+/// Unrecognized methods default to `Admin` — an unknown call is refused
+/// rather than silently allowed.
+pub fn required_role(method: &str) -> Role {
+    match method {
+        Methods::LOOP_GET
+        | Methods::LOOP_LIST
+        | Methods::METRICS_GET
+        | Methods::ARTIFACT_HISTORY
+        | Methods::ARTIFACT_DIFF
+        | Methods::USAGE_REPORT
+        | Methods::STATUS_SNAPSHOT
+        | Methods::HEALTH_CHECK
+        | Methods::CHAT_SESSION_LIST
+        | Methods::LOOP_CHANGELOG
+        | Methods::LOOP_FEEDBACK => Role::Viewer,
+
+        Methods::PLAN_CREATE
+        | Methods::SPEC_CREATE
+        | Methods::PHASE_CREATE
+        | Methods::RALPH_CREATE
+        | Methods::LOOP_RESPAWN
+        | Methods::LOOP_SET_PRIORITY
+        | Methods::CHAT_SEND
+        | Methods::CHAT_SET_PARAMS
+        | Methods::CHAT_COMPACT
+        | Methods::CHAT_SESSION_CREATE
+        | Methods::CHAT_SESSION_RENAME
+        | Methods::CHAT_SESSION_DELETE
+        | Methods::LOOP_ADD_GUIDANCE
+        | Methods::LOOP_PIN_FILE
+        | Methods::LOOP_UPDATE
+        | Methods::LOOP_CLONE
+        | Methods::LOOP_ADOPT
+        | Methods::LOOP_CHECKPOINT
+        | Methods::LOOP_ROLLBACK => Role::Operator,
+
+        Methods::BUDGET_OVERRIDE
+        | Methods::LOOP_APPROVE
+        | Methods::LOOP_REJECT
+        | Methods::LOOP_ITERATE
+        | Methods::LOOP_SKIP => Role::Approver,
+
+        Methods::LOOP_DELETE | Methods::LOOP_BULK_ACTION | Methods::SCHEDULER_PAUSE | Methods::SCHEDULER_RESUME => Role::Admin,
+
+        _ => Role::Admin,
+    }
+}
+
+/// Checks `user` against `method`'s [`required_role`], erroring with
+/// [`AccessDenied`] rather than panicking so the daemon can turn the
+/// refusal into an IPC error response.
+pub fn authorize(user: &User, method: &'static str) -> Result<(), AccessDenied> {
+    let required = required_role(method);
+    if user.role >= required {
+        Ok(())
+    } else {
+        Err(AccessDenied { method, required, actual: user.role })
+    }
+}
+
+/// Whether `status` is an approval gate that only an `Approver` (or
+/// above) may clear; used by the daemon to require the stronger role
+/// specifically for `loop.set_priority`-style calls that move a loop out
+/// of [`LoopStatus::AwaitingApproval`], rather than for every operator
+/// action.
+pub fn requires_approval(status: LoopStatus) -> bool {
+    status == LoopStatus::AwaitingApproval
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_order_viewer_below_admin() {
+        assert!(Role::Viewer < Role::Operator);
+        assert!(Role::Operator < Role::Approver);
+        assert!(Role::Approver < Role::Admin);
+    }
+
+    #[test]
+    fn a_viewer_may_read_but_not_mutate() {
+        let viewer = User::new("ro", Role::Viewer);
+        assert!(authorize(&viewer, Methods::LOOP_LIST).is_ok());
+        assert!(authorize(&viewer, Methods::PLAN_CREATE).is_err());
+    }
+
+    #[test]
+    fn an_operator_may_create_but_not_delete() {
+        let operator = User::new("op", Role::Operator);
+        assert!(authorize(&operator, Methods::PLAN_CREATE).is_ok());
+        assert!(authorize(&operator, Methods::LOOP_DELETE).is_err());
+    }
+
+    #[test]
+    fn an_admin_may_call_every_method() {
+        let admin = User::new("root", Role::Admin);
+        assert!(authorize(&admin, Methods::LOOP_DELETE).is_ok());
+        assert!(authorize(&admin, Methods::BUDGET_OVERRIDE).is_ok());
+        assert!(authorize(&admin, Methods::LOOP_LIST).is_ok());
+    }
+
+    #[test]
+    fn access_denied_reports_the_required_and_actual_role() {
+        let viewer = User::new("ro", Role::Viewer);
+        let err = authorize(&viewer, Methods::LOOP_DELETE).unwrap_err();
+        assert_eq!(err.required, Role::Admin);
+        assert_eq!(err.actual, Role::Viewer);
+    }
+
+    #[test]
+    fn approving_or_rejecting_a_loop_needs_an_approver() {
+        let operator = User::new("op", Role::Operator);
+        assert!(authorize(&operator, Methods::LOOP_APPROVE).is_err());
+        assert!(authorize(&operator, Methods::LOOP_REJECT).is_err());
+
+        let approver = User::new("ap", Role::Approver);
+        assert!(authorize(&approver, Methods::LOOP_APPROVE).is_ok());
+        assert!(authorize(&approver, Methods::LOOP_REJECT).is_ok());
+        assert!(authorize(&approver, Methods::LOOP_ITERATE).is_ok());
+        assert!(authorize(&approver, Methods::LOOP_SKIP).is_ok());
+    }
+
+    #[test]
+    fn only_awaiting_approval_requires_an_approver() {
+        assert!(requires_approval(LoopStatus::AwaitingApproval));
+        assert!(!requires_approval(LoopStatus::Running));
+    }
+}
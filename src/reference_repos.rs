@@ -0,0 +1,36 @@
+//! Read-only reference repos a project can declare in `loopr.yml`'s
+//! `reference_repos:` section (e.g. a shared proto or API-contract repo),
+//! so read/grep/glob tools can consult them while write tools stay
+//! restricted to the loop's worktree; see
+//! [`crate::tools::ToolContext::with_extra_read_only_roots`].
+
+use std::path::PathBuf;
+
+/// One reference repo: a display name (for diagnostics) and the path it's
+/// checked out at.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceRepo {
+    pub name: String,
+    pub path: String,
+}
+
+/// The configured repos' paths, in declared order, ready to hand to
+/// [`crate::tools::ToolContext::with_extra_read_only_roots`].
+pub fn roots(repos: &[ReferenceRepo]) -> Vec<PathBuf> {
+    repos.iter().map(|repo| PathBuf::from(&repo.path)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roots_preserves_declared_order() {
+        let repos = vec![
+            ReferenceRepo { name: "proto".to_string(), path: "/repos/proto".to_string() },
+            ReferenceRepo { name: "contracts".to_string(), path: "/repos/contracts".to_string() },
+        ];
+        assert_eq!(roots(&repos), vec![PathBuf::from("/repos/proto"), PathBuf::from("/repos/contracts")]);
+    }
+}
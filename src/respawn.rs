@@ -0,0 +1,184 @@
+//! Re-parsing a loop's stored artifact and spawning any children it calls
+//! for that weren't already created, for when a parser fix catches a
+//! spec/phase missed by format drift the first time around.
+
+use crate::artifact::{parse_artifact, ArtifactFormat, Plan};
+use crate::domain::{LoopRecord, LoopType};
+use crate::estimate::{estimate_plan, historical_averages};
+use crate::storage::Storage;
+use uuid::Uuid;
+
+/// The child loops `plan` calls for that aren't already present among
+/// `existing`, matched by description so a loop already spawned for an
+/// unchanged spec/phase isn't duplicated. Newly spawned specs are
+/// consulted immediately so their phases can be spawned in the same pass.
+pub fn spawn_children_from_artifact(plan: &Plan, plan_id: Uuid, existing: &[LoopRecord]) -> Vec<LoopRecord> {
+    let mut spawned = Vec::new();
+    for spec in &plan.specs {
+        let spec_id = match existing.iter().chain(spawned.iter()).find(|child| child.parent_id == Some(plan_id) && child.description == spec.name) {
+            Some(found) => found.id,
+            None => {
+                let record = LoopRecord::new(LoopType::Spec, Some(plan_id), spec.name.clone());
+                let spec_id = record.id;
+                spawned.push(record);
+                spec_id
+            }
+        };
+        for phase in &spec.phases {
+            let already = existing.iter().chain(spawned.iter()).any(|child| child.parent_id == Some(spec_id) && child.description == phase.name);
+            if !already {
+                spawned.push(LoopRecord::new(LoopType::Phase, Some(spec_id), phase.name.clone()));
+            }
+        }
+    }
+    spawned
+}
+
+/// Re-parses `loop_id`'s latest recorded artifact with the current parser
+/// and persists any children `spawn_children_from_artifact` finds that
+/// weren't already among its descendants. Also refreshes `loop_id`'s own
+/// [`crate::estimate::PlanEstimate`] against the current historical
+/// averages, so an operator reviewing the approval queue or `loopr tree`
+/// sees a prediction sized from the fully-parsed plan rather than the
+/// rough draft estimate shown at `/plan` time.
+pub fn respawn(storage: &dyn Storage, loop_id: Uuid, format: ArtifactFormat) -> anyhow::Result<Vec<LoopRecord>> {
+    let artifact_history = storage.artifact_history(loop_id)?;
+    let latest = artifact_history.last().ok_or_else(|| anyhow::anyhow!("loop {loop_id} has no recorded artifact to re-parse"))?;
+    let plan = parse_artifact(format, &latest.content)?;
+    let existing = storage.list_loops()?;
+    let spawned = spawn_children_from_artifact(&plan, loop_id, &existing);
+    for record in &spawned {
+        storage.save_loop(record.clone())?;
+    }
+
+    if let Some(mut record) = storage.get_loop(loop_id)? {
+        let averages = historical_averages(storage)?;
+        record = record.with_estimate(estimate_plan(&plan, &averages));
+        storage.save_loop(record)?;
+    }
+
+    Ok(spawned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::InMemoryStorage;
+
+    fn plan() -> Plan {
+        parse_artifact(
+            ArtifactFormat::Markdown,
+            "\
+# Add login flow
+
+## Spec: auth
+
+Handle the backend auth work.
+
+### Phase: session tokens
+
+Issue and verify JWTs.
+
+### Phase: rate limiting
+
+Throttle repeated attempts.
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn spawns_every_spec_and_phase_when_nothing_exists_yet() {
+        let plan_id = Uuid::new_v4();
+        let spawned = spawn_children_from_artifact(&plan(), plan_id, &[]);
+        assert_eq!(spawned.iter().filter(|r| r.loop_type == LoopType::Spec).count(), 1);
+        assert_eq!(spawned.iter().filter(|r| r.loop_type == LoopType::Phase).count(), 2);
+    }
+
+    #[test]
+    fn does_not_duplicate_a_spec_that_already_exists() {
+        let plan_id = Uuid::new_v4();
+        let existing_spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "Spec: auth".to_string());
+        let spawned = spawn_children_from_artifact(&plan(), plan_id, &[existing_spec]);
+        assert!(spawned.iter().all(|r| r.loop_type != LoopType::Spec));
+        assert_eq!(spawned.iter().filter(|r| r.loop_type == LoopType::Phase).count(), 2);
+    }
+
+    #[test]
+    fn only_spawns_the_phase_missed_by_format_drift() {
+        let plan_id = Uuid::new_v4();
+        let existing_spec = LoopRecord::new(LoopType::Spec, Some(plan_id), "Spec: auth".to_string());
+        let existing_phase = LoopRecord::new(LoopType::Phase, Some(existing_spec.id), "Phase: session tokens".to_string());
+        let spawned = spawn_children_from_artifact(&plan(), plan_id, &[existing_spec, existing_phase]);
+        assert_eq!(spawned.len(), 1);
+        assert_eq!(spawned[0].description, "Phase: rate limiting");
+    }
+
+    #[test]
+    fn respawn_persists_the_newly_spawned_children() {
+        let storage = InMemoryStorage::new();
+        let plan_record = LoopRecord::new(LoopType::Plan, None, "add login flow");
+        let plan_id = plan_record.id;
+        storage.save_loop(plan_record).unwrap();
+        storage
+            .save_artifact_version(
+                plan_id,
+                0,
+                "\
+# Add login flow
+
+## Spec: auth
+
+Handle the backend auth work.
+"
+                .to_string(),
+            )
+            .unwrap();
+
+        let spawned = respawn(&storage, plan_id, ArtifactFormat::Markdown).unwrap();
+        assert_eq!(spawned.len(), 1);
+        assert!(storage.get_loop(spawned[0].id).unwrap().is_some());
+
+        let again = respawn(&storage, plan_id, ArtifactFormat::Markdown).unwrap();
+        assert!(again.is_empty());
+    }
+
+    #[test]
+    fn respawn_attaches_an_estimate_to_the_plan_record() {
+        let storage = InMemoryStorage::new();
+        let plan_record = LoopRecord::new(LoopType::Plan, None, "add login flow");
+        let plan_id = plan_record.id;
+        storage.save_loop(plan_record).unwrap();
+        storage
+            .save_artifact_version(
+                plan_id,
+                0,
+                "\
+# Add login flow
+
+## Spec: auth
+
+Handle the backend auth work.
+
+### Phase: session tokens
+
+Issue and verify JWTs.
+"
+                .to_string(),
+            )
+            .unwrap();
+
+        respawn(&storage, plan_id, ArtifactFormat::Markdown).unwrap();
+
+        let estimate = storage.get_loop(plan_id).unwrap().unwrap().estimate.unwrap();
+        assert_eq!(estimate.phase_count, 1);
+        assert!(estimate.predicted_iterations > 0.0);
+    }
+
+    #[test]
+    fn refuses_to_respawn_a_loop_with_no_recorded_artifact() {
+        let storage = InMemoryStorage::new();
+        let err = respawn(&storage, Uuid::new_v4(), ArtifactFormat::Markdown).unwrap_err();
+        assert!(err.to_string().contains("no recorded artifact"));
+    }
+}
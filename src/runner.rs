@@ -0,0 +1,101 @@
+//! Runner lane classification: heavy, network-capable, and
+//! network-isolated tool work is classified into the lane
+//! (`runner-no-net`, `runner-net`, `runner-heavy`) it would run in if
+//! dispatched to a separate runner subprocess pool, so that a future
+//! subprocess split can give each lane its own resource limits (cgroups,
+//! nice) and crash isolation from the daemon and the other lanes.
+//!
+//! [`classify`] is wired into [`crate::storage::ToolJobRecord`] today, so
+//! every recorded tool job carries its lane. No subprocess pool, IPC
+//! send, or resource limit exists yet — every lane still runs in-process
+//! via [`crate::tools::ToolExecutor`]. [`RunnerDispatch`] is the payload
+//! shape a real dispatcher would send once one exists.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Which runner subprocess pool a tool job belongs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunnerLane {
+    NoNet,
+    Net,
+    Heavy,
+}
+
+impl RunnerLane {
+    /// Name of the runner subprocess binary responsible for this lane.
+    pub fn binary_name(&self) -> &'static str {
+        match self {
+            RunnerLane::NoNet => "runner-no-net",
+            RunnerLane::Net => "runner-net",
+            RunnerLane::Heavy => "runner-heavy",
+        }
+    }
+}
+
+const NETWORK_KEYWORDS: &[&str] = &["curl", "wget", "git clone", "git fetch", "git pull", "cargo add", "npm install"];
+const HEAVY_KEYWORDS: &[&str] = &["cargo build", "cargo bench", "cargo test", "docker build"];
+
+/// Classifies a command into the lane it should run in. Network access
+/// wins over "heavy" when a command needs both, since isolating network
+/// access is the stricter security property.
+pub fn classify(command: &str) -> RunnerLane {
+    if NETWORK_KEYWORDS.iter().any(|keyword| command.contains(keyword)) {
+        RunnerLane::Net
+    } else if HEAVY_KEYWORDS.iter().any(|keyword| command.contains(keyword)) {
+        RunnerLane::Heavy
+    } else {
+        RunnerLane::NoNet
+    }
+}
+
+/// One tool job routed to a lane, ready to send over IPC to that lane's
+/// runner pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunnerDispatch {
+    pub lane: RunnerLane,
+    pub loop_id: Uuid,
+    pub command: String,
+}
+
+pub fn dispatch(loop_id: Uuid, command: &str) -> RunnerDispatch {
+    RunnerDispatch {
+        lane: classify(command),
+        loop_id,
+        command: command.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_commands_route_to_the_net_lane() {
+        assert_eq!(classify("curl https://example.com"), RunnerLane::Net);
+    }
+
+    #[test]
+    fn heavy_commands_route_to_the_heavy_lane() {
+        assert_eq!(classify("cargo build --release"), RunnerLane::Heavy);
+    }
+
+    #[test]
+    fn ordinary_commands_route_to_no_net() {
+        assert_eq!(classify("ls -la"), RunnerLane::NoNet);
+    }
+
+    #[test]
+    fn network_classification_wins_over_heavy() {
+        assert_eq!(classify("cargo add serde && cargo build"), RunnerLane::Net);
+    }
+
+    #[test]
+    fn dispatch_carries_the_loop_id_and_command() {
+        let loop_id = Uuid::new_v4();
+        let job = dispatch(loop_id, "cargo test");
+        assert_eq!(job.loop_id, loop_id);
+        assert_eq!(job.lane, RunnerLane::Heavy);
+    }
+}
@@ -0,0 +1,121 @@
+//! Scheduler-wide pause (maintenance mode): an operator can stop new
+//! iterations from starting without killing work already in flight, for
+//! rate-limit incidents or repo maintenance windows.
+
+use crate::chaos::ChaosInjector;
+use chrono::{DateTime, Utc};
+
+/// Whether the scheduler is accepting new iteration starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulerState {
+    Running,
+    Paused,
+}
+
+/// The scheduler's pause state, plus when it last changed so `daemon
+/// status` can show how long maintenance mode has been in effect.
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerControl {
+    pub state: SchedulerState,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl SchedulerControl {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { state: SchedulerState::Running, changed_at: now }
+    }
+
+    /// Stops new iterations from starting; iterations already running are
+    /// unaffected and finish normally.
+    pub fn pause(&mut self, now: DateTime<Utc>) {
+        self.state = SchedulerState::Paused;
+        self.changed_at = now;
+    }
+
+    /// Resumes starting new iterations.
+    pub fn resume(&mut self, now: DateTime<Utc>) {
+        self.state = SchedulerState::Running;
+        self.changed_at = now;
+    }
+
+    /// Whether the scheduler may start a new iteration right now.
+    pub fn may_start_iteration(&self) -> bool {
+        self.state == SchedulerState::Running
+    }
+
+    /// Rolls `injector` for a simulated daemon restart and, if it fires,
+    /// pauses the scheduler exactly as a real restart would leave it
+    /// until the daemon comes back up and an operator (or its own
+    /// startup routine) resumes it. Returns whether a restart was
+    /// injected.
+    pub fn maybe_chaos_restart(&mut self, injector: &mut ChaosInjector, now: DateTime<Utc>) -> bool {
+        if injector.maybe_daemon_restart().is_some() {
+            self.pause(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn starts_out_running() {
+        let control = SchedulerControl::new(Utc::now());
+        assert_eq!(control.state, SchedulerState::Running);
+        assert!(control.may_start_iteration());
+    }
+
+    #[test]
+    fn pause_stops_new_iterations_from_starting() {
+        let mut control = SchedulerControl::new(Utc::now());
+        control.pause(Utc::now());
+        assert!(!control.may_start_iteration());
+    }
+
+    #[test]
+    fn resume_allows_new_iterations_again() {
+        let mut control = SchedulerControl::new(Utc::now());
+        control.pause(Utc::now());
+        control.resume(Utc::now());
+        assert!(control.may_start_iteration());
+    }
+
+    #[test]
+    fn a_chaos_restart_pauses_the_scheduler() {
+        use crate::chaos::ChaosConfig;
+
+        let mut control = SchedulerControl::new(Utc::now());
+        let config = ChaosConfig { enabled: true, daemon_restart_rate: 1.0, ..ChaosConfig::default() };
+        let mut injector = ChaosInjector::new(config, 1);
+
+        let restarted = control.maybe_chaos_restart(&mut injector, Utc::now());
+        assert!(restarted);
+        assert!(!control.may_start_iteration());
+    }
+
+    #[test]
+    fn no_chaos_restart_leaves_the_scheduler_running() {
+        use crate::chaos::ChaosConfig;
+
+        let mut control = SchedulerControl::new(Utc::now());
+        let mut injector = ChaosInjector::new(ChaosConfig::default(), 1);
+
+        let restarted = control.maybe_chaos_restart(&mut injector, Utc::now());
+        assert!(!restarted);
+        assert!(control.may_start_iteration());
+    }
+
+    #[test]
+    fn changed_at_tracks_the_most_recent_transition() {
+        let start = Utc::now();
+        let mut control = SchedulerControl::new(start);
+        let paused_at = start + Duration::minutes(5);
+        control.pause(paused_at);
+        assert_eq!(control.changed_at, paused_at);
+    }
+}
@@ -0,0 +1,86 @@
+//! Self-review pass: before the expensive validation gates run, show the
+//! LLM the diff its own iteration produced and give it one cheap chance to
+//! critique and fix it, rather than paying for a full gate run on an
+//! obvious mistake.
+
+use crate::prompts::estimate_tokens;
+
+/// Per-loop-type self-review settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SelfReviewConfig {
+    pub enabled: bool,
+    pub token_budget: usize,
+}
+
+impl Default for SelfReviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            token_budget: 1500,
+        }
+    }
+}
+
+/// A review pass either finds nothing worth fixing, or returns a revised
+/// diff to apply in place of the original.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelfReviewOutcome {
+    NoChanges,
+    Revised(String),
+}
+
+/// Builds the critique prompt, truncating the diff to fit the configured
+/// token budget. A diff that doesn't fit at all still gets reviewed against
+/// its head, since a partial self-review beats skipping it entirely.
+pub fn build_self_review_prompt(diff: &str, config: &SelfReviewConfig) -> String {
+    let truncated = truncate_to_budget(diff, config.token_budget);
+    format!(
+        "Review the diff below for mistakes before it goes to validation. If it's correct, reply exactly \
+         \"NO_CHANGES\". Otherwise reply with the corrected diff.\n\n{truncated}"
+    )
+}
+
+fn truncate_to_budget(diff: &str, token_budget: usize) -> &str {
+    if estimate_tokens(diff) <= token_budget {
+        return diff;
+    }
+    let byte_budget = token_budget * 4;
+    let mut end = byte_budget.min(diff.len());
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    &diff[..end]
+}
+
+/// Interprets the LLM's review response.
+pub fn parse_self_review_response(response: &str) -> SelfReviewOutcome {
+    if response.trim() == "NO_CHANGES" {
+        SelfReviewOutcome::NoChanges
+    } else {
+        SelfReviewOutcome::Revised(response.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_oversized_diffs_to_the_token_budget() {
+        let diff = "x".repeat(10_000);
+        let config = SelfReviewConfig { enabled: true, token_budget: 100 };
+        let prompt = build_self_review_prompt(&diff, &config);
+        assert!(prompt.len() < diff.len());
+    }
+
+    #[test]
+    fn no_changes_response_parses_to_no_changes() {
+        assert_eq!(parse_self_review_response("NO_CHANGES"), SelfReviewOutcome::NoChanges);
+    }
+
+    #[test]
+    fn other_response_parses_to_revised_diff() {
+        let outcome = parse_self_review_response("--- a\n+++ b\n");
+        assert_eq!(outcome, SelfReviewOutcome::Revised("--- a\n+++ b\n".to_string()));
+    }
+}
@@ -0,0 +1,147 @@
+//! Simulation mode: previews the loop tree and cost a plan would produce
+//! without spawning real loops, writing to the real worktree, or calling
+//! a real model. Tool writes are redirected into a throwaway copy of the
+//! worktree (see [`shadow_worktree`]) and validation gates are stubbed
+//! to a pass rather than actually executed (see [`stub_gate_result`]).
+
+use crate::artifact::Plan;
+use crate::domain::LoopType;
+use crate::estimate::{estimate_plan, HistoricalAverages, PlanEstimate};
+use crate::validation::GateResult;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// One node of the would-be loop tree a simulation produces. Mirrors the
+/// Plan -> Spec -> Phase shape of [`Plan`] without creating any
+/// [`crate::domain::LoopRecord`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedNode {
+    pub loop_type: LoopType,
+    pub name: String,
+    pub children: Vec<SimulatedNode>,
+}
+
+/// The result of simulating a plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub tree: SimulatedNode,
+    pub estimate: PlanEstimate,
+    pub shadow_dir: PathBuf,
+}
+
+/// Builds the would-be Plan -> Spec -> Phase tree for `plan` and sizes it
+/// against `history`, recording `shadow_dir` as where any tool writes
+/// during the simulation actually landed.
+pub fn simulate_plan(plan: &Plan, history: &HistoricalAverages, shadow_dir: impl Into<PathBuf>) -> SimulationResult {
+    let tree = SimulatedNode {
+        loop_type: LoopType::Plan,
+        name: plan.title.clone(),
+        children: plan
+            .specs
+            .iter()
+            .map(|spec| SimulatedNode {
+                loop_type: LoopType::Spec,
+                name: spec.name.clone(),
+                children: spec
+                    .phases
+                    .iter()
+                    .map(|phase| SimulatedNode {
+                        loop_type: LoopType::Phase,
+                        name: phase.name.clone(),
+                        children: Vec::new(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    SimulationResult {
+        tree,
+        estimate: estimate_plan(plan, history),
+        shadow_dir: shadow_dir.into(),
+    }
+}
+
+/// Copies `worktree` into a fresh temp directory so a simulation's tool
+/// writes never touch the real repo, and returns the copy's path.
+pub fn shadow_worktree(worktree: &Path) -> std::io::Result<PathBuf> {
+    let shadow = std::env::temp_dir().join(format!("loopr-simulate-{}", Uuid::new_v4()));
+    copy_dir_recursive(worktree, &shadow)?;
+    Ok(shadow)
+}
+
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &dst_path)?;
+        } else {
+            std::fs::copy(entry.path(), &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// A validation gate result that always passes, standing in for a real
+/// [`crate::validation::CommandGate::run`] during simulation so a
+/// `--simulate` preview never actually runs project commands.
+pub fn stub_gate_result(name: impl Into<String>) -> GateResult {
+    GateResult {
+        name: name.into(),
+        passed: true,
+        output: "skipped: simulation mode stubs validation gates".to_string(),
+        duration_ms: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::{Phase, Spec};
+
+    fn sample_plan() -> Plan {
+        Plan {
+            title: "add login".into(),
+            specs: vec![Spec {
+                name: "auth spec".into(),
+                description: String::new(),
+                phases: vec![Phase { name: "handler".into(), description: String::new() }],
+            }],
+        }
+    }
+
+    #[test]
+    fn builds_a_tree_mirroring_the_plan_shape() {
+        let result = simulate_plan(&sample_plan(), &HistoricalAverages::default(), "/tmp/shadow");
+        assert_eq!(result.tree.loop_type, LoopType::Plan);
+        assert_eq!(result.tree.children.len(), 1);
+        assert_eq!(result.tree.children[0].loop_type, LoopType::Spec);
+        assert_eq!(result.tree.children[0].children[0].name, "handler");
+    }
+
+    #[test]
+    fn estimate_matches_a_direct_call_to_estimate_plan() {
+        let plan = sample_plan();
+        let history = HistoricalAverages::default();
+        let result = simulate_plan(&plan, &history, "/tmp/shadow");
+        assert_eq!(result.estimate, estimate_plan(&plan, &history));
+    }
+
+    #[test]
+    fn shadow_worktree_copies_files_without_touching_the_source() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("main.rs"), "fn main() {}").unwrap();
+        let shadow = shadow_worktree(source.path()).unwrap();
+        assert!(shadow.join("main.rs").exists());
+        assert_ne!(shadow, source.path());
+        std::fs::remove_dir_all(shadow).unwrap();
+    }
+
+    #[test]
+    fn stub_gate_result_always_passes() {
+        let result = stub_gate_result("cargo test");
+        assert!(result.passed);
+        assert_eq!(result.name, "cargo test");
+    }
+}
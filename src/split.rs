@@ -0,0 +1,121 @@
+//! Automatic phase splitting: when a Ralph loop exhausts its iteration
+//! budget making large diffs that keep failing, the parent Phase is
+//! asked to re-decompose the work into smaller phases using the failed
+//! loop's history as input, replacing it with the new phases instead of
+//! retrying the same oversized scope forever.
+
+use crate::domain::{FailureCategory, LoopRecord, LoopStatus, LoopType};
+
+/// Whether a Ralph loop's iteration history looks like a scope problem
+/// rather than a fixable bug: it ran to (or past) `max_iterations`
+/// without a single iteration's diff staying under `max_lines_changed`,
+/// and at least `persistent_failure_ratio` of its iterations still
+/// failed.
+pub fn should_split(ralph: &LoopRecord, max_iterations: usize, max_lines_changed: usize, persistent_failure_ratio: f64) -> bool {
+    if ralph.loop_type != LoopType::Ralph || ralph.iterations.len() < max_iterations {
+        return false;
+    }
+    let every_diff_is_large = ralph
+        .iterations
+        .iter()
+        .all(|iteration| iteration.diff_summary.as_ref().is_some_and(|summary| summary.lines_added + summary.lines_removed > max_lines_changed));
+    let failing = ralph.iterations.iter().filter(|iteration| iteration.failure_category.is_some()).count();
+    let failure_ratio = failing as f64 / ralph.iterations.len() as f64;
+    every_diff_is_large && failure_ratio >= persistent_failure_ratio
+}
+
+/// Builds the re-planning prompt for the parent Phase: the original
+/// phase description plus every distinct failure category the exhausted
+/// Ralph loop hit, so the split can specifically carve out the part that
+/// kept failing.
+pub fn split_prompt(phase_description: &str, ralph: &LoopRecord) -> String {
+    let mut categories: Vec<FailureCategory> = Vec::new();
+    for category in ralph.iterations.iter().filter_map(|iteration| iteration.failure_category) {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    let categories_list = categories.iter().map(|category| format!("{category:?}")).collect::<Vec<_>>().join(", ");
+    format!(
+        "The phase \"{phase_description}\" exhausted its iteration budget without completing, \
+         repeatedly hitting: {categories_list}. Split it into smaller phases that each address a \
+         narrower slice of the work, using that failure history to decide where to cut."
+    )
+}
+
+/// Marks `failed_phase` [`LoopStatus::Invalidated`] rather than deleting
+/// it, so its history stays available for a post-mortem, and builds the
+/// new, smaller phase records to replace it with, parented under the
+/// same Spec.
+pub fn split_into_phases(failed_phase: &LoopRecord, phase_names: &[String]) -> (LoopRecord, Vec<LoopRecord>) {
+    let mut invalidated = failed_phase.clone();
+    invalidated.status = LoopStatus::Invalidated;
+    let replacements = phase_names.iter().map(|name| LoopRecord::new(LoopType::Phase, failed_phase.parent_id, name.clone())).collect();
+    (invalidated, replacements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_summary::DiffSummary;
+    use crate::domain::Iteration;
+    use uuid::Uuid;
+
+    fn large_failing_iteration(index: u32) -> Iteration {
+        let mut iteration = Iteration::new(index);
+        iteration.failure_category = Some(FailureCategory::TestAssertion);
+        iteration.diff_summary = Some(DiffSummary { lines_added: 400, lines_removed: 50, ..Default::default() });
+        iteration
+    }
+
+    fn ralph_loop(iterations: Vec<Iteration>) -> LoopRecord {
+        let mut record = LoopRecord::new(LoopType::Ralph, Some(Uuid::new_v4()), "implement the retry budget");
+        record.iterations = iterations;
+        record
+    }
+
+    #[test]
+    fn flags_a_ralph_loop_with_large_diffs_and_persistent_failures() {
+        let ralph = ralph_loop((0..4).map(large_failing_iteration).collect());
+        assert!(should_split(&ralph, 4, 200, 0.75));
+    }
+
+    #[test]
+    fn does_not_flag_a_loop_below_the_iteration_floor() {
+        let ralph = ralph_loop((0..2).map(large_failing_iteration).collect());
+        assert!(!should_split(&ralph, 4, 200, 0.75));
+    }
+
+    #[test]
+    fn does_not_flag_a_loop_whose_diffs_are_not_consistently_large() {
+        let mut iterations: Vec<Iteration> = (0..4).map(large_failing_iteration).collect();
+        iterations[0].diff_summary = Some(DiffSummary { lines_added: 10, lines_removed: 0, ..Default::default() });
+        let ralph = ralph_loop(iterations);
+        assert!(!should_split(&ralph, 4, 200, 0.75));
+    }
+
+    #[test]
+    fn only_applies_to_ralph_loops() {
+        let mut phase = ralph_loop((0..4).map(large_failing_iteration).collect());
+        phase.loop_type = LoopType::Phase;
+        assert!(!should_split(&phase, 4, 200, 0.75));
+    }
+
+    #[test]
+    fn split_prompt_lists_distinct_failure_categories() {
+        let ralph = ralph_loop(vec![large_failing_iteration(0), large_failing_iteration(1)]);
+        let prompt = split_prompt("implement the retry budget", &ralph);
+        assert!(prompt.contains("TestAssertion"));
+        assert!(prompt.contains("implement the retry budget"));
+    }
+
+    #[test]
+    fn splitting_invalidates_the_old_phase_and_parents_new_ones_under_the_same_spec() {
+        let spec_id = Uuid::new_v4();
+        let failed_phase = LoopRecord::new(LoopType::Phase, Some(spec_id), "add retry logic");
+        let (invalidated, replacements) = split_into_phases(&failed_phase, &["retry budget".to_string(), "retry backoff".to_string()]);
+        assert_eq!(invalidated.status, LoopStatus::Invalidated);
+        assert_eq!(replacements.len(), 2);
+        assert!(replacements.iter().all(|phase| phase.parent_id == Some(spec_id)));
+    }
+}
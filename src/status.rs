@@ -0,0 +1,154 @@
+//! A consolidated status snapshot for the TUI's segmented status bar:
+//! daemon connection state, loop counts, rate-limit backoff, session
+//! cost, and disk quota usage in one `status.snapshot` call instead of
+//! several round trips the bar would otherwise have to poll separately.
+
+use crate::domain::LoopStatus;
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The daemon connection as observed by a client, for the status bar's
+/// leftmost segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DaemonConnectionState {
+    Connected,
+    Reconnecting,
+    Disconnected,
+}
+
+/// The current provider rate-limit backoff, if one is active.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimitState {
+    pub backoff_until: Option<DateTime<Utc>>,
+}
+
+impl RateLimitState {
+    /// Seconds remaining on the backoff as of `now`, or `None` if it's
+    /// not active (never set, or already elapsed).
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        let remaining = (self.backoff_until? - now).num_seconds();
+        if remaining > 0 {
+            Some(remaining)
+        } else {
+            None
+        }
+    }
+}
+
+/// Disk usage against a configured quota, for the status bar's rightmost
+/// segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiskQuota {
+    pub used_bytes: u64,
+    pub limit_bytes: u64,
+}
+
+impl DiskQuota {
+    pub fn percent_used(&self) -> f32 {
+        if self.limit_bytes == 0 {
+            return 0.0;
+        }
+        (self.used_bytes as f32 / self.limit_bytes as f32) * 100.0
+    }
+}
+
+/// Everything the status bar needs to render in one pass, returned by the
+/// `status.snapshot` IPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    pub connection: DaemonConnectionState,
+    pub active_loops: usize,
+    pub queued_loops: usize,
+    pub rate_limit_backoff_secs: Option<i64>,
+    pub session_cost_usd: f64,
+    pub disk_quota: DiskQuota,
+}
+
+/// Assembles a [`StatusSnapshot`] from storage plus the live state a
+/// daemon process tracks in memory (connection, rate limiting, disk
+/// usage), none of which live in `Storage`.
+pub fn build_snapshot(
+    storage: &dyn Storage,
+    connection: DaemonConnectionState,
+    rate_limit: &RateLimitState,
+    disk_quota: DiskQuota,
+    now: DateTime<Utc>,
+) -> anyhow::Result<StatusSnapshot> {
+    let loops = storage.list_loops()?;
+    let active_loops = loops.iter().filter(|l| l.status == LoopStatus::Running).count();
+    let queued_loops = loops.iter().filter(|l| l.status == LoopStatus::Pending).count();
+    let session_cost_usd = loops.iter().flat_map(|l| &l.iterations).map(|it| it.cost_usd).sum();
+
+    Ok(StatusSnapshot {
+        connection,
+        active_loops,
+        queued_loops,
+        rate_limit_backoff_secs: rate_limit.remaining_secs(now),
+        session_cost_usd,
+        disk_quota,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use crate::storage::InMemoryStorage;
+    use chrono::Duration;
+
+    fn record_with_status(status: LoopStatus) -> LoopRecord {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.status = status;
+        record
+    }
+
+    #[test]
+    fn counts_active_and_queued_loops_separately() {
+        let storage = InMemoryStorage::new();
+        storage.save_loop(record_with_status(LoopStatus::Running)).unwrap();
+        storage.save_loop(record_with_status(LoopStatus::Running)).unwrap();
+        storage.save_loop(record_with_status(LoopStatus::Pending)).unwrap();
+        storage.save_loop(record_with_status(LoopStatus::Completed)).unwrap();
+
+        let snapshot = build_snapshot(&storage, DaemonConnectionState::Connected, &RateLimitState::default(), DiskQuota::default(), Utc::now()).unwrap();
+        assert_eq!(snapshot.active_loops, 2);
+        assert_eq!(snapshot.queued_loops, 1);
+    }
+
+    #[test]
+    fn sums_cost_across_every_iteration_of_every_loop() {
+        let storage = InMemoryStorage::new();
+        let mut record = record_with_status(LoopStatus::Completed);
+        let mut a = Iteration::new(0);
+        a.cost_usd = 1.5;
+        let mut b = Iteration::new(1);
+        b.cost_usd = 0.5;
+        record.iterations.push(a);
+        record.iterations.push(b);
+        storage.save_loop(record).unwrap();
+
+        let snapshot = build_snapshot(&storage, DaemonConnectionState::Connected, &RateLimitState::default(), DiskQuota::default(), Utc::now()).unwrap();
+        assert_eq!(snapshot.session_cost_usd, 2.0);
+    }
+
+    #[test]
+    fn an_active_backoff_reports_remaining_seconds() {
+        let now = Utc::now();
+        let rate_limit = RateLimitState { backoff_until: Some(now + Duration::seconds(30)) };
+        assert_eq!(rate_limit.remaining_secs(now), Some(30));
+    }
+
+    #[test]
+    fn an_elapsed_backoff_reports_none() {
+        let now = Utc::now();
+        let rate_limit = RateLimitState { backoff_until: Some(now - Duration::seconds(1)) };
+        assert_eq!(rate_limit.remaining_secs(now), None);
+    }
+
+    #[test]
+    fn disk_quota_reports_percent_used() {
+        let quota = DiskQuota { used_bytes: 50, limit_bytes: 200 };
+        assert_eq!(quota.percent_used(), 25.0);
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the artifact a loop produced at a given iteration,
+/// retained so reviewers can diff what changed between attempts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactVersion {
+    pub loop_id: Uuid,
+    pub iteration: u32,
+    pub content: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl ArtifactVersion {
+    pub fn new(loop_id: Uuid, iteration: u32, content: String) -> Self {
+        Self {
+            loop_id,
+            iteration,
+            content,
+            recorded_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// One attributed action against the daemon, recorded so a shared
+/// (TCP/HTTP) daemon can answer "who approved/cancelled this" instead of
+/// just "what happened"; see [`crate::rbac`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub method: String,
+    pub loop_id: Option<Uuid>,
+    pub allowed: bool,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(user_id: Uuid, user_name: impl Into<String>, method: impl Into<String>, loop_id: Option<Uuid>, allowed: bool) -> Self {
+        Self {
+            user_id,
+            user_name: user_name.into(),
+            method: method.into(),
+            loop_id,
+            allowed,
+            recorded_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_entry_records_the_method_and_outcome() {
+        let user_id = Uuid::new_v4();
+        let entry = AuditEntry::new(user_id, "ada", "loop.delete", Some(Uuid::new_v4()), false);
+        assert_eq!(entry.user_id, user_id);
+        assert_eq!(entry.method, "loop.delete");
+        assert!(!entry.allowed);
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use serde::{Deserialize, Serialize};
+
+/// A named chat session, persisted so the TUI can offer a session picker
+/// across daemon restarts. `id` doubles as the `conversation_id` stamped
+/// onto any loop created from this session, so loops can be traced back
+/// to the chat that spawned them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatSessionRecord {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChatSessionRecord {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            created_at: Utc::now(),
+        }
+    }
+}
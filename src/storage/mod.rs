@@ -0,0 +1,287 @@
+//! Persistence for loop records and artifact versions. The daemon runs
+//! against a real backend; tests and offline tooling run against
+//! [`InMemoryStorage`].
+
+mod artifact_versions;
+mod audit;
+mod chat_sessions;
+mod tool_jobs;
+mod transcript;
+
+pub use artifact_versions::ArtifactVersion;
+pub use audit::AuditEntry;
+pub use chat_sessions::ChatSessionRecord;
+pub use tool_jobs::ToolJobRecord;
+pub use transcript::TranscriptEntry;
+
+use crate::domain::LoopRecord;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+pub trait Storage: Send + Sync {
+    fn save_loop(&self, record: LoopRecord) -> anyhow::Result<()>;
+    fn get_loop(&self, id: Uuid) -> anyhow::Result<Option<LoopRecord>>;
+    fn list_loops(&self) -> anyhow::Result<Vec<LoopRecord>>;
+    /// Removes a loop record and all of its artifact versions, tool jobs,
+    /// and transcript entries. Returns whether a record was present.
+    fn delete_loop(&self, id: Uuid) -> anyhow::Result<bool>;
+
+    /// Records the artifact produced by one iteration of `loop_id`.
+    fn save_artifact_version(&self, loop_id: Uuid, iteration: u32, content: String) -> anyhow::Result<()>;
+    /// Every recorded artifact version for a loop, oldest first.
+    fn artifact_history(&self, loop_id: Uuid) -> anyhow::Result<Vec<ArtifactVersion>>;
+
+    /// Upserts a tool job by id, so the same record can be saved on start
+    /// and again on completion.
+    fn save_tool_job(&self, job: ToolJobRecord) -> anyhow::Result<()>;
+    /// Every tool job recorded for a loop, oldest first.
+    fn tool_jobs(&self, loop_id: Uuid) -> anyhow::Result<Vec<ToolJobRecord>>;
+
+    /// Appends one iteration's exact LLM request/response pair. Opt-in,
+    /// and never overwritten once recorded.
+    fn save_transcript_entry(&self, entry: TranscriptEntry) -> anyhow::Result<()>;
+    /// Every transcript entry recorded for a loop, oldest first.
+    fn transcript(&self, loop_id: Uuid) -> anyhow::Result<Vec<TranscriptEntry>>;
+
+    /// Upserts a chat session by id, so renaming saves over the same record.
+    fn save_chat_session(&self, session: ChatSessionRecord) -> anyhow::Result<()>;
+    /// Every chat session, oldest first, for the TUI's session picker.
+    fn list_chat_sessions(&self) -> anyhow::Result<Vec<ChatSessionRecord>>;
+    /// Removes a chat session. Returns whether a record was present.
+    fn delete_chat_session(&self, id: Uuid) -> anyhow::Result<bool>;
+
+    /// Appends one attributed action. Never overwritten once recorded.
+    fn save_audit_entry(&self, entry: AuditEntry) -> anyhow::Result<()>;
+    /// Every audit entry recorded for a loop, oldest first. `loop_id` of
+    /// `None` returns entries not tied to a specific loop (e.g. `loop.list`).
+    fn audit_log(&self, loop_id: Option<Uuid>) -> anyhow::Result<Vec<AuditEntry>>;
+}
+
+/// A `Storage` backed by an in-process map, used in tests and by tooling
+/// that doesn't need durability.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    loops: Mutex<HashMap<Uuid, LoopRecord>>,
+    artifact_versions: Mutex<HashMap<Uuid, Vec<ArtifactVersion>>>,
+    tool_jobs: Mutex<HashMap<Uuid, Vec<ToolJobRecord>>>,
+    transcripts: Mutex<HashMap<Uuid, Vec<TranscriptEntry>>>,
+    chat_sessions: Mutex<HashMap<Uuid, ChatSessionRecord>>,
+    audit_log: Mutex<Vec<AuditEntry>>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for InMemoryStorage {
+    fn save_loop(&self, record: LoopRecord) -> anyhow::Result<()> {
+        self.loops.lock().unwrap().insert(record.id, record);
+        Ok(())
+    }
+
+    fn get_loop(&self, id: Uuid) -> anyhow::Result<Option<LoopRecord>> {
+        Ok(self.loops.lock().unwrap().get(&id).cloned())
+    }
+
+    fn list_loops(&self) -> anyhow::Result<Vec<LoopRecord>> {
+        Ok(self.loops.lock().unwrap().values().cloned().collect())
+    }
+
+    fn delete_loop(&self, id: Uuid) -> anyhow::Result<bool> {
+        let present = self.loops.lock().unwrap().remove(&id).is_some();
+        self.artifact_versions.lock().unwrap().remove(&id);
+        self.tool_jobs.lock().unwrap().remove(&id);
+        self.transcripts.lock().unwrap().remove(&id);
+        Ok(present)
+    }
+
+    fn save_artifact_version(&self, loop_id: Uuid, iteration: u32, content: String) -> anyhow::Result<()> {
+        self.artifact_versions
+            .lock()
+            .unwrap()
+            .entry(loop_id)
+            .or_default()
+            .push(ArtifactVersion::new(loop_id, iteration, content));
+        Ok(())
+    }
+
+    fn artifact_history(&self, loop_id: Uuid) -> anyhow::Result<Vec<ArtifactVersion>> {
+        Ok(self.artifact_versions.lock().unwrap().get(&loop_id).cloned().unwrap_or_default())
+    }
+
+    fn save_tool_job(&self, job: ToolJobRecord) -> anyhow::Result<()> {
+        let mut tool_jobs = self.tool_jobs.lock().unwrap();
+        let jobs = tool_jobs.entry(job.loop_id).or_default();
+        match jobs.iter_mut().find(|existing| existing.id == job.id) {
+            Some(existing) => *existing = job,
+            None => jobs.push(job),
+        }
+        Ok(())
+    }
+
+    fn tool_jobs(&self, loop_id: Uuid) -> anyhow::Result<Vec<ToolJobRecord>> {
+        Ok(self.tool_jobs.lock().unwrap().get(&loop_id).cloned().unwrap_or_default())
+    }
+
+    fn save_transcript_entry(&self, entry: TranscriptEntry) -> anyhow::Result<()> {
+        self.transcripts.lock().unwrap().entry(entry.loop_id).or_default().push(entry);
+        Ok(())
+    }
+
+    fn transcript(&self, loop_id: Uuid) -> anyhow::Result<Vec<TranscriptEntry>> {
+        Ok(self.transcripts.lock().unwrap().get(&loop_id).cloned().unwrap_or_default())
+    }
+
+    fn save_chat_session(&self, session: ChatSessionRecord) -> anyhow::Result<()> {
+        self.chat_sessions.lock().unwrap().insert(session.id, session);
+        Ok(())
+    }
+
+    fn list_chat_sessions(&self) -> anyhow::Result<Vec<ChatSessionRecord>> {
+        let mut sessions: Vec<ChatSessionRecord> = self.chat_sessions.lock().unwrap().values().cloned().collect();
+        sessions.sort_by_key(|s| s.created_at);
+        Ok(sessions)
+    }
+
+    fn delete_chat_session(&self, id: Uuid) -> anyhow::Result<bool> {
+        Ok(self.chat_sessions.lock().unwrap().remove(&id).is_some())
+    }
+
+    fn save_audit_entry(&self, entry: AuditEntry) -> anyhow::Result<()> {
+        self.audit_log.lock().unwrap().push(entry);
+        Ok(())
+    }
+
+    fn audit_log(&self, loop_id: Option<Uuid>) -> anyhow::Result<Vec<AuditEntry>> {
+        Ok(self.audit_log.lock().unwrap().iter().filter(|entry| entry.loop_id == loop_id).cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::LoopType;
+
+    #[test]
+    fn round_trips_a_loop_record() {
+        let storage = InMemoryStorage::new();
+        let record = LoopRecord::new(LoopType::Plan, None, "example");
+        let id = record.id;
+        storage.save_loop(record).unwrap();
+        let fetched = storage.get_loop(id).unwrap().expect("record present");
+        assert_eq!(fetched.id, id);
+    }
+
+    #[test]
+    fn delete_loop_removes_the_record_and_its_associated_data() {
+        let storage = InMemoryStorage::new();
+        let record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        let id = record.id;
+        storage.save_loop(record).unwrap();
+        storage.save_artifact_version(id, 0, "v0".into()).unwrap();
+        storage.save_tool_job(ToolJobRecord::started(id, "cargo test")).unwrap();
+
+        assert!(storage.delete_loop(id).unwrap());
+        assert!(storage.get_loop(id).unwrap().is_none());
+        assert!(storage.artifact_history(id).unwrap().is_empty());
+        assert!(storage.tool_jobs(id).unwrap().is_empty());
+        assert!(!storage.delete_loop(id).unwrap());
+    }
+
+    #[test]
+    fn artifact_history_returns_versions_in_order() {
+        let storage = InMemoryStorage::new();
+        let loop_id = Uuid::new_v4();
+        storage.save_artifact_version(loop_id, 0, "v0".into()).unwrap();
+        storage.save_artifact_version(loop_id, 1, "v1".into()).unwrap();
+        let history = storage.artifact_history(loop_id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].iteration, 0);
+        assert_eq!(history[1].content, "v1");
+    }
+
+    #[test]
+    fn saving_a_tool_job_twice_updates_it_in_place() {
+        let storage = InMemoryStorage::new();
+        let loop_id = Uuid::new_v4();
+        let mut job = ToolJobRecord::started(loop_id, "cargo test");
+        let job_id = job.id;
+        storage.save_tool_job(job.clone()).unwrap();
+
+        job.complete("ok", 42);
+        storage.save_tool_job(job).unwrap();
+
+        let jobs = storage.tool_jobs(loop_id).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, job_id);
+        assert!(jobs[0].is_complete());
+    }
+
+    #[test]
+    fn transcript_accumulates_entries_in_recorded_order() {
+        use crate::llm::{CompletionRequest, CompletionResponse, Message, Role};
+
+        let storage = InMemoryStorage::new();
+        let loop_id = Uuid::new_v4();
+        let request = CompletionRequest::new("claude-sonnet", vec![Message::text(Role::User, "go")]);
+        let response = CompletionResponse { text: "ok".into(), input_tokens: 3, output_tokens: 2, model: "claude-sonnet".into() };
+        storage.save_transcript_entry(TranscriptEntry::new(loop_id, 0, request.clone(), response.clone())).unwrap();
+        storage.save_transcript_entry(TranscriptEntry::new(loop_id, 1, request, response)).unwrap();
+
+        let entries = storage.transcript(loop_id).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].iteration, 0);
+        assert_eq!(entries[1].iteration, 1);
+    }
+
+    #[test]
+    fn chat_sessions_list_oldest_first() {
+        let storage = InMemoryStorage::new();
+        let first = ChatSessionRecord::new("debugging the flaky test");
+        let mut second = ChatSessionRecord::new("planning the auth rewrite");
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        storage.save_chat_session(second.clone()).unwrap();
+        storage.save_chat_session(first.clone()).unwrap();
+
+        let sessions = storage.list_chat_sessions().unwrap();
+        assert_eq!(sessions, vec![first, second]);
+    }
+
+    #[test]
+    fn saving_a_chat_session_twice_renames_it_in_place() {
+        let storage = InMemoryStorage::new();
+        let mut session = ChatSessionRecord::new("untitled");
+        storage.save_chat_session(session.clone()).unwrap();
+
+        session.name = "auth rewrite".to_string();
+        storage.save_chat_session(session.clone()).unwrap();
+
+        let sessions = storage.list_chat_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].name, "auth rewrite");
+    }
+
+    #[test]
+    fn delete_chat_session_removes_it() {
+        let storage = InMemoryStorage::new();
+        let session = ChatSessionRecord::new("untitled");
+        storage.save_chat_session(session.clone()).unwrap();
+        assert!(storage.delete_chat_session(session.id).unwrap());
+        assert!(storage.list_chat_sessions().unwrap().is_empty());
+        assert!(!storage.delete_chat_session(session.id).unwrap());
+    }
+
+    #[test]
+    fn audit_log_filters_by_loop_id() {
+        let storage = InMemoryStorage::new();
+        let loop_id = Uuid::new_v4();
+        storage.save_audit_entry(AuditEntry::new(Uuid::new_v4(), "ada", "loop.get", Some(loop_id), true)).unwrap();
+        storage.save_audit_entry(AuditEntry::new(Uuid::new_v4(), "ada", "loop.list", None, true)).unwrap();
+
+        assert_eq!(storage.audit_log(Some(loop_id)).unwrap().len(), 1);
+        assert_eq!(storage.audit_log(None).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::runner::{classify, RunnerLane};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// A persisted record of one tool invocation, so crash recovery can tell
+/// whether a command completed before the daemon died, and replay/inspection
+/// tooling can show exact tool history for a loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolJobRecord {
+    pub id: Uuid,
+    pub loop_id: Uuid,
+    pub command: String,
+    /// Which runner lane this command was classified into; see
+    /// [`crate::runner::classify`]. Recorded for inspection even though
+    /// no separate runner subprocess dispatches it yet — every lane
+    /// still runs in-process via [`crate::tools::ToolExecutor`].
+    pub lane: RunnerLane,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub result_hash: Option<String>,
+    pub duration_ms: Option<u128>,
+}
+
+impl ToolJobRecord {
+    pub fn started(loop_id: Uuid, command: impl Into<String>) -> Self {
+        let command = command.into();
+        Self {
+            id: Uuid::new_v4(),
+            loop_id,
+            lane: classify(&command),
+            command,
+            started_at: Utc::now(),
+            finished_at: None,
+            result_hash: None,
+            duration_ms: None,
+        }
+    }
+
+    /// Marks the job finished, recording a hash of its output rather than
+    /// the output itself, which may be large and is already captured
+    /// wherever the command's result is surfaced to the loop.
+    pub fn complete(&mut self, output: &str, duration_ms: u128) {
+        self.finished_at = Some(Utc::now());
+        self.result_hash = Some(hash_output(output));
+        self.duration_ms = Some(duration_ms);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.finished_at.is_some()
+    }
+}
+
+fn hash_output(output: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(output.as_bytes());
+    let digest = hasher.finalize();
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_without_a_result() {
+        let job = ToolJobRecord::started(Uuid::new_v4(), "cargo test");
+        assert!(!job.is_complete());
+    }
+
+    #[test]
+    fn records_the_lane_the_command_classifies_into() {
+        let job = ToolJobRecord::started(Uuid::new_v4(), "cargo build --release");
+        assert_eq!(job.lane, RunnerLane::Heavy);
+    }
+
+    #[test]
+    fn completing_records_a_hash_and_duration() {
+        let mut job = ToolJobRecord::started(Uuid::new_v4(), "cargo test");
+        job.complete("ok", 150);
+        assert!(job.is_complete());
+        assert_eq!(job.duration_ms, Some(150));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::llm::{CompletionRequest, CompletionResponse};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// One iteration's exact LLM request/response pair, recorded only when a
+/// loop opts into transcript recording. Exists for offline prompt
+/// debugging, the replay command, and postmortems of why a loop went off
+/// the rails — none of which are reconstructable from the loop record
+/// alone, since that only keeps the feedback summary.
+#[derive(Debug, Clone)]
+pub struct TranscriptEntry {
+    pub loop_id: Uuid,
+    pub iteration: u32,
+    pub request: CompletionRequest,
+    pub response: CompletionResponse,
+    pub prompt_hash: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl TranscriptEntry {
+    pub fn new(loop_id: Uuid, iteration: u32, request: CompletionRequest, response: CompletionResponse) -> Self {
+        let prompt_hash = hash_request(&request);
+        Self {
+            loop_id,
+            iteration,
+            request,
+            response,
+            prompt_hash,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn total_tokens(&self) -> u32 {
+        self.response.input_tokens + self.response.output_tokens
+    }
+}
+
+fn hash_request(request: &CompletionRequest) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(request.system.as_deref().unwrap_or("").as_bytes());
+    for message in &request.messages {
+        for content in &message.content {
+            match content {
+                crate::llm::MessageContent::Text { text } => hasher.update(text.as_bytes()),
+                crate::llm::MessageContent::Image { media_type, data } => {
+                    hasher.update(media_type.as_bytes());
+                    hasher.update(data);
+                }
+            }
+        }
+    }
+    let digest = hasher.finalize();
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::{Message, Role};
+
+    #[test]
+    fn same_request_produces_the_same_prompt_hash() {
+        let request = CompletionRequest::new("claude-sonnet", vec![Message::text(Role::User, "hello")]);
+        let a = TranscriptEntry::new(Uuid::new_v4(), 0, request.clone(), CompletionResponse { text: "hi".into(), input_tokens: 1, output_tokens: 1, model: "claude-sonnet".into() });
+        let b = TranscriptEntry::new(Uuid::new_v4(), 0, request, CompletionResponse { text: "hi".into(), input_tokens: 1, output_tokens: 1, model: "claude-sonnet".into() });
+        assert_eq!(a.prompt_hash, b.prompt_hash);
+    }
+
+    #[test]
+    fn total_tokens_sums_input_and_output() {
+        let request = CompletionRequest::new("claude-sonnet", vec![Message::text(Role::User, "hello")]);
+        let entry = TranscriptEntry::new(Uuid::new_v4(), 0, request, CompletionResponse { text: "hi".into(), input_tokens: 10, output_tokens: 5, model: "claude-sonnet".into() });
+        assert_eq!(entry.total_tokens(), 15);
+    }
+}
@@ -0,0 +1,84 @@
+//! Loop template library: parameterized task recipes defined in
+//! `.loopr/templates/*.yml`, picked interactively in the TUI. The CLI has
+//! no `loopr new` subcommand that instantiates a template.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A predefined recipe for a common task ("add endpoint", "upgrade
+/// dependency", "fix flaky test"): a prompt with `{{param}}` placeholders,
+/// the validation command to run, and a budget for the resulting loop.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub description: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub validation_command: Option<String>,
+    #[serde(default)]
+    pub max_iterations: Option<u32>,
+}
+
+/// Parses a `.loopr/templates/*.yml` file's contents into a [`Template`].
+pub fn parse_template(content: &str) -> anyhow::Result<Template> {
+    Ok(serde_yaml::from_str(content)?)
+}
+
+/// Fills in `{{param}}` placeholders in the template's prompt, erroring
+/// out (rather than leaving a literal `{{name}}` the LLM would be
+/// confused by) if a referenced param wasn't supplied.
+pub fn render(template: &Template, params: &HashMap<String, String>) -> anyhow::Result<String> {
+    let mut rendered = template.prompt.clone();
+    for (key, value) in params {
+        rendered = rendered.replace(&format!("{{{{{key}}}}}"), value);
+    }
+
+    if let Some(start) = rendered.find("{{") {
+        let end = rendered[start..].find("}}").map(|e| start + e + 2).unwrap_or(rendered.len());
+        anyhow::bail!("unresolved template placeholder: {}", &rendered[start..end]);
+    }
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint_template() -> Template {
+        Template {
+            name: "add-endpoint".into(),
+            description: "Add a new REST endpoint".into(),
+            prompt: "Add a {{method}} endpoint at {{path}}.".into(),
+            validation_command: Some("cargo test".into()),
+            max_iterations: Some(5),
+        }
+    }
+
+    #[test]
+    fn renders_all_supplied_params() {
+        let params = HashMap::from([("method".to_string(), "POST".to_string()), ("path".to_string(), "/users".to_string())]);
+        let rendered = render(&endpoint_template(), &params).unwrap();
+        assert_eq!(rendered, "Add a POST endpoint at /users.");
+    }
+
+    #[test]
+    fn errors_on_missing_param() {
+        let params = HashMap::from([("method".to_string(), "POST".to_string())]);
+        let error = render(&endpoint_template(), &params).unwrap_err();
+        assert!(error.to_string().contains("{{path}}"));
+    }
+
+    #[test]
+    fn parses_template_yaml() {
+        let yaml = "\
+name: fix-flaky-test
+description: Stabilize a flaky test
+prompt: Investigate and fix the flaky test {{test_name}}.
+validation_command: cargo test {{test_name}}
+";
+        let template = parse_template(yaml).unwrap();
+        assert_eq!(template.name, "fix-flaky-test");
+        assert_eq!(template.validation_command.as_deref(), Some("cargo test {{test_name}}"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Issue-tracker ticket linkage: a plan can be linked to a Jira or Linear
+//! ticket via `loopr plan --ticket PROJ-123` (see
+//! [`crate::domain::LoopRecord::ticket_id`]), with progress comments
+//! posted on key lifecycle transitions and the ticket transitioned when
+//! the plan tree merges. Like [`crate::forge`], each provider only builds
+//! the request it wants executed as plain data; sending it, and fetching
+//! ticket context into the planning prompt, are left to the daemon's
+//! orchestration layer.
+
+use crate::domain::LoopStatus;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TicketProvider {
+    Jira,
+    Linear,
+}
+
+/// One HTTP request a [`TicketClient`] implementation wants executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicketRequest {
+    pub method: &'static str,
+    pub path: String,
+    pub body: serde_json::Value,
+}
+
+/// A ticket provider's fetch, comment, and transition endpoints, as
+/// request-building functions rather than methods that perform the call.
+pub trait TicketClient {
+    fn provider(&self) -> TicketProvider;
+    fn fetch(&self, ticket_id: &str) -> TicketRequest;
+    fn post_comment(&self, ticket_id: &str, body: &str) -> TicketRequest;
+    fn transition(&self, ticket_id: &str, status: &str) -> TicketRequest;
+}
+
+pub struct JiraClient;
+
+impl TicketClient for JiraClient {
+    fn provider(&self) -> TicketProvider {
+        TicketProvider::Jira
+    }
+
+    fn fetch(&self, ticket_id: &str) -> TicketRequest {
+        TicketRequest { method: "GET", path: format!("/rest/api/3/issue/{ticket_id}"), body: serde_json::Value::Null }
+    }
+
+    fn post_comment(&self, ticket_id: &str, body: &str) -> TicketRequest {
+        TicketRequest { method: "POST", path: format!("/rest/api/3/issue/{ticket_id}/comment"), body: json!({ "body": body }) }
+    }
+
+    fn transition(&self, ticket_id: &str, status: &str) -> TicketRequest {
+        TicketRequest { method: "POST", path: format!("/rest/api/3/issue/{ticket_id}/transitions"), body: json!({ "transition": { "name": status } }) }
+    }
+}
+
+/// Linear's API is a single GraphQL endpoint; every call is a `POST` with
+/// a different query/mutation in the body rather than a distinct path.
+pub struct LinearClient;
+
+impl TicketClient for LinearClient {
+    fn provider(&self) -> TicketProvider {
+        TicketProvider::Linear
+    }
+
+    fn fetch(&self, ticket_id: &str) -> TicketRequest {
+        TicketRequest {
+            method: "POST",
+            path: "/graphql".to_string(),
+            body: json!({ "query": "query($id: String!) { issue(id: $id) { title description state { name } } }", "variables": { "id": ticket_id } }),
+        }
+    }
+
+    fn post_comment(&self, ticket_id: &str, body: &str) -> TicketRequest {
+        TicketRequest {
+            method: "POST",
+            path: "/graphql".to_string(),
+            body: json!({
+                "query": "mutation($issueId: String!, $body: String!) { commentCreate(input: { issueId: $issueId, body: $body }) { success } }",
+                "variables": { "issueId": ticket_id, "body": body },
+            }),
+        }
+    }
+
+    fn transition(&self, ticket_id: &str, status: &str) -> TicketRequest {
+        TicketRequest {
+            method: "POST",
+            path: "/graphql".to_string(),
+            body: json!({
+                "query": "mutation($issueId: String!, $stateId: String!) { issueUpdate(id: $issueId, input: { stateId: $stateId }) { success } }",
+                "variables": { "issueId": ticket_id, "stateId": status },
+            }),
+        }
+    }
+}
+
+/// Builds the [`TicketClient`] implementation matching `provider`.
+pub fn for_provider(provider: TicketProvider) -> Box<dyn TicketClient> {
+    match provider {
+        TicketProvider::Jira => Box::new(JiraClient),
+        TicketProvider::Linear => Box::new(LinearClient),
+    }
+}
+
+/// The progress comment to post for a loop's lifecycle transition into
+/// `status`, or `None` if it isn't one of the transitions worth
+/// commenting on.
+pub fn comment_for_transition(status: LoopStatus) -> Option<&'static str> {
+    match status {
+        LoopStatus::Running => Some("Work has started on the linked plan."),
+        LoopStatus::AwaitingApproval => Some("Awaiting operator approval."),
+        LoopStatus::Completed => Some("The linked plan completed and merged."),
+        LoopStatus::Failed => Some("The linked plan failed validation; see its post-mortem for details."),
+        _ => None,
+    }
+}
+
+/// The ticket status to transition to when a loop reaches `status`, or
+/// `None` if that status doesn't correspond to a ticket transition.
+pub fn transition_for(status: LoopStatus) -> Option<&'static str> {
+    match status {
+        LoopStatus::Completed => Some("Done"),
+        LoopStatus::Failed => Some("Blocked"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jira_fetch_targets_the_issue_endpoint() {
+        let request = JiraClient.fetch("PROJ-123");
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/rest/api/3/issue/PROJ-123");
+    }
+
+    #[test]
+    fn jira_transition_names_the_target_transition() {
+        let request = JiraClient.transition("PROJ-123", "Done");
+        assert_eq!(request.path, "/rest/api/3/issue/PROJ-123/transitions");
+        assert_eq!(request.body["transition"]["name"], "Done");
+    }
+
+    #[test]
+    fn linear_requests_all_target_the_graphql_endpoint() {
+        let fetch = LinearClient.fetch("ENG-123");
+        let comment = LinearClient.post_comment("ENG-123", "progress update");
+        let transition = LinearClient.transition("ENG-123", "state-done");
+        assert!([&fetch, &comment, &transition].iter().all(|request| request.path == "/graphql"));
+        assert_eq!(comment.body["variables"]["body"], "progress update");
+        assert_eq!(transition.body["variables"]["stateId"], "state-done");
+    }
+
+    #[test]
+    fn comments_only_on_the_transitions_that_matter() {
+        assert!(comment_for_transition(LoopStatus::Completed).is_some());
+        assert!(comment_for_transition(LoopStatus::Pending).is_none());
+    }
+
+    #[test]
+    fn transitions_only_fire_on_terminal_statuses() {
+        assert_eq!(transition_for(LoopStatus::Completed), Some("Done"));
+        assert_eq!(transition_for(LoopStatus::Failed), Some("Blocked"));
+        assert_eq!(transition_for(LoopStatus::Running), None);
+    }
+}
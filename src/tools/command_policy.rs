@@ -0,0 +1,123 @@
+//! Command allow/deny policy for `run_command`: blocks destructive or
+//! exfiltrating commands by default, and can switch to allowlist-only
+//! "strict mode" where only named binaries may run at all. Commands are
+//! matched after [`super::shell_tokens`] normalization (whitespace
+//! collapsing, quote-stripping, `./` prefix removal on the binary name),
+//! but this is still a best-effort check, not a sandbox — see that
+//! module's doc comment for what it doesn't catch.
+
+use super::shell_tokens::{normalize_command, tokenize};
+use crate::ipc::DaemonEvent;
+use uuid::Uuid;
+
+fn default_denylist() -> Vec<String> {
+    vec!["rm -rf".to_string(), "git push".to_string(), "curl".to_string(), "| sh".to_string(), "| bash".to_string()]
+}
+
+/// Command policy for one loop. `allowed_binaries` is `None` in default
+/// (denylist-only) mode and `Some` in strict mode, where only the listed
+/// binaries may run regardless of the denylist.
+#[derive(Debug, Clone)]
+pub struct CommandPolicy {
+    pub denied_patterns: Vec<String>,
+    pub allowed_binaries: Option<Vec<String>>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            denied_patterns: default_denylist(),
+            allowed_binaries: None,
+        }
+    }
+}
+
+impl CommandPolicy {
+    /// Strict mode: only `allowed_binaries` may run, on top of the usual
+    /// denylist.
+    pub fn strict(allowed_binaries: Vec<String>) -> Self {
+        Self {
+            denied_patterns: default_denylist(),
+            allowed_binaries: Some(allowed_binaries),
+        }
+    }
+
+    /// Applies a loop type's extra denied patterns on top of this policy's
+    /// own, so per-loop-type overrides only ever add restrictions.
+    pub fn with_extra_denied(mut self, extra: Vec<String>) -> Self {
+        self.denied_patterns.extend(extra);
+        self
+    }
+
+    /// Evaluates `command`, returning a denial reason and an operator
+    /// alert event if it should be refused.
+    pub fn evaluate(&self, loop_id: Uuid, command: &str) -> Option<(String, DaemonEvent)> {
+        let normalized = normalize_command(command);
+        let reason = if let Some(pattern) = self.denied_patterns.iter().find(|pattern| normalized.contains(pattern.as_str())) {
+            Some(format!("command matches denied pattern \"{pattern}\""))
+        } else if let Some(allowed) = &self.allowed_binaries {
+            let binary = tokenize(command).into_iter().next().unwrap_or_default();
+            (!allowed.contains(&binary)).then(|| format!("strict mode: \"{binary}\" is not in the allowed binaries list"))
+        } else {
+            None
+        }?;
+
+        let alert = DaemonEvent::OperatorAlert {
+            loop_id,
+            message: format!("denied command: {command} ({reason})"),
+        };
+        Some((reason, alert))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denies_commands_matching_the_default_denylist() {
+        let policy = CommandPolicy::default();
+        let (reason, _) = policy.evaluate(Uuid::nil(), "rm -rf /").unwrap();
+        assert!(reason.contains("rm -rf"));
+    }
+
+    #[test]
+    fn allows_ordinary_commands_by_default() {
+        let policy = CommandPolicy::default();
+        assert!(policy.evaluate(Uuid::nil(), "cargo test").is_none());
+    }
+
+    #[test]
+    fn strict_mode_denies_binaries_outside_the_allowlist() {
+        let policy = CommandPolicy::strict(vec!["cargo".to_string()]);
+        assert!(policy.evaluate(Uuid::nil(), "cargo test").is_none());
+        assert!(policy.evaluate(Uuid::nil(), "npm install").is_some());
+    }
+
+    #[test]
+    fn extra_whitespace_does_not_dodge_the_denylist() {
+        let policy = CommandPolicy::default();
+        let (reason, _) = policy.evaluate(Uuid::nil(), "rm  -rf /").unwrap();
+        assert!(reason.contains("rm -rf"));
+    }
+
+    #[test]
+    fn quoting_each_word_does_not_dodge_the_denylist() {
+        let policy = CommandPolicy::default();
+        let (reason, _) = policy.evaluate(Uuid::nil(), "'rm' '-rf' /").unwrap();
+        assert!(reason.contains("rm -rf"));
+    }
+
+    #[test]
+    fn a_leading_dot_slash_on_an_allowed_binary_is_still_allowed() {
+        let policy = CommandPolicy::strict(vec!["cargo".to_string()]);
+        assert!(policy.evaluate(Uuid::nil(), "./cargo test").is_none());
+    }
+
+    #[test]
+    fn extra_denied_patterns_stack_on_the_defaults() {
+        let policy = CommandPolicy::default().with_extra_denied(vec!["cargo publish".to_string()]);
+        assert!(policy.evaluate(Uuid::nil(), "cargo publish").is_some());
+        assert!(policy.evaluate(Uuid::nil(), "rm -rf /").is_some());
+    }
+}
@@ -0,0 +1,70 @@
+//! Container-based execution: runs validation commands and the bash tool
+//! inside a project-defined image (declared via `devcontainer.json` or
+//! `loopr.yml`'s `image` key), so a loop's toolchain doesn't depend on
+//! whatever happens to be installed on the host.
+
+use serde::Deserialize;
+use std::path::Path;
+
+/// The subset of `devcontainer.json` this crate reads.
+#[derive(Debug, Clone, Deserialize)]
+struct DevContainerConfig {
+    image: String,
+}
+
+/// A project's container execution config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerRuntime {
+    pub image: String,
+}
+
+impl ContainerRuntime {
+    pub fn new(image: impl Into<String>) -> Self {
+        Self { image: image.into() }
+    }
+
+    /// Parses a `devcontainer.json` file's contents for its `image` key.
+    pub fn from_devcontainer_json(content: &str) -> anyhow::Result<Self> {
+        let config: DevContainerConfig = serde_json::from_str(content)?;
+        Ok(Self::new(config.image))
+    }
+
+    /// Wraps `command` to run inside the container with `worktree`
+    /// mounted read-write at `/workspace`.
+    pub fn wrap_command(&self, worktree: &Path, command: &str) -> String {
+        format!("docker run --rm -v {}:/workspace -w /workspace {} sh -c {}", worktree.display(), self.image, shell_quote(command))
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_the_image_key_from_devcontainer_json() {
+        let json = r#"{"image": "rust:1.80", "name": "loopr-dev"}"#;
+        let runtime = ContainerRuntime::from_devcontainer_json(json).unwrap();
+        assert_eq!(runtime.image, "rust:1.80");
+    }
+
+    #[test]
+    fn wraps_a_command_with_the_worktree_mounted() {
+        let runtime = ContainerRuntime::new("rust:1.80");
+        let wrapped = runtime.wrap_command(&PathBuf::from("/tmp/work"), "cargo test");
+        assert!(wrapped.contains("-v /tmp/work:/workspace"));
+        assert!(wrapped.contains("rust:1.80"));
+        assert!(wrapped.contains("cargo test"));
+    }
+
+    #[test]
+    fn escapes_single_quotes_in_the_wrapped_command() {
+        let runtime = ContainerRuntime::new("rust:1.80");
+        let wrapped = runtime.wrap_command(&PathBuf::from("/tmp/work"), "echo 'hi'");
+        assert!(wrapped.contains(r"'\''"));
+    }
+}
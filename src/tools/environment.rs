@@ -0,0 +1,109 @@
+//! Environment variable and secrets injection for `run_command` and
+//! validation gates. Plain vars and dotenv-sourced ones are injected
+//! directly; secret references are resolved once (from the OS keychain,
+//! in production) into a value that's injected into the process but
+//! masked out of anything that might reach a transcript or the LLM.
+
+use std::collections::HashMap;
+
+/// Where a secret's value comes from, resolved lazily so the raw value
+/// only ever exists right before a process needs it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretSource {
+    pub service: String,
+    pub account: String,
+}
+
+/// A project's declared environment: plain vars plus secret references
+/// that must be resolved before use.
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentConfig {
+    pub vars: HashMap<String, String>,
+    pub secrets: HashMap<String, SecretSource>,
+}
+
+impl EnvironmentConfig {
+    /// Parses `KEY=value` lines, ignoring blanks, `#` comments, and
+    /// stripping one layer of surrounding double quotes.
+    pub fn parse_dotenv(content: &str) -> HashMap<String, String> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (key, value) = line.split_once('=')?;
+                let value = value.trim();
+                let value = value.strip_prefix('"').and_then(|inner| inner.strip_suffix('"')).unwrap_or(value);
+                Some((key.trim().to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    pub fn with_dotenv(mut self, content: &str) -> Self {
+        self.vars.extend(Self::parse_dotenv(content));
+        self
+    }
+
+    pub fn with_secret(mut self, key: impl Into<String>, source: SecretSource) -> Self {
+        self.secrets.insert(key.into(), source);
+        self
+    }
+
+    /// Resolves every secret via `resolve_secret` and merges the result
+    /// with the plain vars into one map ready to inject into a process.
+    pub fn resolve(&self, resolve_secret: impl Fn(&SecretSource) -> anyhow::Result<String>) -> anyhow::Result<HashMap<String, String>> {
+        let mut resolved = self.vars.clone();
+        for (key, source) in &self.secrets {
+            resolved.insert(key.clone(), resolve_secret(source)?);
+        }
+        Ok(resolved)
+    }
+}
+
+/// Replaces every occurrence of each named secret's resolved value in
+/// `text` with a placeholder, so transcripts and LLM prompts never see
+/// raw secret values.
+pub fn mask_secrets(text: &str, resolved_env: &HashMap<String, String>, secret_keys: &[String]) -> String {
+    let mut masked = text.to_string();
+    for key in secret_keys {
+        if let Some(value) = resolved_env.get(key) {
+            if !value.is_empty() {
+                masked = masked.replace(value.as_str(), "***");
+            }
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dotenv_lines_skipping_comments_and_blanks() {
+        let content = "# comment\nAPI_URL=https://example.com\n\nAPI_KEY=\"abc123\"\n";
+        let vars = EnvironmentConfig::parse_dotenv(content);
+        assert_eq!(vars.get("API_URL"), Some(&"https://example.com".to_string()));
+        assert_eq!(vars.get("API_KEY"), Some(&"abc123".to_string()));
+    }
+
+    #[test]
+    fn resolve_merges_plain_vars_and_resolved_secrets() {
+        let config = EnvironmentConfig::default()
+            .with_dotenv("API_URL=https://example.com")
+            .with_secret("API_KEY", SecretSource { service: "loopr".to_string(), account: "api".to_string() });
+        let resolved = config.resolve(|_| Ok("sekrit".to_string())).unwrap();
+        assert_eq!(resolved.get("API_URL"), Some(&"https://example.com".to_string()));
+        assert_eq!(resolved.get("API_KEY"), Some(&"sekrit".to_string()));
+    }
+
+    #[test]
+    fn mask_secrets_hides_resolved_secret_values() {
+        let resolved = HashMap::from([("API_KEY".to_string(), "sekrit".to_string())]);
+        let text = "request failed with key sekrit";
+        let masked = mask_secrets(text, &resolved, &["API_KEY".to_string()]);
+        assert_eq!(masked, "request failed with key ***");
+    }
+}
@@ -0,0 +1,343 @@
+//! Runs tool commands under a per-tool timeout and output-size budget, so
+//! a runaway command can't stall an iteration and a noisy one can't blow
+//! the next prompt's context budget.
+
+use super::ContainerRuntime;
+use crate::chaos::ChaosInjector;
+use crate::storage::{Storage, ToolJobRecord};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+/// Timeout and output-size limits for one tool invocation.
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionBudget {
+    pub timeout: Duration,
+    pub max_output_bytes: usize,
+}
+
+impl Default for ExecutionBudget {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(120),
+            max_output_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// What running a command under a budget produced.
+#[derive(Debug, Clone)]
+pub enum ExecutionOutcome {
+    Completed { exit_success: bool, output: String, truncated: bool },
+    TimedOut,
+}
+
+/// Executes shell commands against a worktree, applying a default budget
+/// with optional overrides keyed by loop type name.
+#[derive(Clone, Default)]
+pub struct ToolExecutor {
+    pub default_budget: ExecutionBudget,
+    overrides: HashMap<String, ExecutionBudget>,
+    /// When set, every command runs inside this container instead of
+    /// directly on the host.
+    pub container: Option<ContainerRuntime>,
+    /// When set, every command run is persisted as a [`ToolJobRecord`] on
+    /// spawn and again on completion, so a loop's tool history survives a
+    /// daemon restart and crash recovery can tell whether a command
+    /// finished before the daemon died.
+    pub storage: Option<Arc<dyn Storage>>,
+    /// When set, every command run first rolls against the injector for a
+    /// simulated timeout; see [`crate::chaos::ChaosInjector::maybe_tool_timeout`].
+    pub chaos: Option<Arc<Mutex<ChaosInjector>>>,
+}
+
+impl std::fmt::Debug for ToolExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolExecutor")
+            .field("default_budget", &self.default_budget)
+            .field("overrides", &self.overrides)
+            .field("container", &self.container)
+            .field("storage", &self.storage.is_some())
+            .field("chaos", &self.chaos.is_some())
+            .finish()
+    }
+}
+
+impl ToolExecutor {
+    pub fn new(default_budget: ExecutionBudget) -> Self {
+        Self {
+            default_budget,
+            overrides: HashMap::new(),
+            container: None,
+            storage: None,
+            chaos: None,
+        }
+    }
+
+    pub fn with_override(mut self, loop_type: impl Into<String>, budget: ExecutionBudget) -> Self {
+        self.overrides.insert(loop_type.into(), budget);
+        self
+    }
+
+    pub fn with_container(mut self, container: ContainerRuntime) -> Self {
+        self.container = Some(container);
+        self
+    }
+
+    /// Persists a [`ToolJobRecord`] for every command this executor runs.
+    pub fn with_storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Rolls every command run against `chaos` for a simulated timeout
+    /// before it's actually spawned.
+    pub fn with_chaos(mut self, chaos: Arc<Mutex<ChaosInjector>>) -> Self {
+        self.chaos = Some(chaos);
+        self
+    }
+
+    fn budget_for(&self, loop_type: &str) -> ExecutionBudget {
+        self.overrides.get(loop_type).copied().unwrap_or(self.default_budget)
+    }
+
+    /// Runs `command` in `worktree` under the budget for `loop_type`,
+    /// killing the command's whole process group if it overruns the
+    /// timeout so background children don't outlive it. Runs inside
+    /// [`ContainerRuntime`] when one is configured. When [`with_storage`](Self::with_storage)
+    /// is configured, records a [`ToolJobRecord`] for `loop_id` on spawn
+    /// and updates it with the outcome once the command finishes. When
+    /// [`with_chaos`](Self::with_chaos) is configured, the command may
+    /// never actually run, simulating a timeout instead.
+    pub async fn run(&self, worktree: &Path, loop_id: Uuid, loop_type: &str, command: &str) -> anyhow::Result<ExecutionOutcome> {
+        let budget = self.budget_for(loop_type);
+        let effective_command = match &self.container {
+            Some(container) => container.wrap_command(worktree, command),
+            None => command.to_string(),
+        };
+
+        let mut job = self.storage.as_ref().map(|_| ToolJobRecord::started(loop_id, command));
+        if let (Some(storage), Some(job)) = (&self.storage, &job) {
+            storage.save_tool_job(job.clone())?;
+        }
+
+        let injected_timeout = self.chaos.as_ref().is_some_and(|chaos| chaos.lock().unwrap().maybe_tool_timeout().is_some());
+        if injected_timeout {
+            if let (Some(storage), Some(job)) = (&self.storage, &mut job) {
+                job.complete("[timed out]", 0);
+                storage.save_tool_job(job.clone())?;
+            }
+            return Ok(ExecutionOutcome::TimedOut);
+        }
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(&effective_command).current_dir(worktree).stdout(Stdio::piped()).stderr(Stdio::piped());
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let started = Instant::now();
+        let child = cmd.spawn()?;
+        let pid = child.id();
+
+        let outcome = match timeout(budget.timeout, child.wait_with_output()).await {
+            Ok(result) => {
+                let output = result?;
+                let mut combined = output.stdout;
+                combined.extend_from_slice(&output.stderr);
+                let (text, truncated) = truncate_output(&combined, budget.max_output_bytes);
+                ExecutionOutcome::Completed {
+                    exit_success: output.status.success(),
+                    output: text,
+                    truncated,
+                }
+            }
+            Err(_) => {
+                #[cfg(unix)]
+                if let Some(pid) = pid {
+                    // SAFETY: killing the process group we just created above.
+                    unsafe {
+                        libc::kill(-(pid as i32), libc::SIGKILL);
+                    }
+                }
+                ExecutionOutcome::TimedOut
+            }
+        };
+
+        if let (Some(storage), Some(job)) = (&self.storage, &mut job) {
+            let output = match &outcome {
+                ExecutionOutcome::Completed { output, .. } => output.as_str(),
+                ExecutionOutcome::TimedOut => "[timed out]",
+            };
+            job.complete(output, started.elapsed().as_millis());
+            storage.save_tool_job(job.clone())?;
+        }
+
+        Ok(outcome)
+    }
+}
+
+/// One queued tool invocation, tagged with the LLM's call id so the
+/// result can be routed back to the right place in the response.
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub id: String,
+    pub loop_id: Uuid,
+    pub loop_type: String,
+    pub command: String,
+}
+
+impl ToolExecutor {
+    /// Runs independent `calls` concurrently, bounded by
+    /// `max_concurrency`, and returns results in the same order as
+    /// `calls` regardless of completion order.
+    pub async fn run_many(&self, worktree: &Path, calls: &[ToolCall], max_concurrency: usize) -> anyhow::Result<Vec<(String, ExecutionOutcome)>> {
+        let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        let mut handles = Vec::with_capacity(calls.len());
+
+        for call in calls {
+            let semaphore = semaphore.clone();
+            let worktree: PathBuf = worktree.to_path_buf();
+            let executor = self.clone();
+            let call = call.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.expect("semaphore is never closed");
+                let outcome = executor.run(&worktree, call.loop_id, &call.loop_type, &call.command).await;
+                (call.id, outcome)
+            }));
+        }
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let (id, outcome) = handle.await?;
+            results.push((id, outcome?));
+        }
+        Ok(results)
+    }
+}
+
+/// Truncates `output` to `max_bytes`, keeping a larger head and a smaller
+/// tail (the tail usually has the actual error) with a note on how much
+/// was elided in between.
+fn truncate_output(output: &[u8], max_bytes: usize) -> (String, bool) {
+    if output.len() <= max_bytes {
+        return (String::from_utf8_lossy(output).into_owned(), false);
+    }
+
+    let head_bytes = max_bytes * 2 / 3;
+    let tail_bytes = max_bytes - head_bytes;
+    let elided = output.len() - head_bytes - tail_bytes;
+
+    let head = String::from_utf8_lossy(&output[..head_bytes]);
+    let tail = String::from_utf8_lossy(&output[output.len() - tail_bytes..]);
+    (format!("{head}\n... [{elided} bytes elided] ...\n{tail}"), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chaos::ChaosConfig;
+    use crate::storage::InMemoryStorage;
+
+    #[tokio::test]
+    async fn completes_a_quick_command_within_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let executor = ToolExecutor::new(ExecutionBudget::default());
+        let outcome = executor.run(dir.path(), Uuid::new_v4(), "ralph", "echo hi").await.unwrap();
+        match outcome {
+            ExecutionOutcome::Completed { exit_success, output, truncated } => {
+                assert!(exit_success);
+                assert!(output.contains("hi"));
+                assert!(!truncated);
+            }
+            ExecutionOutcome::TimedOut => panic!("expected completion"),
+        }
+    }
+
+    #[test]
+    fn with_container_configures_the_executor() {
+        let executor = ToolExecutor::new(ExecutionBudget::default()).with_container(ContainerRuntime::new("rust:1.80"));
+        assert_eq!(executor.container.unwrap().image, "rust:1.80");
+    }
+
+    #[tokio::test]
+    async fn kills_a_command_that_overruns_its_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let budget = ExecutionBudget { timeout: Duration::from_millis(50), max_output_bytes: 1024 };
+        let executor = ToolExecutor::new(budget);
+        let outcome = executor.run(dir.path(), Uuid::new_v4(), "ralph", "sleep 5").await.unwrap();
+        assert!(matches!(outcome, ExecutionOutcome::TimedOut));
+    }
+
+    #[tokio::test]
+    async fn per_loop_type_override_takes_priority_over_the_default() {
+        let executor = ToolExecutor::new(ExecutionBudget::default())
+            .with_override("ralph", ExecutionBudget { timeout: Duration::from_millis(10), max_output_bytes: 1024 });
+        assert_eq!(executor.budget_for("ralph").timeout, Duration::from_millis(10));
+        assert_eq!(executor.budget_for("docs").timeout, ExecutionBudget::default().timeout);
+    }
+
+    #[tokio::test]
+    async fn run_many_preserves_call_order_regardless_of_completion_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let executor = ToolExecutor::new(ExecutionBudget::default());
+        let loop_id = Uuid::new_v4();
+        let calls = vec![
+            ToolCall { id: "a".to_string(), loop_id, loop_type: "ralph".to_string(), command: "sleep 0.05 && echo first".to_string() },
+            ToolCall { id: "b".to_string(), loop_id, loop_type: "ralph".to_string(), command: "echo second".to_string() },
+        ];
+        let results = executor.run_many(dir.path(), &calls, 2).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn truncates_large_output_keeping_head_and_tail() {
+        let output = vec![b'x'; 10_000];
+        let (text, truncated) = truncate_output(&output, 100);
+        assert!(truncated);
+        assert!(text.contains("bytes elided"));
+        assert!(text.len() < output.len());
+    }
+
+    #[tokio::test]
+    async fn a_completed_command_is_persisted_as_a_finished_tool_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage: Arc<dyn Storage> = Arc::new(InMemoryStorage::new());
+        let executor = ToolExecutor::new(ExecutionBudget::default()).with_storage(storage.clone());
+        let loop_id = Uuid::new_v4();
+
+        executor.run(dir.path(), loop_id, "ralph", "echo hi").await.unwrap();
+
+        let jobs = storage.tool_jobs(loop_id).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].command, "echo hi");
+        assert!(jobs[0].is_complete());
+    }
+
+    #[tokio::test]
+    async fn a_chaos_timeout_fires_without_ever_spawning_the_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ChaosConfig { enabled: true, tool_timeout_rate: 1.0, ..ChaosConfig::default() };
+        let executor = ToolExecutor::new(ExecutionBudget::default()).with_chaos(Arc::new(Mutex::new(ChaosInjector::new(config, 1))));
+        let outcome = executor.run(dir.path(), Uuid::new_v4(), "ralph", "echo should-not-run").await.unwrap();
+        assert!(matches!(outcome, ExecutionOutcome::TimedOut));
+    }
+
+    #[tokio::test]
+    async fn without_storage_configured_nothing_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = InMemoryStorage::new();
+        let executor = ToolExecutor::new(ExecutionBudget::default());
+        let loop_id = Uuid::new_v4();
+
+        executor.run(dir.path(), loop_id, "ralph", "echo hi").await.unwrap();
+
+        assert!(storage.tool_jobs(loop_id).unwrap().is_empty());
+    }
+}
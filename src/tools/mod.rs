@@ -0,0 +1,353 @@
+//! Tools the LLM can invoke against a loop's worktree.
+
+mod command_policy;
+mod container;
+mod environment;
+mod executor;
+mod protected_paths;
+mod resource_limits;
+mod scope;
+mod shell_tokens;
+
+pub use command_policy::CommandPolicy;
+pub use container::ContainerRuntime;
+pub use environment::{mask_secrets, EnvironmentConfig, SecretSource};
+pub use executor::{ExecutionBudget, ExecutionOutcome, ToolCall, ToolExecutor};
+pub use protected_paths::{ProtectedPathPolicy, ToolRefusal};
+pub use resource_limits::{exceeding_limit, read_usage, LoopResourceTracker, ResourceLimits, ResourceUsage};
+
+use crate::llm::MessageContent;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+/// The sandbox a tool call executes within: the worktree root, the loop
+/// it belongs to (for attributing refusals and alerts), and the
+/// protected-path policy every mutating tool consults.
+#[derive(Debug, Clone)]
+pub struct ToolContext {
+    pub worktree: PathBuf,
+    pub loop_id: Uuid,
+    pub protected_paths: ProtectedPathPolicy,
+    pub command_policy: CommandPolicy,
+    /// Additional roots outside the worktree that read access is allowed
+    /// into (e.g. a shared docs checkout); never consulted for writes.
+    pub extra_read_only_roots: Vec<PathBuf>,
+    /// Fully resolved environment (plain vars and secrets alike) injected
+    /// into every `run_command` invocation.
+    pub resolved_env: HashMap<String, String>,
+    /// Keys of `resolved_env` whose values must never appear in output
+    /// shown to the LLM or recorded in a transcript.
+    pub secret_keys: Vec<String>,
+    /// When set, restricts every tool call to this worktree-relative
+    /// subtree, for monorepo plans scoped to one team's area; see
+    /// [`crate::domain::LoopRecord::scope_path`].
+    pub scope_path: Option<String>,
+}
+
+impl ToolContext {
+    pub fn new(worktree: PathBuf) -> Self {
+        Self {
+            worktree,
+            loop_id: Uuid::nil(),
+            protected_paths: ProtectedPathPolicy::default(),
+            command_policy: CommandPolicy::default(),
+            extra_read_only_roots: Vec::new(),
+            resolved_env: HashMap::new(),
+            secret_keys: Vec::new(),
+            scope_path: None,
+        }
+    }
+
+    pub fn with_extra_read_only_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.extra_read_only_roots = roots;
+        self
+    }
+
+    pub fn with_scope_path(mut self, scope_path: impl Into<String>) -> Self {
+        self.scope_path = Some(scope_path.into());
+        self
+    }
+
+    /// Refuses `relative_path` if a [`scope_path`](Self::scope_path) is
+    /// configured and the path falls outside it.
+    fn check_scope(&self, relative_path: &str) -> Result<(), ToolRefusal> {
+        match &self.scope_path {
+            Some(scope) if !relative_path.starts_with(scope.as_str()) => Err(ToolRefusal {
+                reason: format!("{relative_path} is outside this plan's scope {scope:?}"),
+                alert: None,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Refuses `command` if a [`scope_path`](Self::scope_path) is
+    /// configured and any path-like (slash-containing) token names a path
+    /// outside it, the same heuristic [`ProtectedPathPolicy::check_command`]
+    /// uses for protected paths.
+    fn check_scope_command(&self, command: &str) -> Result<(), ToolRefusal> {
+        if self.scope_path.is_none() {
+            return Ok(());
+        }
+        for token in command.split_whitespace() {
+            if token.contains('/') {
+                self.check_scope(token)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Injects `resolved_env` into `run_command`, treating `secret_keys`
+    /// as values that must be masked out of anything surfaced back to the
+    /// LLM (see [`mask_secrets`]).
+    pub fn with_environment(mut self, resolved_env: HashMap<String, String>, secret_keys: Vec<String>) -> Self {
+        self.resolved_env = resolved_env;
+        self.secret_keys = secret_keys;
+        self
+    }
+
+    /// Masks every configured secret's value out of `text`.
+    pub fn mask_secrets(&self, text: &str) -> String {
+        mask_secrets(text, &self.resolved_env, &self.secret_keys)
+    }
+
+    pub fn with_loop_id(mut self, loop_id: Uuid) -> Self {
+        self.loop_id = loop_id;
+        self
+    }
+
+    pub fn with_protected_paths(mut self, patterns: Vec<String>) -> Self {
+        self.protected_paths = ProtectedPathPolicy::new(patterns);
+        self
+    }
+
+    pub fn with_command_policy(mut self, command_policy: CommandPolicy) -> Self {
+        self.command_policy = command_policy;
+        self
+    }
+
+    /// Writes `contents` to `relative_path` inside the worktree, refusing
+    /// if the path is protected or escapes the worktree.
+    pub fn write_file(&mut self, relative_path: &str, contents: &str) -> Result<(), ToolRefusal> {
+        self.check_scope(relative_path)?;
+        if let Some(refusal) = self.protected_paths.check(self.loop_id, relative_path) {
+            return Err(refusal);
+        }
+        let full_path = scope::resolve(&self.worktree, relative_path, &[])?;
+        std::fs::write(full_path, contents).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })
+    }
+
+    /// Replaces the first occurrence of `old` with `new` in `relative_path`,
+    /// refusing if the path is protected or escapes the worktree.
+    pub fn edit_file(&mut self, relative_path: &str, old: &str, new: &str) -> Result<(), ToolRefusal> {
+        self.check_scope(relative_path)?;
+        if let Some(refusal) = self.protected_paths.check(self.loop_id, relative_path) {
+            return Err(refusal);
+        }
+        let full_path = scope::resolve(&self.worktree, relative_path, &[])?;
+        let contents = std::fs::read_to_string(&full_path).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })?;
+        if !contents.contains(old) {
+            return Err(ToolRefusal {
+                reason: format!("{old:?} not found in {relative_path}"),
+                alert: None,
+            });
+        }
+        std::fs::write(full_path, contents.replacen(old, new, 1)).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })
+    }
+
+    /// Reads `relative_path`, which may resolve into the worktree or any
+    /// of `extra_read_only_roots`.
+    pub fn read_file(&self, relative_path: &str) -> Result<String, ToolRefusal> {
+        self.check_scope(relative_path)?;
+        let full_path = scope::resolve(&self.worktree, relative_path, &self.extra_read_only_roots)?;
+        std::fs::read_to_string(full_path).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })
+    }
+
+    /// Reads `relative_path` as an image (e.g. a rendered diagram or a
+    /// failing snapshot test) for a loop to attach to its next LLM
+    /// request, resolving the same way `read_file` does.
+    pub fn read_image(&self, relative_path: &str) -> Result<MessageContent, ToolRefusal> {
+        self.check_scope(relative_path)?;
+        let full_path = scope::resolve(&self.worktree, relative_path, &self.extra_read_only_roots)?;
+        let data = std::fs::read(&full_path).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })?;
+        let media_type = image_media_type(&full_path).ok_or_else(|| ToolRefusal {
+            reason: format!("{relative_path} does not have a recognized image extension"),
+            alert: None,
+        })?;
+        Ok(MessageContent::Image { media_type: media_type.to_string(), data })
+    }
+
+    /// Runs `command` in the worktree, refusing if it's denied by the
+    /// command policy or names a protected path directly.
+    pub fn run_command(&mut self, command: &str) -> Result<std::process::Output, ToolRefusal> {
+        self.check_scope_command(command)?;
+        if let Some((reason, alert)) = self.command_policy.evaluate(self.loop_id, command) {
+            return Err(ToolRefusal { reason, alert: Some(alert) });
+        }
+        if let Some(refusal) = self.protected_paths.check_command(self.loop_id, command) {
+            return Err(refusal);
+        }
+        Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(&self.worktree)
+            .envs(&self.resolved_env)
+            .output()
+            .map_err(|error| ToolRefusal {
+                reason: error.to_string(),
+                alert: None,
+            })
+    }
+
+    /// Captures the current worktree diff for tagging as a named
+    /// [`crate::checkpoint::Checkpoint`]. The caller attaches the name,
+    /// current iteration, and gate results once it persists the result
+    /// onto the `LoopRecord` — this only deals with the worktree itself.
+    pub fn checkpoint(&self) -> Result<String, ToolRefusal> {
+        crate::patch::capture_diff(&self.worktree).map_err(|error| ToolRefusal {
+            reason: error.to_string(),
+            alert: None,
+        })
+    }
+}
+
+/// The media type `read_image` reports for a path's extension, or `None`
+/// if it isn't a recognized image format.
+fn image_media_type(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_file_refuses_a_protected_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf()).with_protected_paths(vec!["migrations/*".to_string()]);
+        let result = ctx.write_file("migrations/0001_init.sql", "alter table");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_file_succeeds_for_an_unprotected_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf());
+        ctx.write_file("lib.rs", "fn main() {}").unwrap();
+        assert!(dir.path().join("lib.rs").exists());
+    }
+
+    #[test]
+    fn run_command_refuses_when_it_names_a_protected_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf()).with_protected_paths(vec!["migrations/*".to_string()]);
+        let result = ctx.run_command("rm migrations/0001_init.sql");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_file_refuses_dotdot_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf());
+        assert!(ctx.write_file("../escape.txt", "x").is_err());
+    }
+
+    #[test]
+    fn read_file_rejects_absolute_paths_even_under_an_allowed_root() {
+        let shared = tempfile::tempdir().unwrap();
+        std::fs::write(shared.path().join("shared.txt"), "shared contents").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf()).with_extra_read_only_roots(vec![shared.path().to_path_buf()]);
+        assert!(ctx.read_file(shared.path().join("shared.txt").to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn read_image_reports_the_media_type_from_the_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("screenshot.png"), [0x89, 0x50, 0x4e, 0x47]).unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf());
+        let content = ctx.read_image("screenshot.png").unwrap();
+        assert_eq!(content, MessageContent::Image { media_type: "image/png".to_string(), data: vec![0x89, 0x50, 0x4e, 0x47] });
+    }
+
+    #[test]
+    fn read_image_refuses_an_unrecognized_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not an image").unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf());
+        assert!(ctx.read_image("notes.txt").is_err());
+    }
+
+    #[test]
+    fn write_file_refuses_a_path_outside_the_configured_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("team-a")).unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf()).with_scope_path("team-a/");
+        assert!(ctx.write_file("team-b/lib.rs", "fn main() {}").is_err());
+        ctx.write_file("team-a/lib.rs", "fn main() {}").unwrap();
+        assert!(dir.path().join("team-a/lib.rs").exists());
+    }
+
+    #[test]
+    fn run_command_refuses_a_path_argument_outside_the_configured_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf()).with_scope_path("team-a/");
+        assert!(ctx.run_command("cat team-b/secret.txt").is_err());
+    }
+
+    #[test]
+    fn run_command_without_path_arguments_is_unaffected_by_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf()).with_scope_path("team-a/");
+        assert!(ctx.run_command("echo hello").is_ok());
+    }
+
+    #[test]
+    fn run_command_injects_the_resolved_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf())
+            .with_environment(HashMap::from([("GREETING".to_string(), "hello".to_string())]), Vec::new());
+        let output = ctx.run_command("echo $GREETING").unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+    }
+
+    #[test]
+    fn mask_secrets_hides_configured_secret_values() {
+        let ctx = ToolContext::new(PathBuf::from("."))
+            .with_environment(HashMap::from([("API_KEY".to_string(), "sekrit".to_string())]), vec!["API_KEY".to_string()]);
+        assert_eq!(ctx.mask_secrets("using key sekrit"), "using key ***");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_file_follows_a_symlink_into_an_allowed_extra_root() {
+        let shared = tempfile::tempdir().unwrap();
+        std::fs::write(shared.path().join("shared.txt"), "shared contents").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(shared.path().join("shared.txt"), dir.path().join("link.txt")).unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf()).with_extra_read_only_roots(vec![shared.path().to_path_buf()]);
+        assert_eq!(ctx.read_file("link.txt").unwrap(), "shared contents");
+    }
+}
@@ -0,0 +1,121 @@
+//! Protected-path policy: globs that `write_file`, `edit_file`, and
+//! `run_command` all consult before touching the worktree, so a single
+//! config applies everywhere a loop can mutate files. Paths are matched
+//! after [`super::shell_tokens`] normalization (quote-stripping, `./`
+//! prefix removal), but this is still a best-effort check, not a
+//! sandbox — see that module's doc comment for what it doesn't catch.
+
+use super::shell_tokens::{normalize_token, tokenize};
+use crate::ipc::DaemonEvent;
+use glob::Pattern;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How many refused attempts against the same path before an operator
+/// alert fires. Below this, refusal alone is enough feedback; a loop that
+/// keeps trying anyway is more likely stuck than exploring.
+const ALERT_AFTER_ATTEMPTS: u32 = 3;
+
+/// A structured refusal the LLM sees in place of a tool result, plus an
+/// operator alert if this path has now been refused enough times to be
+/// worth a human's attention.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRefusal {
+    pub reason: String,
+    pub alert: Option<DaemonEvent>,
+}
+
+/// Tracks per-path refusal counts for one loop's tool calls.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedPathPolicy {
+    pub patterns: Vec<String>,
+    refusal_counts: HashMap<String, u32>,
+}
+
+impl ProtectedPathPolicy {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self {
+            patterns,
+            refusal_counts: HashMap::new(),
+        }
+    }
+
+    /// Checks `path` against the configured patterns, after normalizing
+    /// it the same way [`check_command`](Self::check_command) normalizes
+    /// a command's tokens (stripping quoting and a leading `./`), so a
+    /// path written differently than the pattern still matches. Returns
+    /// `None` if nothing matches, i.e. the caller may proceed.
+    pub fn check(&mut self, loop_id: Uuid, path: &str) -> Option<ToolRefusal> {
+        let path = normalize_token(path);
+        let pattern = self.patterns.iter().find(|pattern| Pattern::new(pattern).is_ok_and(|glob| glob.matches(&path)))?;
+
+        let count = self.refusal_counts.entry(path.clone()).or_insert(0);
+        *count += 1;
+        let alert = (*count >= ALERT_AFTER_ATTEMPTS).then(|| DaemonEvent::OperatorAlert {
+            loop_id,
+            message: format!("repeated attempts ({count}) to modify protected path {path}"),
+        });
+
+        Some(ToolRefusal {
+            reason: format!("{path} matches the protected path pattern \"{pattern}\" and cannot be modified"),
+            alert,
+        })
+    }
+
+    /// Checks every token of a shell command, shell-token-normalized (see
+    /// [`super::shell_tokens`]), so `run_command` can refuse commands
+    /// that name a protected path directly (e.g. `rm
+    /// migrations/0007_add_column.sql`) even when quoted or given a `./`
+    /// prefix.
+    pub fn check_command(&mut self, loop_id: Uuid, command: &str) -> Option<ToolRefusal> {
+        tokenize(command).into_iter().find_map(|token| self.check(loop_id, &token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_a_matching_path() {
+        let mut policy = ProtectedPathPolicy::new(vec!["migrations/*".to_string()]);
+        let refusal = policy.check(Uuid::nil(), "migrations/0001_init.sql").unwrap();
+        assert!(refusal.reason.contains("migrations/*"));
+        assert!(refusal.alert.is_none());
+    }
+
+    #[test]
+    fn allows_an_unmatched_path() {
+        let mut policy = ProtectedPathPolicy::new(vec!["migrations/*".to_string()]);
+        assert!(policy.check(Uuid::nil(), "src/lib.rs").is_none());
+    }
+
+    #[test]
+    fn raises_an_alert_after_repeated_refusals() {
+        let mut policy = ProtectedPathPolicy::new(vec![".github/*".to_string()]);
+        let loop_id = Uuid::nil();
+        assert!(policy.check(loop_id, ".github/workflows/ci.yml").unwrap().alert.is_none());
+        assert!(policy.check(loop_id, ".github/workflows/ci.yml").unwrap().alert.is_none());
+        assert!(policy.check(loop_id, ".github/workflows/ci.yml").unwrap().alert.is_some());
+    }
+
+    #[test]
+    fn check_command_inspects_every_token() {
+        let mut policy = ProtectedPathPolicy::new(vec!["migrations/*".to_string()]);
+        let refusal = policy.check_command(Uuid::nil(), "rm migrations/0001_init.sql").unwrap();
+        assert!(refusal.reason.contains("migrations/0001_init.sql"));
+    }
+
+    #[test]
+    fn a_leading_dot_slash_does_not_dodge_the_glob() {
+        let mut policy = ProtectedPathPolicy::new(vec!["migrations/*".to_string()]);
+        assert!(policy.check(Uuid::nil(), "./migrations/0001_init.sql").is_some());
+    }
+
+    #[test]
+    fn a_quoted_token_does_not_dodge_the_glob() {
+        let mut policy = ProtectedPathPolicy::new(vec!["migrations/*".to_string()]);
+        let refusal = policy.check_command(Uuid::nil(), "rm \"migrations/0001_init.sql\"").unwrap();
+        assert!(refusal.reason.contains("migrations/0001_init.sql"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Per-loop resource tracking and ceilings for spawned tool processes,
+//! read from `/proc` so a loop that runs away with memory or CPU can be
+//! caught and terminated with a clear feedback message instead of
+//! starving the host.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A snapshot of one process's resource usage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceUsage {
+    pub rss_bytes: u64,
+    pub cpu_seconds: f64,
+}
+
+/// Configurable ceilings a loop's tool processes must stay under. `None`
+/// means unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResourceLimits {
+    pub max_rss_bytes: Option<u64>,
+    pub max_cpu_seconds: Option<f64>,
+}
+
+/// Reads RSS and cumulative CPU time for `pid` from `/proc`. Only
+/// implemented on Linux; other platforms get an error rather than silent
+/// zero usage, since a silently-unenforced limit is worse than a loud one.
+pub fn read_usage(pid: u32) -> anyhow::Result<ResourceUsage> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string(format!("/proc/{pid}/status"))?;
+        let rss_kb = status
+            .lines()
+            .find(|line| line.starts_with("VmRSS:"))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))?;
+        // Fields after the comm field (which may itself contain spaces)
+        // are whitespace-separated starting from state at index 0; utime
+        // and stime are fields 14 and 15 overall, i.e. indices 11 and 12
+        // here.
+        let fields: Vec<&str> = stat.rsplit(')').next().unwrap_or("").split_whitespace().collect();
+        let utime = fields.get(11).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+        let stime = fields.get(12).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+        // SAFETY: _SC_CLK_TCK is a simple sysconf query with no preconditions.
+        let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+
+        Ok(ResourceUsage {
+            rss_bytes: rss_kb * 1024,
+            cpu_seconds: (utime + stime) as f64 / ticks_per_sec,
+        })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = pid;
+        anyhow::bail!("resource usage reading is only implemented on Linux")
+    }
+}
+
+/// Returns a human-readable reason `usage` breaches `limits`, or `None`
+/// if it's within every configured ceiling.
+pub fn exceeding_limit(usage: ResourceUsage, limits: ResourceLimits) -> Option<String> {
+    if let Some(max_rss) = limits.max_rss_bytes {
+        if usage.rss_bytes > max_rss {
+            return Some(format!("memory usage {} bytes exceeds the {max_rss} byte limit", usage.rss_bytes));
+        }
+    }
+    if let Some(max_cpu) = limits.max_cpu_seconds {
+        if usage.cpu_seconds > max_cpu {
+            return Some(format!("cpu usage {:.1}s exceeds the {max_cpu:.1}s limit", usage.cpu_seconds));
+        }
+    }
+    None
+}
+
+/// The most recently observed resource usage for every loop with a tool
+/// process running, consulted by `loop.get` and metrics reporting.
+#[derive(Debug, Default)]
+pub struct LoopResourceTracker {
+    usage: HashMap<Uuid, ResourceUsage>,
+}
+
+impl LoopResourceTracker {
+    pub fn record(&mut self, loop_id: Uuid, usage: ResourceUsage) {
+        self.usage.insert(loop_id, usage);
+    }
+
+    pub fn usage(&self, loop_id: Uuid) -> Option<ResourceUsage> {
+        self.usage.get(&loop_id).copied()
+    }
+
+    pub fn forget(&mut self, loop_id: Uuid) {
+        self.usage.remove(&loop_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn reads_nonzero_rss_for_the_current_process() {
+        let usage = read_usage(std::process::id()).unwrap();
+        assert!(usage.rss_bytes > 0);
+    }
+
+    #[test]
+    fn exceeding_limit_reports_the_first_breached_ceiling() {
+        let usage = ResourceUsage { rss_bytes: 2_000_000_000, cpu_seconds: 1.0 };
+        let limits = ResourceLimits { max_rss_bytes: Some(1_000_000_000), max_cpu_seconds: None };
+        assert!(exceeding_limit(usage, limits).unwrap().contains("memory"));
+    }
+
+    #[test]
+    fn exceeding_limit_is_none_when_within_every_ceiling() {
+        let usage = ResourceUsage { rss_bytes: 100, cpu_seconds: 1.0 };
+        let limits = ResourceLimits { max_rss_bytes: Some(1_000), max_cpu_seconds: Some(10.0) };
+        assert!(exceeding_limit(usage, limits).is_none());
+    }
+
+    #[test]
+    fn tracker_round_trips_recorded_usage() {
+        let mut tracker = LoopResourceTracker::default();
+        let loop_id = Uuid::new_v4();
+        let usage = ResourceUsage { rss_bytes: 42, cpu_seconds: 0.5 };
+        tracker.record(loop_id, usage);
+        assert_eq!(tracker.usage(loop_id), Some(usage));
+        tracker.forget(loop_id);
+        assert_eq!(tracker.usage(loop_id), None);
+    }
+}
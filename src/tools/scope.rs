@@ -0,0 +1,118 @@
+//! Filesystem scope enforcement: every path a tool touches is canonicalized
+//! and checked to stay inside the worktree (or an explicitly allowed
+//! read-only root), so a symlink or `..` traversal can't walk a tool call
+//! out of its sandbox.
+
+use crate::tools::ToolRefusal;
+use std::path::{Path, PathBuf};
+
+/// Canonicalizes `relative_path` against `root` without requiring the
+/// final component to exist yet (needed for writes to new files), by
+/// canonicalizing the nearest existing ancestor and reattaching the rest.
+fn canonicalize_under(root: &Path, relative_path: &str) -> std::io::Result<PathBuf> {
+    let candidate = root.join(relative_path);
+    let mut existing = candidate.clone();
+    let mut remainder = Vec::new();
+    while !existing.exists() {
+        let Some(name) = existing.file_name() else { break };
+        remainder.push(name.to_owned());
+        let Some(parent) = existing.parent() else { break };
+        existing = parent.to_path_buf();
+    }
+    let mut canonical = existing.canonicalize()?;
+    for part in remainder.into_iter().rev() {
+        canonical.push(part);
+    }
+    Ok(canonical)
+}
+
+/// Resolves `relative_path` against `worktree`, refusing absolute paths
+/// and anything that canonicalizes outside `worktree` or `extra_roots`.
+/// `extra_roots` should only ever be consulted for read access — callers
+/// that mutate the filesystem should pass an empty slice.
+pub fn resolve(worktree: &Path, relative_path: &str, extra_roots: &[PathBuf]) -> Result<PathBuf, ToolRefusal> {
+    if Path::new(relative_path).is_absolute() {
+        return Err(ToolRefusal {
+            reason: format!("{relative_path} is an absolute path; only paths relative to the worktree are allowed"),
+            alert: None,
+        });
+    }
+
+    let worktree_canonical = worktree.canonicalize().map_err(|error| ToolRefusal {
+        reason: format!("failed to canonicalize worktree: {error}"),
+        alert: None,
+    })?;
+
+    let canonical = canonicalize_under(worktree, relative_path).map_err(|error| ToolRefusal {
+        reason: format!("failed to resolve {relative_path}: {error}"),
+        alert: None,
+    })?;
+
+    if canonical.starts_with(&worktree_canonical) {
+        return Ok(canonical);
+    }
+
+    for root in extra_roots {
+        if let Ok(root_canonical) = root.canonicalize() {
+            if canonical.starts_with(&root_canonical) {
+                return Ok(canonical);
+            }
+        }
+    }
+
+    Err(ToolRefusal {
+        reason: format!("{relative_path} resolves outside the worktree"),
+        alert: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_an_ordinary_path_inside_the_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hi").unwrap();
+        let resolved = resolve(dir.path(), "a.txt", &[]).unwrap();
+        assert!(resolved.starts_with(dir.path().canonicalize().unwrap()));
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(resolve(dir.path(), "/etc/passwd", &[]).is_err());
+    }
+
+    #[test]
+    fn rejects_dotdot_traversal_out_of_the_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(resolve(dir.path(), "../../etc/passwd", &[]).is_err());
+    }
+
+    #[test]
+    fn rejects_a_symlink_that_escapes_the_worktree() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "top secret").unwrap();
+
+        let worktree = tempfile::tempdir().unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(outside.path().join("secret.txt"), worktree.path().join("link.txt")).unwrap();
+
+        #[cfg(unix)]
+        assert!(resolve(worktree.path(), "link.txt", &[]).is_err());
+    }
+
+    #[test]
+    fn allows_a_path_under_an_explicitly_permitted_extra_root() {
+        let extra = tempfile::tempdir().unwrap();
+        std::fs::write(extra.path().join("shared.txt"), "shared").unwrap();
+
+        let worktree = tempfile::tempdir().unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(extra.path().join("shared.txt"), worktree.path().join("link.txt")).unwrap();
+
+        #[cfg(unix)]
+        assert!(resolve(worktree.path(), "link.txt", &[extra.path().to_path_buf()]).is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+//! Best-effort shell-aware tokenization shared by [`super::protected_paths`]
+//! and [`super::command_policy`], so a path or binary name can't dodge a
+//! glob or denylist match just by being written differently (extra
+//! whitespace, a quoted token, a `./` prefix) without changing what it
+//! does.
+//!
+//! This is not a shell parser: it doesn't resolve variable expansion,
+//! command substitution, or multi-command separators (`&&`, `;`, `|`).
+//! A command built from a shell variable or chained through those still
+//! evades it; the policies that use this are a best-effort safety net,
+//! not a sandbox.
+
+/// Splits `command` on whitespace, then strips every leading and trailing
+/// `'`/`"` character (paired or not) and a leading `./` from each token, so
+/// `"migrations/x.sql"` and `./migrations/x.sql` normalize to the same
+/// token as `migrations/x.sql`.
+pub(crate) fn tokenize(command: &str) -> Vec<String> {
+    command.split_whitespace().map(normalize_token).collect()
+}
+
+/// Re-joins [`tokenize`]'s tokens with single spaces, collapsing
+/// whitespace runs and stripping quoting/`./` prefixes along the way, so
+/// a substring check against the result can't be dodged by `rm  -rf`
+/// becoming `rm -rf` or `'rm' '-rf'` becoming `rm -rf`.
+pub(crate) fn normalize_command(command: &str) -> String {
+    tokenize(command).join(" ")
+}
+
+/// Strips every leading and trailing `'`/`"` character (paired or not)
+/// and a leading `./` from a single token.
+pub(crate) fn normalize_token(token: &str) -> String {
+    let unquoted = token.trim_matches(|c| c == '\'' || c == '"');
+    unquoted.strip_prefix("./").unwrap_or(unquoted).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_strips_surrounding_quotes() {
+        assert_eq!(tokenize("rm \"migrations/x.sql\""), vec!["rm", "migrations/x.sql"]);
+    }
+
+    #[test]
+    fn tokenize_strips_a_leading_dot_slash() {
+        assert_eq!(tokenize("rm ./migrations/x.sql"), vec!["rm", "migrations/x.sql"]);
+    }
+
+    #[test]
+    fn normalize_command_collapses_repeated_spaces() {
+        assert_eq!(normalize_command("rm  -rf   /"), "rm -rf /");
+    }
+
+    #[test]
+    fn normalize_command_strips_quoting_around_each_token() {
+        assert_eq!(normalize_command("'rm' '-rf' /"), "rm -rf /");
+    }
+}
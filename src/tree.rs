@@ -0,0 +1,165 @@
+//! Renders a loop's hierarchy as a Mermaid or Graphviz diagram, for
+//! embedding in PR descriptions and docs. Pure rendering over
+//! [`crate::domain::LoopRecord`]s already fetched from a
+//! [`crate::storage::Storage`] impl, so `loopr tree` doesn't need a
+//! daemon connection.
+
+use crate::domain::LoopRecord;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeFormat {
+    Mermaid,
+    Dot,
+}
+
+/// A short, diagram-safe node id for `id`, since neither Mermaid nor dot
+/// node ids may start with a digit or contain dashes.
+fn node_id(id: Uuid) -> String {
+    format!("n{}", id.simple())
+}
+
+fn label(record: &LoopRecord) -> String {
+    match &record.estimate {
+        Some(estimate) => format!(
+            "{} ({:?}, {:?}, ~{:.0}min/${:.2})",
+            record.description, record.loop_type, record.status, estimate.predicted_minutes, estimate.predicted_cost_usd
+        ),
+        None => format!("{} ({:?}, {:?})", record.description, record.loop_type, record.status),
+    }
+}
+
+/// Every record reachable from `root` (inclusive) by following
+/// `parent_id` downward, within `records`.
+fn subtree(records: &[LoopRecord], root: Uuid) -> Vec<&LoopRecord> {
+    let mut ids = vec![root];
+    let mut frontier = vec![root];
+    while let Some(parent) = frontier.pop() {
+        for record in records {
+            if record.parent_id == Some(parent) && !ids.contains(&record.id) {
+                ids.push(record.id);
+                frontier.push(record.id);
+            }
+        }
+    }
+    records.iter().filter(|record| ids.contains(&record.id)).collect()
+}
+
+fn render_mermaid(nodes: &[&LoopRecord]) -> String {
+    let mut out = String::from("graph TD\n");
+    for node in nodes {
+        out.push_str(&format!("    {}[\"{}\"]\n", node_id(node.id), label(node).replace('"', "'")));
+    }
+    for node in nodes {
+        if let Some(parent_id) = node.parent_id {
+            if nodes.iter().any(|candidate| candidate.id == parent_id) {
+                out.push_str(&format!("    {} --> {}\n", node_id(parent_id), node_id(node.id)));
+            }
+        }
+    }
+    out
+}
+
+fn render_dot(nodes: &[&LoopRecord]) -> String {
+    let mut out = String::from("digraph tree {\n");
+    for node in nodes {
+        out.push_str(&format!("    \"{}\" [label=\"{}\"];\n", node_id(node.id), label(node).replace('"', "'")));
+    }
+    for node in nodes {
+        if let Some(parent_id) = node.parent_id {
+            if nodes.iter().any(|candidate| candidate.id == parent_id) {
+                out.push_str(&format!("    \"{}\" -> \"{}\";\n", node_id(parent_id), node_id(node.id)));
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders `root`'s subtree out of `records` in `format`. Records not
+/// reachable from `root` are ignored, so the caller can pass every loop
+/// the storage layer knows about without pre-filtering.
+pub fn render(records: &[LoopRecord], root: Uuid, format: TreeFormat) -> anyhow::Result<String> {
+    if !records.iter().any(|record| record.id == root) {
+        anyhow::bail!("no loop with id {root}");
+    }
+    let nodes = subtree(records, root);
+    Ok(match format {
+        TreeFormat::Mermaid => render_mermaid(&nodes),
+        TreeFormat::Dot => render_dot(&nodes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{LoopRecord, LoopType};
+
+    fn tree() -> (LoopRecord, LoopRecord, LoopRecord) {
+        let plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        let spec = LoopRecord::new(LoopType::Spec, Some(plan.id), "auth spec");
+        let phase = LoopRecord::new(LoopType::Phase, Some(spec.id), "session tokens phase");
+        (plan, spec, phase)
+    }
+
+    #[test]
+    fn mermaid_includes_every_descendant_and_edge() {
+        let (plan, spec, phase) = tree();
+        let plan_id = plan.id;
+        let records = vec![plan, spec, phase];
+        let rendered = render(&records, plan_id, TreeFormat::Mermaid).unwrap();
+        assert!(rendered.starts_with("graph TD\n"));
+        assert!(rendered.contains("ship login flow"));
+        assert!(rendered.contains("auth spec"));
+        assert!(rendered.contains("session tokens phase"));
+        assert!(rendered.contains("-->"));
+    }
+
+    #[test]
+    fn dot_includes_every_descendant_and_edge() {
+        let (plan, spec, phase) = tree();
+        let plan_id = plan.id;
+        let records = vec![plan, spec, phase];
+        let rendered = render(&records, plan_id, TreeFormat::Dot).unwrap();
+        assert!(rendered.starts_with("digraph tree {\n"));
+        assert!(rendered.contains("ship login flow"));
+        assert!(rendered.contains("->"));
+        assert!(rendered.ends_with("}\n"));
+    }
+
+    #[test]
+    fn a_leaf_root_renders_with_no_edges() {
+        let (_, _, phase) = tree();
+        let phase_id = phase.id;
+        let records = vec![phase];
+        let rendered = render(&records, phase_id, TreeFormat::Mermaid).unwrap();
+        assert!(!rendered.contains("-->"));
+    }
+
+    #[test]
+    fn an_unrelated_sibling_tree_is_excluded() {
+        let (plan, spec, phase) = tree();
+        let plan_id = plan.id;
+        let other_plan = LoopRecord::new(LoopType::Plan, None, "unrelated plan");
+        let records = vec![plan, spec, phase, other_plan];
+        let rendered = render(&records, plan_id, TreeFormat::Mermaid).unwrap();
+        assert!(!rendered.contains("unrelated plan"));
+    }
+
+    #[test]
+    fn an_unknown_root_is_an_error() {
+        let err = render(&[], Uuid::new_v4(), TreeFormat::Mermaid).unwrap_err();
+        assert!(err.to_string().contains("no loop with id"));
+    }
+
+    #[test]
+    fn a_plan_with_an_estimate_shows_predicted_minutes_and_cost() {
+        use crate::estimate::PlanEstimate;
+        let mut plan = LoopRecord::new(LoopType::Plan, None, "ship login flow");
+        plan.estimate = Some(PlanEstimate { phase_count: 2, predicted_iterations: 6.0, predicted_minutes: 24.0, predicted_cost_usd: 1.5 });
+        let plan_id = plan.id;
+        let records = vec![plan];
+        let rendered = render(&records, plan_id, TreeFormat::Mermaid).unwrap();
+        assert!(rendered.contains("~24min/$1.50"));
+    }
+}
@@ -0,0 +1,519 @@
+//! Chat input handling for the TUI: `/`-prefixed slash commands and tab
+//! completion over loop ids and command names.
+
+use crate::domain::{LoopRecord, LoopStatus};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// A `/`-prefixed command recognized by the chat input, parsed client-side
+/// before anything is sent over IPC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashCommand {
+    Plan(String),
+    Status(String),
+    Pause(String),
+    Cost,
+    Clear,
+    Model(String),
+    Temp(String),
+    /// An operator note for the selected loop's next iteration, e.g. "use
+    /// the existing AuthService, don't create a new one"; see
+    /// [`crate::guidance::add_guidance`].
+    Guidance(String),
+    /// A worktree-relative path to pin into the selected loop's prompt;
+    /// see [`crate::pins::render_pinned_sections`].
+    Pin(String),
+    /// Started with `/` but didn't match a known command name.
+    Unknown(String),
+}
+
+const COMMAND_NAMES: &[&str] = &["/plan", "/status", "/pause", "/cost", "/clear", "/model", "/temp", "/guidance", "/pin"];
+
+/// Parses chat input into a [`SlashCommand`] if it starts with `/`, or
+/// `None` if it's a plain message destined for `chat.send`.
+pub fn parse_slash_command(input: &str) -> Option<SlashCommand> {
+    let input = input.trim();
+    if !input.starts_with('/') {
+        return None;
+    }
+
+    let mut parts = input.splitn(2, char::is_whitespace);
+    let name = parts.next().unwrap_or("");
+    let rest = parts.next().unwrap_or("").trim().to_string();
+
+    Some(match name {
+        "/plan" => SlashCommand::Plan(rest),
+        "/status" => SlashCommand::Status(rest),
+        "/pause" => SlashCommand::Pause(rest),
+        "/cost" => SlashCommand::Cost,
+        "/clear" => SlashCommand::Clear,
+        "/model" => SlashCommand::Model(rest),
+        "/temp" => SlashCommand::Temp(rest),
+        "/guidance" => SlashCommand::Guidance(rest),
+        "/pin" => SlashCommand::Pin(rest),
+        _ => SlashCommand::Unknown(input.to_string()),
+    })
+}
+
+/// Tab-completion candidates for the current input: command names when
+/// completing the first word, loop id prefixes when completing an
+/// argument to a command that takes one.
+pub fn complete(input: &str, known_loop_ids: &[String]) -> Vec<String> {
+    let input = input.trim_start();
+    if !input.starts_with('/') {
+        return Vec::new();
+    }
+
+    match input.split_once(char::is_whitespace) {
+        None => COMMAND_NAMES.iter().filter(|name| name.starts_with(input)).map(|s| s.to_string()).collect(),
+        Some((_, arg)) => known_loop_ids.iter().filter(|id| id.starts_with(arg)).cloned().collect(),
+    }
+}
+
+/// The chat input box's buffered state, separate from rendering so the
+/// parsing/completion logic above can be unit tested without a terminal.
+#[derive(Debug, Clone, Default)]
+pub struct ChatInput {
+    pub buffer: String,
+}
+
+impl ChatInput {
+    pub fn push(&mut self, c: char) {
+        self.buffer.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.buffer.pop();
+    }
+
+    pub fn take(&mut self) -> String {
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+/// Context-window usage shown in the status bar, derived from the active
+/// conversation's estimated token count against the model's limit, so an
+/// operator can see compaction coming before it happens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextUsage {
+    pub used_tokens: usize,
+    pub limit_tokens: usize,
+}
+
+impl ContextUsage {
+    pub fn percent_used(&self) -> f32 {
+        if self.limit_tokens == 0 {
+            return 0.0;
+        }
+        (self.used_tokens as f32 / self.limit_tokens as f32) * 100.0
+    }
+
+    /// Whether the status bar should warn that compaction is imminent.
+    pub fn nearing_limit(&self) -> bool {
+        self.percent_used() >= 80.0
+    }
+}
+
+/// One entry in the session picker, mirroring the fields of a
+/// `crate::storage::ChatSessionRecord` the TUI actually renders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionEntry {
+    pub id: String,
+    pub name: String,
+}
+
+/// The session picker's navigable state: the known sessions and which
+/// one is currently highlighted, separate from rendering so switching
+/// logic can be unit tested without a terminal.
+#[derive(Debug, Clone, Default)]
+pub struct SessionPicker {
+    pub sessions: Vec<SessionEntry>,
+    pub selected: usize,
+}
+
+impl SessionPicker {
+    pub fn new(sessions: Vec<SessionEntry>) -> Self {
+        Self { sessions, selected: 0 }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.sessions.is_empty() {
+            self.selected = (self.selected + 1) % self.sessions.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.sessions.is_empty() {
+            self.selected = (self.selected + self.sessions.len() - 1) % self.sessions.len();
+        }
+    }
+
+    /// The id of the session the picker would switch to if confirmed now.
+    pub fn selected_id(&self) -> Option<&str> {
+        self.sessions.get(self.selected).map(|s| s.id.as_str())
+    }
+}
+
+/// One loop awaiting approval, as shown in the approval queue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalEntry {
+    pub loop_id: Uuid,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    /// The plan's predicted iterations, duration, and cost, so an
+    /// operator can weigh an approval against what it's expected to cost
+    /// before clearing it; see [`crate::estimate::PlanEstimate`].
+    pub estimate: Option<crate::estimate::PlanEstimate>,
+}
+
+/// An operator's resolution of an approval-queue entry, triggered by the
+/// `a`/`r`/`i`/`s` keybindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalAction {
+    Approve,
+    Reject { feedback: String },
+    Iterate,
+    Skip,
+}
+
+/// Maps an approval-queue keypress to the action it triggers. `r`
+/// (reject) takes the already-typed feedback text, since it's collected
+/// in a follow-up prompt rather than inline.
+pub fn parse_approval_key(key: char, feedback: &str) -> Option<ApprovalAction> {
+    match key {
+        'a' => Some(ApprovalAction::Approve),
+        'r' => Some(ApprovalAction::Reject { feedback: feedback.to_string() }),
+        'i' => Some(ApprovalAction::Iterate),
+        's' => Some(ApprovalAction::Skip),
+        _ => None,
+    }
+}
+
+/// Every loop currently `AwaitingApproval`, oldest first, replacing the
+/// old single `pending_approval` slot so an operator can triage a backlog
+/// of approvals instead of handling them one at a time in creation order.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalQueue {
+    pub entries: Vec<ApprovalEntry>,
+    pub selected: usize,
+}
+
+impl ApprovalQueue {
+    /// Builds the queue from every loop record, regardless of type or
+    /// nesting, filtered to `AwaitingApproval` and sorted oldest first.
+    pub fn from_loops(loops: &[LoopRecord]) -> Self {
+        let mut entries: Vec<ApprovalEntry> = loops
+            .iter()
+            .filter(|l| l.status == LoopStatus::AwaitingApproval)
+            .map(|l| ApprovalEntry { loop_id: l.id, description: l.description.clone(), created_at: l.created_at, estimate: l.estimate })
+            .collect();
+        entries.sort_by_key(|e| e.created_at);
+        Self { entries, selected: 0 }
+    }
+
+    /// The count shown on the Approval tab's badge.
+    pub fn badge_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn selected_entry(&self) -> Option<&ApprovalEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + 1) % self.entries.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = (self.selected + self.entries.len() - 1) % self.entries.len();
+        }
+    }
+
+    /// Removes the selected entry once it's been resolved (approved,
+    /// rejected, iterated, or skipped), clamping the selection to the
+    /// remaining entries.
+    pub fn resolve_selected(&mut self) -> Option<ApprovalEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let resolved = self.entries.remove(self.selected);
+        if self.selected >= self.entries.len() {
+            self.selected = self.entries.len().saturating_sub(1);
+        }
+        Some(resolved)
+    }
+}
+
+/// Renders a [`crate::status::StatusSnapshot`] into the status bar's
+/// segments, left to right, as plain text the terminal backend joins
+/// with separators. Kept as a standalone function (rather than a method
+/// on `StatusSnapshot`) since formatting is a TUI display concern, not
+/// part of the status domain type.
+pub fn status_bar_segments(snapshot: &crate::status::StatusSnapshot) -> Vec<String> {
+    use crate::status::DaemonConnectionState;
+
+    let mut segments = vec![match snapshot.connection {
+        DaemonConnectionState::Connected => "daemon: connected".to_string(),
+        DaemonConnectionState::Reconnecting => "daemon: reconnecting".to_string(),
+        DaemonConnectionState::Disconnected => "daemon: disconnected".to_string(),
+    }];
+
+    segments.push(format!("{} active / {} queued", snapshot.active_loops, snapshot.queued_loops));
+
+    if let Some(secs) = snapshot.rate_limit_backoff_secs {
+        segments.push(format!("rate limited: {secs}s"));
+    }
+
+    segments.push(format!("${:.2} this session", snapshot.session_cost_usd));
+
+    segments.push(format!("disk: {:.0}%", snapshot.disk_quota.percent_used()));
+
+    segments
+}
+
+/// The line shown for one entry in the approval queue: its description,
+/// plus the plan's predicted duration and cost when one was computed.
+pub fn render_approval_entry(entry: &ApprovalEntry) -> String {
+    match &entry.estimate {
+        Some(estimate) => format!("{} (~{:.0}min, ~${:.2})", entry.description, estimate.predicted_minutes, estimate.predicted_cost_usd),
+        None => entry.description.clone(),
+    }
+}
+
+/// One line per gate from the selected loop's last validation run, for
+/// the checklist shown under the loop in the TUI: a pass/fail mark, the
+/// gate name, its duration, and (for a failure) the first failure line.
+pub fn render_gate_checklist(gate_results: &[crate::validation::GateSummary]) -> Vec<String> {
+    gate_results
+        .iter()
+        .map(|gate| {
+            let mark = if gate.passed { "✓" } else { "✗" };
+            let mut line = format!("{mark} {} ({}ms)", gate.name, gate.duration_ms);
+            if let Some(first_failure_line) = &gate.first_failure_line {
+                line.push_str(&format!(" — {first_failure_line}"));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Tracks an in-flight streamed chat reply: the accumulated text so far
+/// and whether the stream has finished, so the view can show a typing
+/// indicator until `done` and offer a cancel key while streaming.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingReply {
+    pub text: String,
+    pub done: bool,
+}
+
+impl StreamingReply {
+    pub fn apply_chunk(&mut self, chunk: &crate::llm::StreamChunk) {
+        self.text.push_str(&chunk.text);
+        self.done = chunk.done;
+    }
+
+    /// Whether the typing indicator should currently be shown.
+    pub fn is_typing(&self) -> bool {
+        !self.done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plan_command_with_argument() {
+        assert_eq!(parse_slash_command("/plan add OAuth login"), Some(SlashCommand::Plan("add OAuth login".into())));
+    }
+
+    #[test]
+    fn parses_argument_free_commands() {
+        assert_eq!(parse_slash_command("/cost"), Some(SlashCommand::Cost));
+        assert_eq!(parse_slash_command("/clear"), Some(SlashCommand::Clear));
+    }
+
+    #[test]
+    fn parses_guidance_command_with_argument() {
+        assert_eq!(
+            parse_slash_command("/guidance use the existing AuthService, don't create a new one"),
+            Some(SlashCommand::Guidance("use the existing AuthService, don't create a new one".into()))
+        );
+    }
+
+    #[test]
+    fn parses_pin_command_with_argument() {
+        assert_eq!(parse_slash_command("/pin src/auth/service.rs"), Some(SlashCommand::Pin("src/auth/service.rs".into())));
+    }
+
+    #[test]
+    fn plain_message_is_not_a_command() {
+        assert_eq!(parse_slash_command("just chatting"), None);
+    }
+
+    #[test]
+    fn completes_command_names() {
+        let completions = complete("/pl", &[]);
+        assert_eq!(completions, vec!["/plan".to_string()]);
+    }
+
+    #[test]
+    fn completes_loop_id_arguments() {
+        let ids = vec!["a3f1".to_string(), "a3f2".to_string(), "b901".to_string()];
+        let completions = complete("/status a3f", &ids);
+        assert_eq!(completions, vec!["a3f1".to_string(), "a3f2".to_string()]);
+    }
+
+    #[test]
+    fn context_usage_reports_nearing_limit_past_eighty_percent() {
+        let usage = ContextUsage { used_tokens: 8500, limit_tokens: 10_000 };
+        assert!(usage.nearing_limit());
+        assert_eq!(usage.percent_used(), 85.0);
+    }
+
+    #[test]
+    fn context_usage_is_not_nearing_limit_well_under_it() {
+        let usage = ContextUsage { used_tokens: 100, limit_tokens: 10_000 };
+        assert!(!usage.nearing_limit());
+    }
+
+    #[test]
+    fn session_picker_wraps_around_in_both_directions() {
+        let mut picker = SessionPicker::new(vec![
+            SessionEntry { id: "a".into(), name: "first".into() },
+            SessionEntry { id: "b".into(), name: "second".into() },
+        ]);
+        assert_eq!(picker.selected_id(), Some("a"));
+        picker.select_next();
+        assert_eq!(picker.selected_id(), Some("b"));
+        picker.select_next();
+        assert_eq!(picker.selected_id(), Some("a"));
+        picker.select_previous();
+        assert_eq!(picker.selected_id(), Some("b"));
+    }
+
+    #[test]
+    fn session_picker_with_no_sessions_has_no_selection() {
+        let picker = SessionPicker::new(vec![]);
+        assert_eq!(picker.selected_id(), None);
+    }
+
+    fn awaiting_loop(description: &str, created_at: DateTime<Utc>) -> LoopRecord {
+        use crate::domain::LoopType;
+        let mut record = LoopRecord::new(LoopType::Plan, None, description);
+        record.status = LoopStatus::AwaitingApproval;
+        record.created_at = created_at;
+        record
+    }
+
+    #[test]
+    fn approval_queue_only_includes_awaiting_approval_loops_oldest_first() {
+        use crate::domain::LoopType;
+        let now = Utc::now();
+        let older = awaiting_loop("older plan", now - chrono::Duration::minutes(5));
+        let newer = awaiting_loop("newer plan", now);
+        let mut running = LoopRecord::new(LoopType::Plan, None, "running plan");
+        running.status = LoopStatus::Running;
+
+        let queue = ApprovalQueue::from_loops(&[newer.clone(), running, older.clone()]);
+        assert_eq!(queue.badge_count(), 2);
+        assert_eq!(queue.entries[0].loop_id, older.id);
+        assert_eq!(queue.entries[1].loop_id, newer.id);
+    }
+
+    #[test]
+    fn resolving_the_selected_entry_removes_it_and_clamps_selection() {
+        let now = Utc::now();
+        let a = awaiting_loop("a", now - chrono::Duration::minutes(2));
+        let b = awaiting_loop("b", now - chrono::Duration::minutes(1));
+        let mut queue = ApprovalQueue::from_loops(&[a.clone(), b.clone()]);
+        queue.selected = 1;
+
+        let resolved = queue.resolve_selected().unwrap();
+        assert_eq!(resolved.loop_id, b.id);
+        assert_eq!(queue.badge_count(), 1);
+        assert_eq!(queue.selected, 0);
+    }
+
+    #[test]
+    fn parses_every_approval_keybinding() {
+        assert_eq!(parse_approval_key('a', ""), Some(ApprovalAction::Approve));
+        assert_eq!(parse_approval_key('r', "needs more tests"), Some(ApprovalAction::Reject { feedback: "needs more tests".to_string() }));
+        assert_eq!(parse_approval_key('i', ""), Some(ApprovalAction::Iterate));
+        assert_eq!(parse_approval_key('s', ""), Some(ApprovalAction::Skip));
+        assert_eq!(parse_approval_key('x', ""), None);
+    }
+
+    #[test]
+    fn status_bar_omits_the_rate_limit_segment_when_not_backing_off() {
+        use crate::status::{DaemonConnectionState, DiskQuota, StatusSnapshot};
+        let snapshot = StatusSnapshot {
+            connection: DaemonConnectionState::Connected,
+            active_loops: 2,
+            queued_loops: 1,
+            rate_limit_backoff_secs: None,
+            session_cost_usd: 1.5,
+            disk_quota: DiskQuota { used_bytes: 50, limit_bytes: 100 },
+        };
+        let segments = status_bar_segments(&snapshot);
+        assert!(!segments.iter().any(|s| s.contains("rate limited")));
+        assert!(segments.iter().any(|s| s == "2 active / 1 queued"));
+        assert!(segments.iter().any(|s| s == "disk: 50%"));
+    }
+
+    #[test]
+    fn status_bar_shows_the_rate_limit_segment_while_backing_off() {
+        use crate::status::{DaemonConnectionState, DiskQuota, StatusSnapshot};
+        let snapshot = StatusSnapshot {
+            connection: DaemonConnectionState::Reconnecting,
+            active_loops: 0,
+            queued_loops: 0,
+            rate_limit_backoff_secs: Some(12),
+            session_cost_usd: 0.0,
+            disk_quota: DiskQuota::default(),
+        };
+        let segments = status_bar_segments(&snapshot);
+        assert!(segments.iter().any(|s| s == "rate limited: 12s"));
+        assert!(segments.iter().any(|s| s == "daemon: reconnecting"));
+    }
+
+    #[test]
+    fn gate_checklist_shows_failure_line_only_for_failing_gates() {
+        use crate::validation::GateSummary;
+        let results = vec![
+            GateSummary { name: "build".to_string(), passed: true, duration_ms: 1200, first_failure_line: None },
+            GateSummary {
+                name: "test".to_string(),
+                passed: false,
+                duration_ms: 340,
+                first_failure_line: Some("assertion failed: left == right".to_string()),
+            },
+        ];
+        let lines = render_gate_checklist(&results);
+        assert_eq!(lines[0], "✓ build (1200ms)");
+        assert_eq!(lines[1], "✗ test (340ms) — assertion failed: left == right");
+    }
+
+    #[test]
+    fn approval_entry_shows_the_estimate_when_one_was_computed() {
+        use crate::estimate::PlanEstimate;
+        let mut entry = ApprovalEntry { loop_id: Uuid::new_v4(), description: "add login flow".to_string(), created_at: Utc::now(), estimate: None };
+        assert_eq!(render_approval_entry(&entry), "add login flow");
+
+        entry.estimate = Some(PlanEstimate { phase_count: 2, predicted_iterations: 6.0, predicted_minutes: 24.0, predicted_cost_usd: 1.5 });
+        assert_eq!(render_approval_entry(&entry), "add login flow (~24min, ~$1.50)");
+    }
+
+    #[test]
+    fn streaming_reply_shows_typing_until_done() {
+        let mut reply = StreamingReply::default();
+        reply.apply_chunk(&crate::llm::StreamChunk { text: "hi".into(), done: false });
+        assert!(reply.is_typing());
+        reply.apply_chunk(&crate::llm::StreamChunk { text: " there".into(), done: true });
+        assert!(!reply.is_typing());
+        assert_eq!(reply.text, "hi there");
+    }
+}
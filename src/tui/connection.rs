@@ -0,0 +1,174 @@
+//! Tracks the TUI's connection to the daemon: exponential-backoff
+//! reconnection, a banner for the disconnected state, a read-only gate
+//! for offline browsing of cached state, and replay of the one action
+//! that was in flight when the connection dropped.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// How the backoff between reconnect attempts grows, capped so a
+/// long-dead daemon doesn't push retries out indefinitely.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self { base: Duration::seconds(1), max: Duration::seconds(30) }
+    }
+}
+
+impl ReconnectPolicy {
+    /// The delay before the `attempt`th retry (1-indexed), doubling each
+    /// time and capped at `max`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let scaled = self.base * 2i32.saturating_pow(attempt.saturating_sub(1).min(30));
+        scaled.min(self.max)
+    }
+}
+
+/// The TUI's view of its connection to the daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    /// Disconnected and waiting for `retry_at` before attempting again.
+    Reconnecting { attempt: u32, retry_at: DateTime<Utc> },
+}
+
+/// Drives reconnection and tracks the action to replay once back online,
+/// so a session outlives a daemon restart instead of erroring on every
+/// subsequent keypress.
+#[derive(Debug, Clone)]
+pub struct ConnectionTracker {
+    policy: ReconnectPolicy,
+    state: ConnectionState,
+    /// The action that was in flight when the connection dropped, so it
+    /// can be replayed exactly once after reconnecting.
+    pending_replay: Option<String>,
+}
+
+impl ConnectionTracker {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self { policy, state: ConnectionState::Connected, pending_replay: None }
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.state == ConnectionState::Connected
+    }
+
+    /// Only a connected session may send mutating actions; a disconnected
+    /// one still renders cached state but every write is refused at this
+    /// gate rather than reaching an IPC call that will just error.
+    pub fn allows_write(&self) -> bool {
+        self.is_connected()
+    }
+
+    /// The banner text to show while disconnected, or `None` when
+    /// connected and nothing needs to be shown.
+    pub fn banner(&self, now: DateTime<Utc>) -> Option<String> {
+        match self.state {
+            ConnectionState::Connected => None,
+            ConnectionState::Reconnecting { attempt, retry_at } => {
+                let retry_in = (retry_at - now).num_seconds().max(0);
+                Some(format!("disconnected from daemon — retry {attempt} in {retry_in}s (browsing cached state)"))
+            }
+        }
+    }
+
+    /// Records that the connection dropped, optionally while `action` was
+    /// in flight so it can be replayed once reconnected. Schedules the
+    /// first retry.
+    pub fn record_disconnect(&mut self, now: DateTime<Utc>, action: Option<String>) {
+        if let Some(action) = action {
+            self.pending_replay = Some(action);
+        }
+        self.state = ConnectionState::Reconnecting { attempt: 1, retry_at: now + self.policy.backoff(1) };
+    }
+
+    /// Records another failed reconnect attempt, pushing the next retry
+    /// further out per the backoff policy.
+    pub fn record_retry_failure(&mut self, now: DateTime<Utc>) {
+        if let ConnectionState::Reconnecting { attempt, .. } = self.state {
+            let next_attempt = attempt + 1;
+            self.state = ConnectionState::Reconnecting { attempt: next_attempt, retry_at: now + self.policy.backoff(next_attempt) };
+        }
+    }
+
+    /// Whether a retry is due as of `now`.
+    pub fn should_retry(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.state, ConnectionState::Reconnecting { retry_at, .. } if now >= retry_at)
+    }
+
+    /// Marks the connection restored, returning the action that was
+    /// pending when it dropped (if any) so the caller can replay it
+    /// exactly once.
+    pub fn record_reconnect(&mut self) -> Option<String> {
+        self.state = ConnectionState::Connected;
+        self.pending_replay.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps_at_max() {
+        let policy = ReconnectPolicy { base: Duration::seconds(1), max: Duration::seconds(10) };
+        assert_eq!(policy.backoff(1), Duration::seconds(1));
+        assert_eq!(policy.backoff(2), Duration::seconds(2));
+        assert_eq!(policy.backoff(3), Duration::seconds(4));
+        assert_eq!(policy.backoff(10), Duration::seconds(10));
+    }
+
+    #[test]
+    fn starts_connected_with_no_banner() {
+        let tracker = ConnectionTracker::new(ReconnectPolicy::default());
+        assert!(tracker.is_connected());
+        assert!(tracker.allows_write());
+        assert_eq!(tracker.banner(Utc::now()), None);
+    }
+
+    #[test]
+    fn disconnecting_schedules_the_first_retry_and_blocks_writes() {
+        let now = Utc::now();
+        let mut tracker = ConnectionTracker::new(ReconnectPolicy::default());
+        tracker.record_disconnect(now, None);
+        assert!(!tracker.is_connected());
+        assert!(!tracker.allows_write());
+        assert!(tracker.banner(now).unwrap().contains("disconnected"));
+        assert!(!tracker.should_retry(now));
+        assert!(tracker.should_retry(now + Duration::seconds(2)));
+    }
+
+    #[test]
+    fn repeated_failures_push_the_retry_further_out() {
+        let now = Utc::now();
+        let mut tracker = ConnectionTracker::new(ReconnectPolicy::default());
+        tracker.record_disconnect(now, None);
+        tracker.record_retry_failure(now + Duration::seconds(1));
+        match tracker.state() {
+            ConnectionState::Reconnecting { attempt, retry_at } => {
+                assert_eq!(attempt, 2);
+                assert!(retry_at > now + Duration::seconds(1));
+            }
+            other => panic!("expected Reconnecting, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reconnecting_clears_state_and_returns_the_pending_action() {
+        let now = Utc::now();
+        let mut tracker = ConnectionTracker::new(ReconnectPolicy::default());
+        tracker.record_disconnect(now, Some("approve loop-123".to_string()));
+        let replay = tracker.record_reconnect();
+        assert_eq!(replay, Some("approve loop-123".to_string()));
+        assert!(tracker.is_connected());
+        assert_eq!(tracker.record_reconnect(), None);
+    }
+}
@@ -0,0 +1,15 @@
+//! Terminal UI state and input handling. Rendering lives behind a
+//! terminal backend the daemon-less test suite doesn't need to exercise;
+//! this module covers the parts that matter to get right without a
+//! terminal: command parsing, completion, and chat input state.
+
+pub mod app;
+pub mod connection;
+pub mod timeline;
+
+pub use app::{
+    complete, parse_approval_key, parse_slash_command, status_bar_segments, ApprovalAction, ApprovalEntry, ApprovalQueue, ChatInput,
+    ContextUsage, SessionEntry, SessionPicker, SlashCommand, StreamingReply,
+};
+pub use connection::{ConnectionState, ConnectionTracker, ReconnectPolicy};
+pub use timeline::{build_timeline, render_timeline, TimelineEntry, TimelineEvent};
@@ -0,0 +1,143 @@
+//! Per-loop event timeline: a chronological list of what happened to a
+//! loop (created, iteration start/end, tool bursts), with relative
+//! timestamps and durations, for the TUI's timeline panel.
+
+use crate::domain::{FailureCategory, LoopRecord};
+use crate::storage::ToolJobRecord;
+use chrono::{DateTime, Utc};
+
+/// One thing that happened to a loop, in the order it happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineEvent {
+    Created,
+    IterationStarted { index: u32 },
+    /// `failure_category` is `None` for an iteration that passed every
+    /// gate.
+    IterationFinished { index: u32, failure_category: Option<FailureCategory> },
+    ToolJob { command: String, duration_ms: Option<u128> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    pub event: TimelineEvent,
+}
+
+/// Builds a loop's timeline from its own record plus its recorded tool
+/// jobs, sorted oldest first. Scheduling, approvals, and watchdog signals
+/// aren't persisted anywhere yet, so they're absent rather than guessed at.
+pub fn build_timeline(record: &LoopRecord, tool_jobs: &[ToolJobRecord]) -> Vec<TimelineEntry> {
+    let mut entries = vec![TimelineEntry { at: record.created_at, event: TimelineEvent::Created }];
+
+    for iteration in &record.iterations {
+        entries.push(TimelineEntry { at: iteration.started_at, event: TimelineEvent::IterationStarted { index: iteration.index } });
+        if let Some(finished_at) = iteration.finished_at {
+            entries.push(TimelineEntry {
+                at: finished_at,
+                event: TimelineEvent::IterationFinished { index: iteration.index, failure_category: iteration.failure_category },
+            });
+        }
+    }
+
+    for job in tool_jobs {
+        let at = job.finished_at.unwrap_or(job.started_at);
+        entries.push(TimelineEntry { at, event: TimelineEvent::ToolJob { command: job.command.clone(), duration_ms: job.duration_ms } });
+    }
+
+    entries.sort_by_key(|entry| entry.at);
+    entries
+}
+
+/// Formats a duration relative to `now` as a short, human-readable age
+/// ("just now", "5m ago", "3h ago", "2d ago").
+fn relative_age(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = (now - at).num_seconds().max(0);
+    if elapsed < 60 {
+        "just now".to_string()
+    } else if elapsed < 3600 {
+        format!("{}m ago", elapsed / 60)
+    } else if elapsed < 86400 {
+        format!("{}h ago", elapsed / 3600)
+    } else {
+        format!("{}d ago", elapsed / 86400)
+    }
+}
+
+/// Renders a single event's description, without its timestamp.
+fn describe(event: &TimelineEvent) -> String {
+    match event {
+        TimelineEvent::Created => "created".to_string(),
+        TimelineEvent::IterationStarted { index } => format!("iteration {index} started"),
+        TimelineEvent::IterationFinished { index, failure_category: None } => format!("iteration {index} passed"),
+        TimelineEvent::IterationFinished { index, failure_category: Some(category) } => format!("iteration {index} failed: {category:?}"),
+        TimelineEvent::ToolJob { command, duration_ms: Some(duration_ms) } => format!("ran `{command}` ({duration_ms}ms)"),
+        TimelineEvent::ToolJob { command, duration_ms: None } => format!("ran `{command}` (in progress)"),
+    }
+}
+
+/// Renders a timeline into display lines for the TUI's timeline panel,
+/// one entry per line, oldest first, with a relative timestamp computed
+/// against `now`.
+pub fn render_timeline(entries: &[TimelineEntry], now: DateTime<Utc>) -> Vec<String> {
+    entries.iter().map(|entry| format!("{}  {}", relative_age(entry.at, now), describe(&entry.event))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[test]
+    fn includes_a_created_entry_for_a_fresh_loop() {
+        let record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let timeline = build_timeline(&record, &[]);
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].event, TimelineEvent::Created);
+    }
+
+    #[test]
+    fn includes_iteration_start_and_finish_entries() {
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let mut iteration = Iteration::new(0);
+        iteration.finished_at = Some(iteration.started_at + Duration::seconds(30));
+        iteration.failure_category = Some(FailureCategory::CompileError);
+        record.iterations.push(iteration);
+
+        let timeline = build_timeline(&record, &[]);
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[1].event, TimelineEvent::IterationStarted { index: 0 });
+        assert_eq!(timeline[2].event, TimelineEvent::IterationFinished { index: 0, failure_category: Some(FailureCategory::CompileError) });
+    }
+
+    #[test]
+    fn includes_tool_job_entries_sorted_chronologically_with_other_events() {
+        let record = LoopRecord::new(LoopType::Ralph, None, "fix the bug");
+        let mut job = ToolJobRecord::started(Uuid::new_v4(), "cargo test");
+        job.started_at = record.created_at + Duration::seconds(5);
+        job.complete("ok", 200);
+        job.finished_at = Some(record.created_at + Duration::seconds(10));
+
+        let timeline = build_timeline(&record, &[job]);
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[1].event, TimelineEvent::ToolJob { command: "cargo test".to_string(), duration_ms: Some(200) });
+    }
+
+    #[test]
+    fn relative_age_buckets_by_elapsed_time() {
+        let now = Utc::now();
+        assert_eq!(relative_age(now, now), "just now");
+        assert_eq!(relative_age(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(relative_age(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(relative_age(now - Duration::days(2), now), "2d ago");
+    }
+
+    #[test]
+    fn render_timeline_includes_the_relative_age_and_description() {
+        let now = Utc::now();
+        let entries = vec![TimelineEntry { at: now - Duration::minutes(2), event: TimelineEvent::Created }];
+        let lines = render_timeline(&entries, now);
+        assert_eq!(lines, vec!["2m ago  created".to_string()]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Usage aggregation for finance chargeback: totals tokens and cost
+//! across a time window, bucketed by loop type, model, or project.
+//! Backs `loopr usage --from --to --group-by`, exported as CSV or JSON.
+
+use crate::storage::Storage;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// How a usage report buckets its rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupBy {
+    LoopType,
+    Model,
+    Project,
+}
+
+/// One bucket's totals.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageRow {
+    pub key: String,
+    pub iterations: u32,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Aggregates every iteration across `storage`'s loops whose `started_at`
+/// falls within `[from, to]`, bucketed by `group_by`. A storage backend
+/// belongs to a single project, so under [`GroupBy::Project`] every
+/// iteration falls into the one bucket named `project`.
+pub fn build_report(storage: &dyn Storage, from: DateTime<Utc>, to: DateTime<Utc>, group_by: GroupBy, project: &str) -> anyhow::Result<Vec<UsageRow>> {
+    let mut rows: HashMap<String, UsageRow> = HashMap::new();
+    for record in storage.list_loops()? {
+        for iteration in &record.iterations {
+            if iteration.started_at < from || iteration.started_at > to {
+                continue;
+            }
+            let key = match group_by {
+                GroupBy::LoopType => record.loop_type.as_str().to_string(),
+                GroupBy::Model => iteration.model.clone().unwrap_or_else(|| "unknown".to_string()),
+                GroupBy::Project => project.to_string(),
+            };
+            let row = rows.entry(key.clone()).or_insert_with(|| UsageRow { key, ..Default::default() });
+            row.iterations += 1;
+            row.input_tokens += iteration.input_tokens as u64;
+            row.output_tokens += iteration.output_tokens as u64;
+            row.cost_usd += iteration.cost_usd;
+        }
+    }
+    let mut rows: Vec<UsageRow> = rows.into_values().collect();
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(rows)
+}
+
+/// Renders `rows` as CSV with a header row, suitable for finance import.
+pub fn to_csv(rows: &[UsageRow]) -> String {
+    let mut csv = String::from("group,iterations,input_tokens,output_tokens,cost_usd\n");
+    for row in rows {
+        csv.push_str(&format!("{},{},{},{},{:.4}\n", row.key, row.iterations, row.input_tokens, row.output_tokens, row.cost_usd));
+    }
+    csv
+}
+
+/// Renders `rows` as a JSON array of objects.
+pub fn to_json(rows: &[UsageRow]) -> anyhow::Result<String> {
+    let values: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|row| {
+            serde_json::json!({
+                "group": row.key,
+                "iterations": row.iterations,
+                "input_tokens": row.input_tokens,
+                "output_tokens": row.output_tokens,
+                "cost_usd": row.cost_usd,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&values)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Iteration, LoopRecord, LoopType};
+    use crate::storage::InMemoryStorage;
+    use chrono::Duration;
+
+    fn iteration_at(now: DateTime<Utc>, model: &str, cost_usd: f64) -> Iteration {
+        let mut iteration = Iteration::new(0);
+        iteration.started_at = now;
+        iteration.model = Some(model.to_string());
+        iteration.cost_usd = cost_usd;
+        iteration.input_tokens = 100;
+        iteration.output_tokens = 50;
+        iteration
+    }
+
+    #[test]
+    fn groups_by_loop_type() {
+        let storage = InMemoryStorage::new();
+        let now = Utc::now();
+        let mut ralph = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        ralph.iterations = vec![iteration_at(now, "claude-sonnet", 1.0)];
+        let mut phase = LoopRecord::new(LoopType::Phase, None, "plan it");
+        phase.iterations = vec![iteration_at(now, "claude-sonnet", 2.0)];
+        storage.save_loop(ralph).unwrap();
+        storage.save_loop(phase).unwrap();
+
+        let rows = build_report(&storage, now - Duration::hours(1), now + Duration::hours(1), GroupBy::LoopType, "loopr").unwrap();
+        assert_eq!(rows.len(), 2);
+        let ralph_row = rows.iter().find(|r| r.key == "ralph").unwrap();
+        assert_eq!(ralph_row.cost_usd, 1.0);
+        assert_eq!(ralph_row.input_tokens, 100);
+    }
+
+    #[test]
+    fn groups_by_model() {
+        let storage = InMemoryStorage::new();
+        let now = Utc::now();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.iterations = vec![iteration_at(now, "claude-sonnet", 1.0), iteration_at(now, "claude-haiku", 0.1)];
+        storage.save_loop(record).unwrap();
+
+        let rows = build_report(&storage, now - Duration::hours(1), now + Duration::hours(1), GroupBy::Model, "loopr").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows.iter().find(|r| r.key == "claude-haiku").unwrap().cost_usd, 0.1);
+    }
+
+    #[test]
+    fn groups_by_project_into_a_single_bucket() {
+        let storage = InMemoryStorage::new();
+        let now = Utc::now();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.iterations = vec![iteration_at(now, "claude-sonnet", 1.0), iteration_at(now, "claude-haiku", 0.1)];
+        storage.save_loop(record).unwrap();
+
+        let rows = build_report(&storage, now - Duration::hours(1), now + Duration::hours(1), GroupBy::Project, "loopr").unwrap();
+        assert_eq!(rows, vec![UsageRow { key: "loopr".to_string(), iterations: 2, input_tokens: 200, output_tokens: 100, cost_usd: 1.1 }]);
+    }
+
+    #[test]
+    fn excludes_iterations_outside_the_time_window() {
+        let storage = InMemoryStorage::new();
+        let now = Utc::now();
+        let mut record = LoopRecord::new(LoopType::Ralph, None, "fix it");
+        record.iterations = vec![iteration_at(now - Duration::days(30), "claude-sonnet", 1.0)];
+        storage.save_loop(record).unwrap();
+
+        let rows = build_report(&storage, now - Duration::hours(1), now + Duration::hours(1), GroupBy::LoopType, "loopr").unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[test]
+    fn csv_export_includes_a_header_and_one_row_per_group() {
+        let rows = vec![UsageRow { key: "ralph".to_string(), iterations: 2, input_tokens: 200, output_tokens: 100, cost_usd: 1.5 }];
+        let csv = to_csv(&rows);
+        assert!(csv.starts_with("group,iterations,input_tokens,output_tokens,cost_usd\n"));
+        assert!(csv.contains("ralph,2,200,100,1.5000"));
+    }
+
+    #[test]
+    fn json_export_round_trips_the_group_key() {
+        let rows = vec![UsageRow { key: "ralph".to_string(), iterations: 2, input_tokens: 200, output_tokens: 100, cost_usd: 1.5 }];
+        let json = to_json(&rows).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["group"], "ralph");
+        assert_eq!(parsed[0]["iterations"], 2);
+    }
+}
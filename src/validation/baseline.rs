@@ -0,0 +1,98 @@
+//! Baseline failure masking: when the base branch already has failing
+//! tests or lints, a [`super::preflight_check`] run records them as a
+//! baseline, and this module subtracts them from later gate results so
+//! an iteration's feedback only contains the regressions it introduced,
+//! not pre-existing failures it can't reasonably fix on its own.
+
+use super::GateResult;
+
+/// Lines that mark a single failure each, used as the unit baseline and
+/// current output are compared line-by-line on.
+const FAILURE_MARKERS: &[&str] = &["FAILED", "error[", "error:", "warning:"];
+
+fn is_failure_line(line: &str) -> bool {
+    FAILURE_MARKERS.iter().any(|marker| line.contains(marker))
+}
+
+/// A gate's pre-existing failures, captured once from a
+/// [`super::preflight_check`] baseline run and re-captured whenever the
+/// worktree rebases onto a new base commit, so "pre-existing" stays
+/// accurate as the base branch moves.
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    pub gate_name: String,
+    lines: Vec<String>,
+}
+
+impl Baseline {
+    pub fn capture(result: &GateResult) -> Self {
+        Self {
+            gate_name: result.name.clone(),
+            lines: result.output.lines().filter(|line| is_failure_line(line)).map(str::to_string).collect(),
+        }
+    }
+}
+
+/// Masks `result`'s output against `baseline`, keeping only failure
+/// lines that weren't already present before the loop started and
+/// re-deciding `passed` from what's left. A gate left with no new
+/// failures is reported as passed, even if it still has the exact
+/// baseline failures — the loop didn't make it worse, which is all a
+/// gate can fairly ask of it.
+pub fn mask(result: &GateResult, baseline: &Baseline) -> GateResult {
+    if result.name != baseline.gate_name {
+        return result.clone();
+    }
+    let regressions: Vec<&str> = result.output.lines().filter(|line| is_failure_line(line) && !baseline.lines.iter().any(|b| b == line)).collect();
+    GateResult {
+        name: result.name.clone(),
+        passed: regressions.is_empty(),
+        output: if regressions.is_empty() { result.output.clone() } else { regressions.join("\n") },
+        duration_ms: result.duration_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate_result(name: &str, passed: bool, output: &str) -> GateResult {
+        GateResult { name: name.to_string(), passed, output: output.to_string(), duration_ms: 0 }
+    }
+
+    #[test]
+    fn a_result_with_only_baseline_failures_is_masked_to_passing() {
+        let baseline = Baseline::capture(&gate_result("tests", false, "test foo ... FAILED"));
+        let current = gate_result("tests", false, "test foo ... FAILED");
+        let masked = mask(&current, &baseline);
+        assert!(masked.passed);
+    }
+
+    #[test]
+    fn a_new_failure_alongside_the_baseline_still_fails() {
+        let baseline = Baseline::capture(&gate_result("tests", false, "test foo ... FAILED"));
+        let current = gate_result("tests", false, "test foo ... FAILED\ntest bar ... FAILED");
+        let masked = mask(&current, &baseline);
+        assert!(!masked.passed);
+        assert!(masked.output.contains("test bar"));
+        assert!(!masked.output.contains("test foo"));
+    }
+
+    #[test]
+    fn a_clean_baseline_masks_nothing() {
+        let baseline = Baseline::capture(&gate_result("tests", true, "all tests passed"));
+        let current = gate_result("tests", false, "test foo ... FAILED");
+        let masked = mask(&current, &baseline);
+        assert!(!masked.passed);
+        assert!(masked.output.contains("test foo"));
+    }
+
+    #[test]
+    fn a_mismatched_gate_name_is_left_unmasked() {
+        let baseline = Baseline::capture(&gate_result("lint", false, "warning: unused import"));
+        let current = gate_result("tests", false, "test foo ... FAILED");
+        let masked = mask(&current, &baseline);
+        assert_eq!(masked.output, current.output);
+        assert_eq!(masked.passed, current.passed);
+    }
+}
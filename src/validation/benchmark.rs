@@ -0,0 +1,93 @@
+//! Benchmark regression gate: runs configured criterion benchmarks,
+//! compares against a stored baseline, and fails with per-benchmark
+//! deltas above a threshold.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub nanoseconds: f64,
+}
+
+/// Parses criterion's `name  time: [lo mid hi ns]` lines into a name ->
+/// mid-estimate map. Criterion's human-readable output is the lowest
+/// common denominator across its output formats, so that's what this
+/// expects on stdin rather than the (also supported, but less stable)
+/// machine-readable JSON.
+pub fn parse_criterion_output(output: &str) -> std::collections::HashMap<String, BenchmarkResult> {
+    let mut results = std::collections::HashMap::new();
+    for line in output.lines() {
+        let Some((name, rest)) = line.split_once("time:") else { continue };
+        let Some(start) = rest.find('[') else { continue };
+        let Some(end) = rest.find(']') else { continue };
+        let numbers: Vec<f64> = rest[start + 1..end]
+            .split_whitespace()
+            .filter_map(|token| token.parse::<f64>().ok())
+            .collect();
+        if let Some(&mid) = numbers.get(1) {
+            results.insert(name.trim().to_string(), BenchmarkResult { nanoseconds: mid });
+        }
+    }
+    results
+}
+
+/// One benchmark's before/after comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkDelta {
+    pub name: String,
+    pub baseline_ns: f64,
+    pub current_ns: f64,
+    pub pct_change: f64,
+}
+
+/// Returns the benchmarks that regressed by more than `threshold_pct`
+/// (e.g. `5.0` for 5%) relative to `baseline`.
+pub fn regressions(
+    current: &std::collections::HashMap<String, BenchmarkResult>,
+    baseline: &std::collections::HashMap<String, BenchmarkResult>,
+    threshold_pct: f64,
+) -> Vec<BenchmarkDelta> {
+    let mut deltas = Vec::new();
+    for (name, current_result) in current {
+        let Some(baseline_result) = baseline.get(name) else { continue };
+        let pct_change = (current_result.nanoseconds - baseline_result.nanoseconds) / baseline_result.nanoseconds * 100.0;
+        if pct_change > threshold_pct {
+            deltas.push(BenchmarkDelta {
+                name: name.clone(),
+                baseline_ns: baseline_result.nanoseconds,
+                current_ns: current_result.nanoseconds,
+                pct_change,
+            });
+        }
+    }
+    deltas.sort_by(|a, b| b.pct_change.total_cmp(&a.pct_change));
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_criterion_human_readable_output() {
+        let output = "fib_20                 time:   [120.43 ns 121.00 ns 121.60 ns]\n";
+        let results = parse_criterion_output(output);
+        assert_eq!(results["fib_20"].nanoseconds, 121.00);
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let current = std::collections::HashMap::from([("fib_20".to_string(), BenchmarkResult { nanoseconds: 150.0 })]);
+        let baseline = std::collections::HashMap::from([("fib_20".to_string(), BenchmarkResult { nanoseconds: 100.0 })]);
+        let deltas = regressions(&current, &baseline, 5.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].pct_change, 50.0);
+    }
+
+    #[test]
+    fn ignores_changes_within_threshold() {
+        let current = std::collections::HashMap::from([("fib_20".to_string(), BenchmarkResult { nanoseconds: 101.0 })]);
+        let baseline = std::collections::HashMap::from([("fib_20".to_string(), BenchmarkResult { nanoseconds: 100.0 })]);
+        assert!(regressions(&current, &baseline, 5.0).is_empty());
+    }
+}
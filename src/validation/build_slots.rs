@@ -0,0 +1,100 @@
+//! Throttles expensive build/test commands across concurrently running
+//! loops, so several Ralph loops running `cargo test` at once don't
+//! thrash CPU and the cargo lock. Cheap gates (format checks, lint
+//! summaries) aren't routed through the arbiter and keep running in
+//! parallel.
+
+use std::sync::{Condvar, Mutex};
+
+/// Substrings that mark a command as expensive enough to need a build
+/// slot rather than running unthrottled.
+const EXPENSIVE_COMMAND_MARKERS: &[&str] = &["cargo build", "cargo test", "cargo bench", "cargo check"];
+
+/// Whether `command` should be routed through a [`BuildSlotArbiter`].
+pub fn is_expensive(command: &str) -> bool {
+    EXPENSIVE_COMMAND_MARKERS.iter().any(|marker| command.contains(marker))
+}
+
+/// A counting semaphore limiting how many expensive build commands may
+/// run at once, shared across every loop's [`super::CommandGate`]s.
+pub struct BuildSlotArbiter {
+    available: Mutex<usize>,
+    condvar: Condvar,
+}
+
+impl std::fmt::Debug for BuildSlotArbiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildSlotArbiter").field("available", &*self.available.lock().unwrap()).finish()
+    }
+}
+
+impl BuildSlotArbiter {
+    pub fn new(slots: usize) -> Self {
+        Self { available: Mutex::new(slots.max(1)), condvar: Condvar::new() }
+    }
+
+    /// Blocks the calling thread until a build slot is free, then holds it
+    /// until the returned guard drops.
+    pub fn acquire(&self) -> BuildSlotGuard<'_> {
+        let mut available = self.available.lock().unwrap();
+        while *available == 0 {
+            available = self.condvar.wait(available).unwrap();
+        }
+        *available -= 1;
+        BuildSlotGuard { arbiter: self }
+    }
+}
+
+/// Holds one build slot until dropped.
+pub struct BuildSlotGuard<'a> {
+    arbiter: &'a BuildSlotArbiter,
+}
+
+impl Drop for BuildSlotGuard<'_> {
+    fn drop(&mut self) {
+        let mut available = self.arbiter.available.lock().unwrap();
+        *available += 1;
+        self.arbiter.condvar.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn classifies_cargo_build_and_test_commands_as_expensive() {
+        assert!(is_expensive("cargo test --workspace"));
+        assert!(is_expensive("cargo build --release"));
+        assert!(!is_expensive("cargo fmt --check"));
+    }
+
+    #[test]
+    fn only_the_configured_number_of_slots_run_at_once() {
+        let arbiter = Arc::new(BuildSlotArbiter::new(1));
+        let active = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let max_observed = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let arbiter = arbiter.clone();
+                let active = active.clone();
+                let max_observed = max_observed.clone();
+                std::thread::spawn(move || {
+                    let _guard = arbiter.acquire();
+                    let now_active = active.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                    max_observed.fetch_max(now_active, std::sync::atomic::Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    active.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(max_observed.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
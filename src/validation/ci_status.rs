@@ -0,0 +1,93 @@
+//! An external validation gate that ingests the repo's own CI status
+//! (GitHub checks API / GitLab pipelines) instead of running a local
+//! command, so a loop can rely on the project's canonical CI rather than
+//! an approximation of it. This layer only turns a fetched set of job
+//! statuses into a [`GateResult`] and per-job [`FailureDetail`]s; pushing
+//! the branch and polling the provider's API is left to the daemon's
+//! [`crate::forge`]-based orchestration layer.
+
+use super::GateResult;
+use crate::domain::FailureCategory;
+use crate::failure::classify;
+
+/// One CI job's outcome, already fetched from the provider's API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CiJobStatus {
+    pub name: String,
+    pub passed: bool,
+    pub log_excerpt: String,
+}
+
+/// A single failing CI job, classified for feedback phrasing; see
+/// [`crate::failure::phrasing`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureDetail {
+    pub job_name: String,
+    pub log_excerpt: String,
+    pub category: FailureCategory,
+}
+
+/// Whether every job has reported a final status; a pending poll should
+/// keep waiting rather than treat missing jobs as failures.
+pub fn is_complete(jobs: &[Option<CiJobStatus>]) -> bool {
+    jobs.iter().all(Option::is_some)
+}
+
+/// Classifies every failing job's log into a [`FailureDetail`], so the
+/// next iteration's feedback can point at the specific job and reason
+/// rather than just "CI failed".
+pub fn failure_details(jobs: &[CiJobStatus]) -> Vec<FailureDetail> {
+    jobs.iter()
+        .filter(|job| !job.passed)
+        .map(|job| FailureDetail { job_name: job.name.clone(), log_excerpt: job.log_excerpt.clone(), category: classify(&job.log_excerpt) })
+        .collect()
+}
+
+/// Turns a completed set of CI job statuses into a [`GateResult`],
+/// passing only if every job passed, with each job's log concatenated
+/// into the gate's output.
+pub fn gate_result(name: impl Into<String>, jobs: &[CiJobStatus], duration_ms: u128) -> GateResult {
+    let passed = jobs.iter().all(|job| job.passed);
+    let output = jobs
+        .iter()
+        .map(|job| format!("{}: {}\n{}", job.name, if job.passed { "passed" } else { "failed" }, job.log_excerpt))
+        .collect::<Vec<_>>()
+        .join("\n");
+    GateResult { name: name.into(), passed, output, duration_ms }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(name: &str, passed: bool, log_excerpt: &str) -> CiJobStatus {
+        CiJobStatus { name: name.to_string(), passed, log_excerpt: log_excerpt.to_string() }
+    }
+
+    #[test]
+    fn incomplete_while_any_job_is_still_pending() {
+        assert!(!is_complete(&[Some(job("build", true, "")), None]));
+        assert!(is_complete(&[Some(job("build", true, "")), Some(job("test", true, ""))]));
+    }
+
+    #[test]
+    fn failure_details_only_includes_failing_jobs_classified_by_log() {
+        let jobs = vec![job("build", true, "ok"), job("test", false, "thread 'main' panicked at 'assertion failed'")];
+        let details = failure_details(&jobs);
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].job_name, "test");
+        assert_eq!(details[0].category, FailureCategory::TestAssertion);
+    }
+
+    #[test]
+    fn gate_result_passes_only_when_every_job_passed() {
+        let all_passed = vec![job("build", true, "ok"), job("test", true, "ok")];
+        let result = gate_result("ci", &all_passed, 1000);
+        assert!(result.passed);
+
+        let one_failed = vec![job("build", true, "ok"), job("test", false, "assertion failed")];
+        let result = gate_result("ci", &one_failed, 1000);
+        assert!(!result.passed);
+        assert!(result.output.contains("test: failed"));
+    }
+}
@@ -0,0 +1,25 @@
+use super::CommandGate;
+
+/// The built-in `Docs` loop type's validation pipeline: `cargo doc`
+/// warnings are a mechanical check; the `doc-links` gate runs
+/// `cargo deadlinks` and records its output but always passes; the LLM-judge
+/// accuracy rubric runs separately as a review pass (see
+/// [`crate::prompts::rule_of_five`]) since it needs the code diff rather
+/// than a shell exit code.
+pub fn docs_pipeline() -> Vec<CommandGate> {
+    vec![
+        CommandGate::new("cargo-doc-warnings", "! cargo doc --no-deps --quiet 2>&1 | tee /dev/stderr | grep -q warning"),
+        CommandGate::new("doc-links", "cargo deadlinks --dir target/doc || true"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipeline_includes_the_warnings_gate() {
+        let pipeline = docs_pipeline();
+        assert!(pipeline.iter().any(|g| g.name == "cargo-doc-warnings"));
+    }
+}
@@ -0,0 +1,209 @@
+//! Validation gates: the shell commands and checks a loop's work must
+//! pass before an iteration is accepted.
+
+mod baseline;
+mod benchmark;
+mod build_slots;
+mod ci_status;
+mod docs;
+mod preflight;
+mod workspace;
+
+pub use baseline::{mask as mask_baseline, Baseline};
+pub use benchmark::{parse_criterion_output, regressions, BenchmarkDelta, BenchmarkResult};
+pub use build_slots::{is_expensive, BuildSlotArbiter};
+pub use ci_status::{failure_details, gate_result as ci_gate_result, is_complete as ci_is_complete, CiJobStatus, FailureDetail};
+pub use docs::docs_pipeline;
+pub use preflight::{check as preflight_check, PreflightIssue};
+pub use workspace::{test_gates, touched_members, WorkspaceConfig, WorkspaceMember};
+
+use crate::failure::classify;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The outcome of running one gate against a worktree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateResult {
+    pub name: String,
+    pub passed: bool,
+    pub output: String,
+    pub duration_ms: u128,
+}
+
+/// A named shell command that must exit zero for the gate to pass.
+#[derive(Debug, Clone)]
+pub struct CommandGate {
+    pub name: String,
+    pub command: String,
+    /// When set and the command is expensive (see [`is_expensive`]), a
+    /// build slot is acquired before running and held for the duration.
+    /// Cheap commands run unthrottled even with an arbiter configured.
+    pub build_slot_arbiter: Option<Arc<BuildSlotArbiter>>,
+}
+
+impl CommandGate {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            build_slot_arbiter: None,
+        }
+    }
+
+    pub fn with_build_slot_arbiter(mut self, arbiter: Arc<BuildSlotArbiter>) -> Self {
+        self.build_slot_arbiter = Some(arbiter);
+        self
+    }
+
+    pub fn run(&self, worktree: &Path) -> anyhow::Result<GateResult> {
+        let started = Instant::now();
+        let _guard = match &self.build_slot_arbiter {
+            Some(arbiter) if is_expensive(&self.command) => Some(arbiter.acquire()),
+            _ => None,
+        };
+        let output = Command::new("sh").arg("-c").arg(&self.command).current_dir(worktree).output()?;
+        let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        Ok(GateResult {
+            name: self.name.clone(),
+            passed: output.status.success(),
+            output: combined,
+            duration_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+/// An ordered set of gates run against a worktree; stops at the first
+/// failure so the feedback points at the earliest real problem rather
+/// than a cascade of downstream ones.
+pub fn run_pipeline(gates: &[CommandGate], worktree: &Path) -> anyhow::Result<Vec<GateResult>> {
+    let mut results = Vec::new();
+    for gate in gates {
+        let result = gate.run(worktree)?;
+        let passed = result.passed;
+        results.push(result);
+        if !passed {
+            break;
+        }
+    }
+    Ok(results)
+}
+
+/// Runs a criterion benchmark command and fails if any benchmark regressed
+/// by more than `threshold_pct` against a stored baseline. Unlike
+/// [`CommandGate`], a zero exit code alone doesn't pass the gate (criterion
+/// exits zero on a regression); it passes only if the command succeeds and
+/// the gate's own comparison finds no regression.
+#[derive(Debug, Clone)]
+pub struct BenchmarkGate {
+    pub name: String,
+    pub command: String,
+    pub baseline: std::collections::HashMap<String, BenchmarkResult>,
+    pub threshold_pct: f64,
+}
+
+impl BenchmarkGate {
+    pub fn new(name: impl Into<String>, command: impl Into<String>, baseline: std::collections::HashMap<String, BenchmarkResult>, threshold_pct: f64) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            baseline,
+            threshold_pct,
+        }
+    }
+
+    pub fn run(&self, worktree: &Path) -> anyhow::Result<GateResult> {
+        let started = Instant::now();
+        let output = Command::new("sh").arg("-c").arg(&self.command).current_dir(worktree).output()?;
+        let combined = String::from_utf8_lossy(&output.stdout).into_owned();
+        let current = parse_criterion_output(&combined);
+        let deltas = regressions(&current, &self.baseline, self.threshold_pct);
+        let passed = output.status.success() && deltas.is_empty();
+        let mut report = combined;
+        for delta in &deltas {
+            report.push_str(&format!("\nregression: {} {:.1}ns -> {:.1}ns ({:+.1}%)\n", delta.name, delta.baseline_ns, delta.current_ns, delta.pct_change));
+        }
+        Ok(GateResult {
+            name: self.name.clone(),
+            passed,
+            output: report,
+            duration_ms: started.elapsed().as_millis(),
+        })
+    }
+}
+
+/// Classifies the first failing gate's output, for feedback phrasing.
+pub fn first_failure_category(results: &[GateResult]) -> Option<crate::domain::FailureCategory> {
+    results.iter().find(|r| !r.passed).map(|r| classify(&r.output))
+}
+
+/// A gate result trimmed down for display and storage on [`crate::domain::LoopRecord`]:
+/// name, pass/fail, duration, and (for a failure) just the first line of
+/// output rather than the whole captured log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GateSummary {
+    pub name: String,
+    pub passed: bool,
+    pub duration_ms: u128,
+    pub first_failure_line: Option<String>,
+}
+
+/// Summarizes a validation run's [`GateResult`]s for the loop's TUI
+/// checklist, so an operator sees at a glance which gate is blocking
+/// without opening the full gate output.
+pub fn summarize(results: &[GateResult]) -> Vec<GateSummary> {
+    results
+        .iter()
+        .map(|result| GateSummary {
+            name: result.name.clone(),
+            passed: result.passed,
+            duration_ms: result.duration_ms,
+            first_failure_line: (!result.passed).then(|| result.output.lines().next().unwrap_or_default().to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passing_command_produces_a_passed_result() {
+        let gate = CommandGate::new("echo", "exit 0");
+        let result = gate.run(Path::new(".")).unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn an_expensive_command_acquires_a_build_slot_before_running() {
+        let arbiter = Arc::new(BuildSlotArbiter::new(1));
+        let gate = CommandGate::new("tests", "echo 'running cargo test'").with_build_slot_arbiter(arbiter);
+        let result = gate.run(Path::new(".")).unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn failing_command_stops_the_pipeline() {
+        let gates = vec![CommandGate::new("fails", "exit 1"), CommandGate::new("never-runs", "exit 0")];
+        let results = run_pipeline(&gates, Path::new(".")).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed);
+    }
+
+    #[test]
+    fn benchmark_gate_fails_on_regression_beyond_threshold() {
+        let baseline = std::collections::HashMap::from([("fib_20".to_string(), BenchmarkResult { nanoseconds: 100.0 })]);
+        let gate = BenchmarkGate::new(
+            "bench",
+            "echo 'fib_20  time:   [140.0 ns 150.0 ns 160.0 ns]'",
+            baseline,
+            5.0,
+        );
+        let result = gate.run(Path::new(".")).unwrap();
+        assert!(!result.passed);
+        assert!(result.output.contains("regression: fib_20"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Pre-iteration repository state check: before a loop's first
+//! iteration starts, verify the base branch is clean, optionally
+//! up to date with origin, and that the validation command already
+//! passes on unmodified code — so a loop never chases a pre-existing
+//! failure and its feedback can tell "broken before we started" apart
+//! from a regression it introduced.
+
+use super::{CommandGate, GateResult};
+use std::path::Path;
+use std::process::Command;
+
+/// One problem found checking the repository's state before the first
+/// iteration starts.
+#[derive(Debug, Clone)]
+pub enum PreflightIssue {
+    /// `git status --porcelain` reported uncommitted changes.
+    DirtyWorktree { files: Vec<String> },
+    /// The base branch is behind its origin tracking branch.
+    BehindOrigin { commits_behind: usize },
+    /// The validation command itself failed on unmodified code; carries
+    /// the gate's own result so the failure can still be classified (see
+    /// [`crate::failure::classify`]) and shown as the baseline.
+    BaselineValidationFailed(GateResult),
+}
+
+fn dirty_files(worktree: &Path) -> anyhow::Result<Vec<String>> {
+    let output = Command::new("git").arg("status").arg("--porcelain").current_dir(worktree).output()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    Ok(text.lines().filter(|line| line.len() > 3).map(|line| line[3..].to_string()).collect())
+}
+
+/// How many commits `HEAD` is behind its upstream tracking branch.
+/// Returns `0` (not a failure) when there is no tracking branch, e.g. a
+/// fresh local-only branch.
+fn commits_behind_origin(worktree: &Path, fetch: bool) -> anyhow::Result<usize> {
+    if fetch {
+        Command::new("git").arg("fetch").arg("origin").current_dir(worktree).output()?;
+    }
+    let output = Command::new("git").arg("rev-list").arg("--count").arg("HEAD..@{u}").current_dir(worktree).output()?;
+    if !output.status.success() {
+        return Ok(0);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().parse().unwrap_or(0))
+}
+
+/// Checks `worktree`'s git state and runs `validation_gate` against
+/// unmodified code, returning every issue found. An empty result means
+/// the loop can start its first iteration on known-good ground. `fetch`
+/// controls whether origin is fetched before comparing against it, so a
+/// caller without network access can skip straight to the local
+/// comparison.
+pub fn check(worktree: &Path, validation_gate: &CommandGate, fetch: bool) -> anyhow::Result<Vec<PreflightIssue>> {
+    let mut issues = Vec::new();
+
+    let dirty = dirty_files(worktree)?;
+    if !dirty.is_empty() {
+        issues.push(PreflightIssue::DirtyWorktree { files: dirty });
+    }
+
+    let behind = commits_behind_origin(worktree, fetch)?;
+    if behind > 0 {
+        issues.push(PreflightIssue::BehindOrigin { commits_behind: behind });
+    }
+
+    let result = validation_gate.run(worktree)?;
+    if !result.passed {
+        issues.push(PreflightIssue::BaselineValidationFailed(result));
+    }
+
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(dir.path()).output().unwrap();
+        };
+        run(&["init", "-q"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "test"]);
+        std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        run(&["add", "-A"]);
+        run(&["commit", "-q", "-m", "initial"]);
+        dir
+    }
+
+    #[test]
+    fn a_clean_repo_with_a_passing_baseline_has_no_issues() {
+        let dir = init_repo();
+        let gate = CommandGate::new("baseline", "exit 0");
+        let issues = check(dir.path(), &gate, false).unwrap();
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn a_dirty_worktree_is_reported() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("untracked.txt"), "oops\n").unwrap();
+        let gate = CommandGate::new("baseline", "exit 0");
+        let issues = check(dir.path(), &gate, false).unwrap();
+        assert!(matches!(&issues[0], PreflightIssue::DirtyWorktree { files } if files == &vec!["untracked.txt".to_string()]));
+    }
+
+    #[test]
+    fn a_failing_baseline_is_reported() {
+        let dir = init_repo();
+        let gate = CommandGate::new("baseline", "exit 1");
+        let issues = check(dir.path(), &gate, false).unwrap();
+        assert!(matches!(&issues[0], PreflightIssue::BaselineValidationFailed(result) if !result.passed));
+    }
+
+    #[test]
+    fn no_tracking_branch_does_not_count_as_behind() {
+        let dir = init_repo();
+        let gate = CommandGate::new("baseline", "exit 0");
+        let issues = check(dir.path(), &gate, false).unwrap();
+        assert!(!issues.iter().any(|issue| matches!(issue, PreflightIssue::BehindOrigin { .. })));
+    }
+}
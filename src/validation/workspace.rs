@@ -0,0 +1,96 @@
+//! Cargo-workspace-aware test gating: when a project declares its
+//! workspace members under `loopr.yml`'s `workspace:` section, a loop's
+//! test gate runs `cargo test -p <member>` for just the members its diff
+//! touches instead of always paying for the repository-wide test
+//! command.
+
+use super::CommandGate;
+
+/// One member of a Cargo workspace: its package name (passed to
+/// `cargo test -p`) and the path, relative to the repository root, its
+/// crate lives under.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceMember {
+    pub name: String,
+    pub path: String,
+}
+
+/// A project's `workspace:` config section. Empty `members` means the
+/// project isn't a Cargo workspace (or hasn't declared one here yet), so
+/// [`test_gates`] always falls back to the repository-wide command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    #[serde(default)]
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// The workspace members whose path prefixes a changed file in
+/// `changed_files` falls under, in declared order.
+pub fn touched_members<'a>(config: &'a WorkspaceConfig, changed_files: &[String]) -> Vec<&'a WorkspaceMember> {
+    config.members.iter().filter(|member| changed_files.iter().any(|file| file.starts_with(&member.path))).collect()
+}
+
+/// Builds the test gates for a diff: one `cargo test -p <member>` gate
+/// per touched workspace member, or a single gate running
+/// `fallback_command` when no workspace is configured or the diff
+/// touches nothing under a known member (e.g. root-level files like
+/// `loopr.yml` itself).
+pub fn test_gates(config: &WorkspaceConfig, changed_files: &[String], fallback_command: &str) -> Vec<CommandGate> {
+    let touched = touched_members(config, changed_files);
+    if touched.is_empty() {
+        return vec![CommandGate::new("test", fallback_command)];
+    }
+    touched
+        .iter()
+        .map(|member| CommandGate::new(format!("test-{}", member.name), format!("cargo test -p {}", member.name)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WorkspaceConfig {
+        WorkspaceConfig {
+            members: vec![
+                WorkspaceMember { name: "loopr-core".to_string(), path: "crates/core/".to_string() },
+                WorkspaceMember { name: "loopr-cli".to_string(), path: "crates/cli/".to_string() },
+            ],
+        }
+    }
+
+    #[test]
+    fn touched_members_matches_by_path_prefix() {
+        let changed = vec!["crates/core/src/lib.rs".to_string()];
+        let config = config();
+        let touched = touched_members(&config, &changed);
+        assert_eq!(touched.len(), 1);
+        assert_eq!(touched[0].name, "loopr-core");
+    }
+
+    #[test]
+    fn a_diff_touching_two_members_runs_both() {
+        let changed = vec!["crates/core/src/lib.rs".to_string(), "crates/cli/src/main.rs".to_string()];
+        let gates = test_gates(&config(), &changed, "otto ci");
+        assert_eq!(gates.len(), 2);
+        assert!(gates.iter().any(|g| g.command == "cargo test -p loopr-core"));
+        assert!(gates.iter().any(|g| g.command == "cargo test -p loopr-cli"));
+    }
+
+    #[test]
+    fn a_diff_touching_no_known_member_falls_back() {
+        let changed = vec!["README.md".to_string()];
+        let gates = test_gates(&config(), &changed, "otto ci");
+        assert_eq!(gates.len(), 1);
+        assert_eq!(gates[0].command, "otto ci");
+    }
+
+    #[test]
+    fn an_unconfigured_workspace_always_falls_back() {
+        let gates = test_gates(&WorkspaceConfig::default(), &["src/lib.rs".to_string()], "otto ci");
+        assert_eq!(gates.len(), 1);
+        assert_eq!(gates[0].command, "otto ci");
+    }
+}
@@ -0,0 +1,127 @@
+//! Detects hung loops from their heartbeat (last tool/LLM activity
+//! timestamp) and decides what the daemon should do about them: capture
+//! diagnostics, cancel, or restart, per [`WatchdogPolicy`].
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What to do with a loop that's gone silent past the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    Cancel,
+    Restart,
+}
+
+/// How long a loop may go without a heartbeat before the watchdog acts,
+/// and what it does when that happens.
+#[derive(Debug, Clone, Copy)]
+pub struct WatchdogPolicy {
+    pub silence_threshold: ChronoDuration,
+    pub action: WatchdogAction,
+}
+
+impl Default for WatchdogPolicy {
+    fn default() -> Self {
+        Self {
+            silence_threshold: ChronoDuration::minutes(10),
+            action: WatchdogAction::Restart,
+        }
+    }
+}
+
+/// A hung loop the watchdog decided to act on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchdogFinding {
+    pub loop_id: Uuid,
+    pub silent_for: ChronoDuration,
+    pub action: WatchdogAction,
+    /// Captured for the operator alert and postmortem: a one-line
+    /// `loop <id> silent for <duration>` summary built by
+    /// [`Watchdog::check`].
+    pub diagnostics: String,
+}
+
+/// Tracks per-loop heartbeats and, on [`Watchdog::check`], reports every
+/// loop that's gone silent beyond `policy`'s threshold.
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    policy: WatchdogPolicy,
+    last_activity: HashMap<Uuid, DateTime<Utc>>,
+}
+
+impl Watchdog {
+    pub fn new(policy: WatchdogPolicy) -> Self {
+        Self { policy, last_activity: HashMap::new() }
+    }
+
+    /// Records that `loop_id` did something (a tool call, an LLM
+    /// response) at `at`, resetting its silence clock.
+    pub fn record_activity(&mut self, loop_id: Uuid, at: DateTime<Utc>) {
+        self.last_activity.insert(loop_id, at);
+    }
+
+    pub fn stop_tracking(&mut self, loop_id: Uuid) {
+        self.last_activity.remove(&loop_id);
+    }
+
+    /// Returns a finding for every tracked loop whose last heartbeat is
+    /// older than the policy's threshold as of `now`.
+    pub fn check(&self, now: DateTime<Utc>) -> Vec<WatchdogFinding> {
+        self.last_activity
+            .iter()
+            .filter_map(|(&loop_id, &last_seen)| {
+                let silent_for = now - last_seen;
+                if silent_for >= self.policy.silence_threshold {
+                    Some(WatchdogFinding {
+                        loop_id,
+                        silent_for,
+                        action: self.policy.action,
+                        diagnostics: format!("loop {loop_id} silent for {silent_for}"),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fresh_heartbeat_produces_no_finding() {
+        let mut watchdog = Watchdog::new(WatchdogPolicy::default());
+        let loop_id = Uuid::new_v4();
+        let now = Utc::now();
+        watchdog.record_activity(loop_id, now);
+        assert!(watchdog.check(now).is_empty());
+    }
+
+    #[test]
+    fn a_loop_silent_past_the_threshold_is_reported() {
+        let policy = WatchdogPolicy { silence_threshold: ChronoDuration::minutes(5), action: WatchdogAction::Cancel };
+        let mut watchdog = Watchdog::new(policy);
+        let loop_id = Uuid::new_v4();
+        let now = Utc::now();
+        watchdog.record_activity(loop_id, now - ChronoDuration::minutes(6));
+
+        let findings = watchdog.check(now);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].loop_id, loop_id);
+        assert_eq!(findings[0].action, WatchdogAction::Cancel);
+    }
+
+    #[test]
+    fn stop_tracking_removes_a_loop_from_future_checks() {
+        let policy = WatchdogPolicy { silence_threshold: ChronoDuration::minutes(5), action: WatchdogAction::Cancel };
+        let mut watchdog = Watchdog::new(policy);
+        let loop_id = Uuid::new_v4();
+        let now = Utc::now();
+        watchdog.record_activity(loop_id, now - ChronoDuration::minutes(6));
+        watchdog.stop_tracking(loop_id);
+        assert!(watchdog.check(now).is_empty());
+    }
+}